@@ -0,0 +1,115 @@
+use std::{
+  sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Mutex,
+  },
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::logging;
+
+/* --------------------------------- Controller ------------------------------- */
+/* Hill climbing on throughput: every few seconds, step the number of active
+   workers one way; if the bytes/sec that follow are clearly worse, turn around.
+   Spinning disks settle at one or two readers, SSDs and RAIDs climb toward the
+   cap, without anyone having to know which is which. The copy side runs the
+   controller once per batch, so a sample carries over between runs and only
+   time spent inside one counts. */
+
+const SAMPLE: Duration = Duration::from_secs(2);
+
+// A step counts as worse below 95% of the previous rate (reads are noisy)
+const WORSE: f64 = 0.95;
+
+pub struct AdaptiveLimit {
+  limit: AtomicUsize,
+  max: usize,
+  climb: Mutex<Climb>,
+}
+
+// Hill-climbing state, kept between `run` calls
+struct Climb {
+  // Sample in progress
+  bytes: u64,
+  busy: Duration,
+  prev_rate: Option<f64>,
+  dir: isize,
+}
+
+impl AdaptiveLimit {
+  pub fn new(start: usize, max: usize) -> Self {
+    let max = max.max(1);
+    AdaptiveLimit {
+      limit: AtomicUsize::new(start.clamp(1, max)),
+      max,
+      climb: Mutex::new(Climb {
+        bytes: 0,
+        busy: Duration::ZERO,
+        prev_rate: None,
+        dir: 1,
+      }),
+    }
+  }
+
+  /// Workers are numbered 0..max; only the first `limit` take new work.
+  pub fn allows(&self, worker: usize) -> bool {
+    worker < self.limit.load(Ordering::SeqCst)
+  }
+
+  pub fn max(&self) -> usize {
+    self.max
+  }
+
+  pub fn current(&self) -> usize {
+    self.limit.load(Ordering::SeqCst)
+  }
+
+  /// Samples `bytes_done` and moves the limit until `finished` returns true.
+  pub fn run(&self, bytes_done: &AtomicU64, finished: &dyn Fn() -> bool) {
+    let mut last_bytes = bytes_done.load(Ordering::SeqCst);
+    let mut last_at = Instant::now();
+
+    loop {
+      // Poll in small steps so the pass doesn't wait out a whole sample at the end
+      let done = finished();
+      let bytes = bytes_done.load(Ordering::SeqCst);
+      if let Ok(mut c) = self.climb.lock() {
+        c.bytes += bytes.saturating_sub(last_bytes);
+        c.busy += last_at.elapsed();
+        if c.busy >= SAMPLE {
+          self.step(&mut c);
+        }
+      }
+      last_bytes = bytes;
+      last_at = Instant::now();
+      if done {
+        return;
+      }
+      thread::sleep(Duration::from_millis(100));
+    }
+  }
+
+  fn step(&self, c: &mut Climb) {
+    let rate = c.bytes as f64 / c.busy.as_secs_f64();
+    c.bytes = 0;
+    c.busy = Duration::ZERO;
+
+    if c.prev_rate.is_some_and(|p| rate < p * WORSE) {
+      c.dir = -c.dir;
+    }
+    let cur = self.current();
+    let mut next = (cur as isize + c.dir).clamp(1, self.max as isize) as usize;
+    if next == cur {
+      // Hit a bound; probe the other way next time
+      c.dir = -c.dir;
+      next = (cur as isize + c.dir).clamp(1, self.max as isize) as usize;
+    }
+    self.limit.store(next, Ordering::SeqCst);
+    logging::debug(&format!(
+      "adaptive workers: {cur} -> {next} ({:.1} MB/s)",
+      rate / 1_000_000.0
+    ));
+    c.prev_rate = Some(rate);
+  }
+}
@@ -0,0 +1,227 @@
+use std::{
+  fs,
+  io::{Read, Seek, SeekFrom},
+  path::Path,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+/* Just enough tag reading to file music by artist/album: ID3v2 (2.2-2.4) with an
+   ID3v1 fallback for MP3 and friends, and Vorbis comments for FLAC and Ogg
+   Vorbis/Opus. Anything unreadable simply has no tags. */
+
+#[derive(Debug, Clone, Default)]
+pub struct AudioTags {
+  pub artist: Option<String>,
+  pub album_artist: Option<String>,
+  pub album: Option<String>,
+  pub title: Option<String>,
+  pub track: Option<String>,
+}
+
+impl AudioTags {
+  fn is_empty(&self) -> bool {
+    self.artist.is_none() && self.album_artist.is_none() && self.album.is_none() && self.title.is_none()
+  }
+
+  // First value wins, so ID3v2 beats the v1 fallback
+  fn set(slot: &mut Option<String>, value: String) {
+    let v = value.trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string();
+    if slot.is_none() && !v.is_empty() {
+      *slot = Some(v);
+    }
+  }
+}
+
+// Tag blocks bigger than this are mostly cover art; we never need that far in
+const MAX_TAG_BYTES: usize = 4 * 1024 * 1024;
+
+pub fn read(path: &Path) -> Option<AudioTags> {
+  let mut f = fs::File::open(path).ok()?;
+  let mut magic = [0u8; 4];
+  f.read_exact(&mut magic).ok()?;
+  f.seek(SeekFrom::Start(0)).ok()?;
+
+  let mut tags = AudioTags::default();
+  match &magic {
+    b"fLaC" => {
+      read_flac(&mut f, &mut tags);
+    }
+    b"OggS" => {
+      read_ogg(&mut f, &mut tags);
+    }
+    _ => {
+      if &magic[..3] == b"ID3" {
+        read_id3v2(&mut f, &mut tags);
+      }
+      read_id3v1(&mut f, &mut tags);
+    }
+  }
+  (!tags.is_empty()).then_some(tags)
+}
+
+/* ----------------------------------- ID3 ------------------------------------ */
+
+fn syncsafe(b: &[u8]) -> usize {
+  b.iter().fold(0usize, |acc, &x| (acc << 7) | (x & 0x7f) as usize)
+}
+
+fn be(b: &[u8]) -> usize {
+  b.iter().fold(0usize, |acc, &x| (acc << 8) | x as usize)
+}
+
+fn read_id3v2(f: &mut fs::File, tags: &mut AudioTags) -> Option<()> {
+  let mut header = [0u8; 10];
+  f.read_exact(&mut header).ok()?;
+  let version = header[3];
+  let flags = header[5];
+  let size = syncsafe(&header[6..10]).min(MAX_TAG_BYTES);
+
+  let mut body = vec![0u8; size];
+  f.read_exact(&mut body).ok()?;
+
+  let mut pos = 0;
+  // Extended header
+  if flags & 0x40 != 0 && body.len() >= 4 {
+    pos = match version {
+      4 => syncsafe(&body[0..4]),
+      _ => be(&body[0..4]) + 4,
+    };
+  }
+
+  let (id_len, header_len) = if version == 2 { (3, 6) } else { (4, 10) };
+  while pos + header_len <= body.len() {
+    let id = &body[pos..pos + id_len];
+    if id[0] == 0 {
+      break; // padding
+    }
+    let frame_size = match version {
+      2 => be(&body[pos + 3..pos + 6]),
+      4 => syncsafe(&body[pos + 4..pos + 8]),
+      _ => be(&body[pos + 4..pos + 8]),
+    };
+    let start = pos + header_len;
+    let end = (start + frame_size).min(body.len());
+    let data = &body[start..end];
+    pos = end;
+
+    let slot = match id {
+      b"TPE1" | b"TP1" => &mut tags.artist,
+      b"TPE2" | b"TP2" => &mut tags.album_artist,
+      b"TALB" | b"TAL" => &mut tags.album,
+      b"TIT2" | b"TT2" => &mut tags.title,
+      b"TRCK" | b"TRK" => &mut tags.track,
+      _ => continue,
+    };
+    if let Some(text) = id3_text(data) {
+      AudioTags::set(slot, text);
+    }
+  }
+  Some(())
+}
+
+// Text frame: encoding byte, then the string (several values NUL-separated; keep the first)
+fn id3_text(data: &[u8]) -> Option<String> {
+  let (&enc, s) = data.split_first()?;
+  let text = match enc {
+    0 => s.iter().map(|&b| b as char).collect(),
+    1 | 2 => {
+      let (big_endian, s) = match s {
+        [0xfe, 0xff, rest @ ..] => (true, rest),
+        [0xff, 0xfe, rest @ ..] => (false, rest),
+        _ => (enc == 2, s),
+      };
+      let units: Vec<u16> = s
+        .chunks_exact(2)
+        .map(|c| if big_endian { u16::from_be_bytes([c[0], c[1]]) } else { u16::from_le_bytes([c[0], c[1]]) })
+        .take_while(|&u| u != 0)
+        .collect();
+      String::from_utf16_lossy(&units)
+    }
+    _ => String::from_utf8_lossy(s).to_string(),
+  };
+  text.split('\0').next().map(|s| s.to_string())
+}
+
+fn read_id3v1(f: &mut fs::File, tags: &mut AudioTags) -> Option<()> {
+  f.seek(SeekFrom::End(-128)).ok()?;
+  let mut b = [0u8; 128];
+  f.read_exact(&mut b).ok()?;
+  if &b[..3] != b"TAG" {
+    return None;
+  }
+  let field = |r: std::ops::Range<usize>| b[r].iter().map(|&c| c as char).collect::<String>();
+  AudioTags::set(&mut tags.title, field(3..33));
+  AudioTags::set(&mut tags.artist, field(33..63));
+  AudioTags::set(&mut tags.album, field(63..93));
+  // ID3v1.1: zero byte then track number at the end of the comment
+  if b[125] == 0 && b[126] != 0 {
+    AudioTags::set(&mut tags.track, b[126].to_string());
+  }
+  Some(())
+}
+
+/* ---------------------------------- Vorbis ---------------------------------- */
+
+fn le32(b: &[u8], pos: usize) -> Option<usize> {
+  let s = b.get(pos..pos + 4)?;
+  Some(u32::from_le_bytes([s[0], s[1], s[2], s[3]]) as usize)
+}
+
+// vendor string, then N "KEY=value" entries, all with little-endian lengths
+fn parse_vorbis_comments(b: &[u8], tags: &mut AudioTags) -> Option<()> {
+  let vendor_len = le32(b, 0)?;
+  let mut pos = 4 + vendor_len;
+  let count = le32(b, pos)?;
+  pos += 4;
+  for _ in 0..count {
+    let len = le32(b, pos)?;
+    pos += 4;
+    let entry = String::from_utf8_lossy(b.get(pos..pos + len)?).to_string();
+    pos += len;
+    let Some((key, value)) = entry.split_once('=') else {
+      continue;
+    };
+    let slot = match key.to_ascii_uppercase().as_str() {
+      "ARTIST" => &mut tags.artist,
+      "ALBUMARTIST" | "ALBUM ARTIST" => &mut tags.album_artist,
+      "ALBUM" => &mut tags.album,
+      "TITLE" => &mut tags.title,
+      "TRACKNUMBER" => &mut tags.track,
+      _ => continue,
+    };
+    AudioTags::set(slot, value.to_string());
+  }
+  Some(())
+}
+
+fn read_flac(f: &mut fs::File, tags: &mut AudioTags) -> Option<()> {
+  f.seek(SeekFrom::Start(4)).ok()?;
+  loop {
+    let mut h = [0u8; 4];
+    f.read_exact(&mut h).ok()?;
+    let last = h[0] & 0x80 != 0;
+    let len = be(&h[1..4]);
+    if h[0] & 0x7f == 4 {
+      let mut block = vec![0u8; len.min(MAX_TAG_BYTES)];
+      f.read_exact(&mut block).ok()?;
+      return parse_vorbis_comments(&block, tags);
+    }
+    if last {
+      return None;
+    }
+    f.seek(SeekFrom::Current(len as i64)).ok()?;
+  }
+}
+
+// The comment packet sits in the first few pages; look for its signature rather
+// than reassembling pages (tags that span a page boundary are rare outside cover art).
+fn read_ogg(f: &mut fs::File, tags: &mut AudioTags) -> Option<()> {
+  let mut head = vec![];
+  Read::take(f, 256 * 1024).read_to_end(&mut head).ok()?;
+  for sig in [&b"\x03vorbis"[..], &b"OpusTags"[..]] {
+    if let Some(i) = head.windows(sig.len()).position(|w| w == sig) {
+      return parse_vorbis_comments(&head[i + sig.len()..], tags);
+    }
+  }
+  None
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+  fs::{self, OpenOptions},
+  io::Write,
+  path::PathBuf,
+  sync::{Mutex, OnceLock},
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+// One line of audit.log. `hash` covers every other field plus `prev_hash`,
+// so editing or removing any earlier line breaks the chain from that point on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+  pub seq: u64,
+  pub at: String,
+  pub event: String, // transfer_start|offload_start|transfer_cancel|source_delete|card_checksum_mismatch|partials_removed|quota_trim|transfer_retry_elevated|session_verify|rollback
+  pub user: String,
+  pub details: serde_json::Value,
+  pub prev_hash: String,
+  pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditVerifyReport {
+  pub entries: u64,
+  pub valid: bool,
+  pub first_bad_seq: Option<u64>,
+  pub message: Option<String>,
+}
+
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/* ----------------------------------- State ---------------------------------- */
+
+struct AuditState {
+  path: Option<PathBuf>,
+  last: Option<(u64, String)>, // (seq, hash) of the tail entry
+}
+
+static AUDIT: OnceLock<Mutex<AuditState>> = OnceLock::new();
+
+fn state() -> &'static Mutex<AuditState> {
+  AUDIT.get_or_init(|| Mutex::new(AuditState { path: None, last: None }))
+}
+
+pub fn set_audit_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  let last = read_entries(&path)
+    .ok()
+    .and_then(|v| v.last().map(|e| (e.seq, e.hash.clone())));
+  if let Ok(mut g) = state().lock() {
+    g.path = Some(path);
+    g.last = last;
+  }
+}
+
+pub(crate) fn current_user() -> String {
+  std::env::var("USER")
+    .or_else(|_| std::env::var("USERNAME"))
+    .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn entry_hash(e: &AuditEntry) -> String {
+  let mut hasher = Sha256::new();
+  hasher.update(e.prev_hash.as_bytes());
+  hasher.update(e.seq.to_le_bytes());
+  hasher.update(e.at.as_bytes());
+  hasher.update(e.event.as_bytes());
+  hasher.update(e.user.as_bytes());
+  hasher.update(e.details.to_string().as_bytes());
+  hex::encode(hasher.finalize())
+}
+
+/* ---------------------------------- Writing --------------------------------- */
+
+/// Appends an event to the chain. Failures are logged, never fatal to a transfer.
+pub fn record(event: &str, details: serde_json::Value) {
+  let Ok(mut g) = state().lock() else {
+    return;
+  };
+  let Some(path) = g.path.clone() else {
+    return;
+  };
+
+  let (prev_seq, prev_hash) = g.last.clone().unwrap_or((0, GENESIS.to_string()));
+  let mut entry = AuditEntry {
+    seq: prev_seq + 1,
+    at: chrono::Local::now().to_rfc3339(),
+    event: event.to_string(),
+    user: current_user(),
+    details,
+    prev_hash,
+    hash: String::new(),
+  };
+  entry.hash = entry_hash(&entry);
+
+  let line = match serde_json::to_string(&entry) {
+    Ok(l) => l,
+    Err(e) => {
+      crate::logging::error(&format!("audit serialize error: {e}"));
+      return;
+    }
+  };
+
+  match OpenOptions::new().create(true).append(true).open(&path) {
+    Ok(mut f) => {
+      if let Err(e) = writeln!(f, "{line}").and_then(|_| f.sync_data()) {
+        crate::logging::error(&format!("audit write error: {e}"));
+        return;
+      }
+      g.last = Some((entry.seq, entry.hash));
+    }
+    Err(e) => crate::logging::error(&format!("audit open error: {e}")),
+  }
+}
+
+/* ---------------------------------- Reading --------------------------------- */
+
+fn read_entries(path: &PathBuf) -> Result<Vec<AuditEntry>, String> {
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+  let s = fs::read_to_string(path).map_err(|e| format!("audit read error: {e}"))?;
+  let mut out = vec![];
+  for (i, line) in s.lines().enumerate() {
+    if line.trim().is_empty() {
+      continue;
+    }
+    let e: AuditEntry =
+      serde_json::from_str(line).map_err(|e| format!("audit parse error on line {}: {e}", i + 1))?;
+    out.push(e);
+  }
+  Ok(out)
+}
+
+fn audit_path() -> Result<PathBuf, String> {
+  state()
+    .lock()
+    .ok()
+    .and_then(|g| g.path.clone())
+    .ok_or_else(|| "audit log not initialised".to_string())
+}
+
+pub fn read_log() -> Result<Vec<AuditEntry>, String> {
+  read_entries(&audit_path()?)
+}
+
+pub fn verify_log() -> Result<AuditVerifyReport, String> {
+  let entries = match read_entries(&audit_path()?) {
+    Ok(v) => v,
+    Err(e) => {
+      return Ok(AuditVerifyReport {
+        entries: 0,
+        valid: false,
+        first_bad_seq: None,
+        message: Some(e),
+      })
+    }
+  };
+
+  let mut prev_hash = GENESIS.to_string();
+  let mut expected_seq = 1u64;
+  for e in &entries {
+    let problem = if e.seq != expected_seq {
+      Some(format!("expected seq {expected_seq}, found {}", e.seq))
+    } else if e.prev_hash != prev_hash {
+      Some("prev_hash does not match preceding entry".to_string())
+    } else if entry_hash(e) != e.hash {
+      Some("entry hash mismatch".to_string())
+    } else {
+      None
+    };
+
+    if let Some(msg) = problem {
+      return Ok(AuditVerifyReport {
+        entries: entries.len() as u64,
+        valid: false,
+        first_bad_seq: Some(e.seq),
+        message: Some(msg),
+      });
+    }
+
+    prev_hash = e.hash.clone();
+    expected_seq += 1;
+  }
+
+  Ok(AuditVerifyReport {
+    entries: entries.len() as u64,
+    valid: true,
+    first_bad_seq: None,
+    message: None,
+  })
+}
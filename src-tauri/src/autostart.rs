@@ -0,0 +1,113 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+use crate::{daemon::BACKGROUND_ARG, logging};
+
+/* --------------------------------- Autostart -------------------------------- */
+/* Launch at login registers the app to start with --background, which hides the
+   window and leaves the tray, the card watcher and auto-import running. Uses the
+   per-user mechanism of each OS: a LaunchAgent on macOS, an XDG autostart entry
+   on Linux, the HKCU Run key on Windows. */
+
+const LABEL: &str = "com.transferpilot.app";
+const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+fn exe() -> Result<String, String> {
+  env::current_exe()
+    .map(|p| p.to_string_lossy().to_string())
+    .map_err(|e| format!("current exe error: {e}"))
+}
+
+fn home() -> Result<PathBuf, String> {
+  env::var_os("HOME")
+    .map(PathBuf::from)
+    .ok_or_else(|| "HOME is not set".to_string())
+}
+
+fn entry_path() -> Result<PathBuf, String> {
+  if cfg!(target_os = "macos") {
+    Ok(home()?.join("Library/LaunchAgents").join(format!("{LABEL}.plist")))
+  } else {
+    let config = env::var_os("XDG_CONFIG_HOME")
+      .map(PathBuf::from)
+      .map_or_else(|| home().map(|h| h.join(".config")), Ok)?;
+    Ok(config.join("autostart").join("transferpilot.desktop"))
+  }
+}
+
+fn xml_escape(s: &str) -> String {
+  s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn entry_contents(exe: &str) -> String {
+  if cfg!(target_os = "macos") {
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{LABEL}</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{}</string>
+    <string>{BACKGROUND_ARG}</string>
+  </array>
+  <key>RunAtLoad</key>
+  <true/>
+</dict>
+</plist>
+"#,
+      xml_escape(exe)
+    )
+  } else {
+    format!(
+      "[Desktop Entry]\nType=Application\nName=TransferPilot\nExec=\"{}\" {BACKGROUND_ARG}\nX-GNOME-Autostart-enabled=true\n",
+      exe.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+  }
+}
+
+pub fn is_enabled() -> bool {
+  if cfg!(windows) {
+    Command::new("reg")
+      .args(["query", RUN_KEY, "/v", "TransferPilot"])
+      .output()
+      .map(|o| o.status.success())
+      .unwrap_or(false)
+  } else {
+    entry_path().map(|p| p.exists()).unwrap_or(false)
+  }
+}
+
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+  if cfg!(windows) {
+    let out = if enabled {
+      let value = format!("\"{}\" {BACKGROUND_ARG}", exe()?);
+      Command::new("reg")
+        .args(["add", RUN_KEY, "/v", "TransferPilot", "/t", "REG_SZ", "/d", &value, "/f"])
+        .output()
+    } else {
+      Command::new("reg")
+        .args(["delete", RUN_KEY, "/v", "TransferPilot", "/f"])
+        .output()
+    }
+    .map_err(|e| format!("reg failed to start: {e}"))?;
+    // Deleting a value that isn't there is fine
+    if !out.status.success() && enabled {
+      let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+      return Err(format!("autostart registry error: {stderr}"));
+    }
+  } else {
+    let path = entry_path()?;
+    if enabled {
+      if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("autostart dir error: {e}"))?;
+      }
+      fs::write(&path, entry_contents(&exe()?)).map_err(|e| format!("autostart write error: {e}"))?;
+    } else if path.exists() {
+      fs::remove_file(&path).map_err(|e| format!("autostart remove error: {e}"))?;
+    }
+  }
+  logging::info(&format!("launch at login {}", if enabled { "enabled" } else { "disabled" }));
+  Ok(())
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+// Sent in the progress payload while copying
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IoStats {
+  // Throughput while each side was busy (bytes/sec), i.e. what that device manages
+  pub read_bytes_per_sec: u64,
+  pub write_bytes_per_sec: u64,
+  // "source" | "destination" | "balanced"
+  pub bottleneck: String,
+  // e.g. "destination-limited at 38 MB/s"
+  pub summary: String,
+}
+
+/* ----------------------------------- Meter ---------------------------------- */
+/* The copy loop alternates read and write, so whichever call the loop spends more
+   time blocked in is the limit. Counts cover a sliding window of a few seconds so
+   a slow patch (a card reader hitting fragmented files) shows up quickly. */
+
+const WINDOW: Duration = Duration::from_secs(3);
+
+// Within 20% of each other counts as balanced
+const MARGIN: f64 = 1.2;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Side {
+  bytes: u64,
+  busy: Duration,
+}
+
+impl Side {
+  fn rate(&self) -> u64 {
+    let secs = self.busy.as_secs_f64();
+    if secs <= 0.0 {
+      0
+    } else {
+      (self.bytes as f64 / secs) as u64
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct IoMeter {
+  started: Instant,
+  read: Side,
+  write: Side,
+  // Last full window, reported until the current one has enough in it
+  last: Option<(Side, Side)>,
+}
+
+impl Default for IoMeter {
+  fn default() -> Self {
+    IoMeter {
+      started: Instant::now(),
+      read: Side::default(),
+      write: Side::default(),
+      last: None,
+    }
+  }
+}
+
+impl IoMeter {
+  pub fn read(&mut self, bytes: u64, took: Duration) {
+    self.roll();
+    self.read.bytes += bytes;
+    self.read.busy += took;
+  }
+
+  pub fn write(&mut self, bytes: u64, took: Duration) {
+    self.roll();
+    self.write.bytes += bytes;
+    self.write.busy += took;
+  }
+
+  fn roll(&mut self) {
+    if self.started.elapsed() >= WINDOW {
+      self.last = Some((self.read, self.write));
+      self.read = Side::default();
+      self.write = Side::default();
+      self.started = Instant::now();
+    }
+  }
+
+  pub fn stats(&self) -> Option<IoStats> {
+    let (read, write) = match self.last {
+      Some(last) if self.started.elapsed() < WINDOW / 2 => last,
+      _ => (self.read, self.write),
+    };
+    if read.bytes == 0 || write.bytes == 0 {
+      return None;
+    }
+    let (r, w) = (read.busy.as_secs_f64(), write.busy.as_secs_f64());
+    let (bottleneck, rate) = if r > w * MARGIN {
+      ("source", read.rate())
+    } else if w > r * MARGIN {
+      ("destination", write.rate())
+    } else {
+      ("balanced", read.rate().min(write.rate()))
+    };
+    let mb = rate / 1_000_000;
+    let summary = match bottleneck {
+      "balanced" => format!("source and destination both near {mb} MB/s"),
+      side => format!("{side}-limited at {mb} MB/s"),
+    };
+    Some(IoStats {
+      read_bytes_per_sec: read.rate(),
+      write_bytes_per_sec: write.rate(),
+      bottleneck: bottleneck.to_string(),
+      summary,
+    })
+  }
+}
@@ -0,0 +1,75 @@
+use std::{
+  collections::{BTreeMap, HashMap},
+  path::{Path, PathBuf},
+};
+
+use crate::exif;
+
+/* ---------------------------------- Bursts ---------------------------------- */
+/* A burst is a run of consecutive frames in one folder (by file name) shot no
+   more than a second apart. RAW+JPEG pairs share a stem and count as one frame.
+   Each burst gets a label from its first frame, e.g. "Burst_DSC01234", used as a
+   subfolder or a name prefix at the destination (see layout_rel). */
+
+// Fewer frames than this is a double tap, not a burst
+const MIN_FRAMES: usize = 3;
+const MAX_GAP_SECS: i64 = 1;
+
+const STILL_EXTS: &[&str] = &[
+  "jpg", "jpeg", "heic", "heif", "tif", "tiff", "dng", "arw", "nef", "cr2", "orf", "rw2", "pef", "raf",
+];
+
+/// Burst label for every file that belongs to one.
+pub fn detect<'a>(paths: impl IntoIterator<Item = &'a Path>) -> HashMap<PathBuf, String> {
+  // folder -> stem -> files, in name order
+  let mut folders: BTreeMap<PathBuf, BTreeMap<String, Vec<PathBuf>>> = BTreeMap::new();
+  for p in paths {
+    let ext = p
+      .extension()
+      .and_then(|s| s.to_str())
+      .unwrap_or("")
+      .to_lowercase();
+    if !STILL_EXTS.contains(&ext.as_str()) {
+      continue;
+    }
+    let (Some(dir), Some(stem)) = (p.parent(), p.file_stem()) else {
+      continue;
+    };
+    folders
+      .entry(dir.to_path_buf())
+      .or_default()
+      .entry(stem.to_string_lossy().to_string())
+      .or_default()
+      .push(p.to_path_buf());
+  }
+
+  let mut out = HashMap::new();
+  for frames in folders.values() {
+    // (stem, files, capture time) for frames that have one
+    let timed: Vec<(&String, &Vec<PathBuf>, i64)> = frames
+      .iter()
+      .filter_map(|(stem, files)| {
+        let t = files.iter().find_map(|f| exif::read(f)?.taken_at)?;
+        Some((stem, files, t.timestamp()))
+      })
+      .collect();
+
+    let mut start = 0;
+    for i in 1..=timed.len() {
+      let continues = i < timed.len() && (timed[i].2 - timed[i - 1].2).abs() <= MAX_GAP_SECS;
+      if continues {
+        continue;
+      }
+      if i - start >= MIN_FRAMES {
+        let label = format!("Burst_{}", timed[start].0);
+        for (_, files, _) in &timed[start..i] {
+          for f in files.iter() {
+            out.insert(f.clone(), label.clone());
+          }
+        }
+      }
+      start = i;
+    }
+  }
+  out
+}
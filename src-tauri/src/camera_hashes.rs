@@ -0,0 +1,252 @@
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::{
+  audit,
+  checksum::{self, Algo},
+  logging,
+  watchdog::Watchdog,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+/* Cameras and on-set tools leave Media Hash Lists next to the clips: MHL v1
+   (<hash><file>…</file><md5>…</md5></hash>) or ASC MHL in an ascmhl/ folder
+   (<hash><path size="…">…</path><xxh64>…</xxh64></hash>). Those hashes were taken
+   when the clip was recorded, so checking against them catches corruption that
+   happened on the card itself, which a source-vs-copy compare can't see. */
+
+// Biggest checksum file we'll parse; real ones are a few MB at most
+const MAX_HASH_FILE_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct CameraHash {
+  pub algo: Algo,
+  pub digest: String,
+  pub size: Option<u64>,
+  // The checksum file this came from
+  pub hash_file: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct CameraHashes {
+  by_path: HashMap<PathBuf, CameraHash>,
+  // Checksum files that contributed at least one hash
+  pub files: Vec<PathBuf>,
+}
+
+impl CameraHashes {
+  pub fn get(&self, src: &Path) -> Option<&CameraHash> {
+    self.by_path.get(src)
+  }
+
+  pub fn len(&self) -> usize {
+    self.by_path.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.by_path.is_empty()
+  }
+}
+
+/* --------------------------------- Detection -------------------------------- */
+
+/// Finds .mhl/.xml checksum files among `paths` (the scanned selection) and
+/// collects every hash they list, keyed by the absolute path of the clip.
+pub fn detect<'a>(paths: impl IntoIterator<Item = &'a Path>) -> CameraHashes {
+  let mut out = CameraHashes::default();
+
+  for p in paths {
+    let ext = p
+      .extension()
+      .and_then(|s| s.to_str())
+      .unwrap_or("")
+      .to_lowercase();
+    if ext != "mhl" && ext != "xml" {
+      continue;
+    }
+    if fs::metadata(p).map(|m| m.len() > MAX_HASH_FILE_BYTES).unwrap_or(true) {
+      continue;
+    }
+    let Ok(text) = fs::read_to_string(p) else {
+      continue;
+    };
+
+    let parent = p.parent().unwrap_or_else(|| Path::new("."));
+    // ASC MHL paths are relative to the folder holding ascmhl/
+    let base = if parent.file_name().and_then(|s| s.to_str()) == Some("ascmhl") {
+      parent.parent().unwrap_or(parent)
+    } else {
+      parent
+    };
+
+    let hashes = parse_hash_list(&text);
+    if hashes.is_empty() {
+      continue;
+    }
+    logging::debug(&format!("{}: {} camera hash(es)", p.display(), hashes.len()));
+    for (rel, algo, digest, size) in hashes {
+      out.by_path.insert(
+        base.join(rel),
+        CameraHash {
+          algo,
+          digest,
+          size,
+          hash_file: p.to_path_buf(),
+        },
+      );
+    }
+    out.files.push(p.to_path_buf());
+  }
+
+  out
+}
+
+/* ---------------------------------- Parsing --------------------------------- */
+
+// (relative path, algo, lowercase hex digest, size) for every <hash> entry we can check
+fn parse_hash_list(text: &str) -> Vec<(String, Algo, String, Option<u64>)> {
+  let mut out = vec![];
+  let mut rest = text;
+
+  while let Some(start) = find_open_tag(rest, "hash") {
+    let Some(end) = rest[start..].find("</hash>") else {
+      break;
+    };
+    let block = &rest[start..start + end];
+    rest = &rest[start + end + "</hash>".len()..];
+
+    let (rel, attr_size) = match tag(block, "path").or_else(|| tag(block, "file")) {
+      Some((attrs, text)) => (unescape(text.trim()), attr_value(attrs, "size")),
+      None => continue,
+    };
+    let size = attr_size
+      .or_else(|| tag(block, "size").map(|(_, t)| t.trim().to_string()))
+      .and_then(|s| s.parse().ok());
+
+    // Strongest algorithm we can compute wins
+    let digest = [
+      ("md5", Algo::Md5),
+      ("xxh64", Algo::Xxh64),
+      ("xxhash64be", Algo::Xxh64),
+      ("xxh3", Algo::Xxh3),
+    ]
+    .iter()
+    .find_map(|(name, algo)| tag(block, name).map(|(_, t)| (*algo, t.trim().to_lowercase())));
+
+    if let Some((algo, digest)) = digest.filter(|(_, d)| !d.is_empty()) {
+      let rel = rel.replace('\\', "/");
+      out.push((rel.trim_start_matches("./").to_string(), algo, digest, size));
+    }
+  }
+
+  out
+}
+
+// Byte offset of `<name>` or `<name attr…>` (not `<namefoo>`)
+fn find_open_tag(s: &str, name: &str) -> Option<usize> {
+  let needle = format!("<{name}");
+  let mut from = 0;
+  while let Some(i) = s[from..].find(&needle) {
+    let at = from + i;
+    match s[at + needle.len()..].chars().next() {
+      Some('>') | Some(' ') | Some('\t') | Some('\n') | Some('\r') => return Some(at),
+      _ => from = at + needle.len(),
+    }
+  }
+  None
+}
+
+// (attributes, inner text) of the first <name …>…</name> in `block`
+fn tag<'a>(block: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+  let open = find_open_tag(block, name)?;
+  let after_name = open + name.len() + 1;
+  let gt = after_name + block[after_name..].find('>')?;
+  let close = format!("</{name}>");
+  let end = gt + 1 + block[gt + 1..].find(&close)?;
+  Some((&block[after_name..gt], &block[gt + 1..end]))
+}
+
+fn attr_value(attrs: &str, name: &str) -> Option<String> {
+  let needle = format!("{name}=\"");
+  let start = attrs.find(&needle)? + needle.len();
+  let end = start + attrs[start..].find('"')?;
+  Some(attrs[start..end].to_string())
+}
+
+fn unescape(s: &str) -> String {
+  s.replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&apos;", "'")
+    .replace("&amp;", "&")
+}
+
+/* ---------------------------------- Verify ---------------------------------- */
+
+/// Checks a copied clip against its camera hash. `source_checksum` is the digest
+/// the copy verify already computed (in `verify_mode`), reused when the camera used
+/// the same algorithm; otherwise the verified copy is hashed rather than the card.
+/// Returns the error to record on the row, if any.
+#[allow(clippy::too_many_arguments)]
+pub fn check(
+  hash: &CameraHash,
+  src: &Path,
+  dst: &Path,
+  bytes: u64,
+  source_checksum: Option<&str>,
+  verify_mode: &str,
+  bypass_cache: bool,
+  watchdog: &Watchdog,
+) -> Option<String> {
+  let file_name = hash
+    .hash_file
+    .file_name()
+    .map(|s| s.to_string_lossy().to_string())
+    .unwrap_or_default();
+
+  let actual = match source_checksum.filter(|_| verify_mode == hash.algo.name()) {
+    Some(c) => c.to_string(),
+    None => match checksum::hash_file(dst, hash.algo, bypass_cache, watchdog) {
+      Ok(c) => c,
+      Err(e) => return Some(format!("camera checksum error: {e}")),
+    },
+  };
+
+  let size_ok = !matches!(hash.size, Some(s) if s != bytes);
+  if size_ok && actual == hash.digest {
+    return None;
+  }
+
+  logging::warn(&format!(
+    "{}: does not match camera {} in {}",
+    src.display(),
+    hash.algo.name(),
+    hash.hash_file.display()
+  ));
+  audit::record(
+    "card_checksum_mismatch",
+    serde_json::json!({
+      "source": src.to_string_lossy(),
+      "hash_file": hash.hash_file.to_string_lossy(),
+      "algo": hash.algo.name(),
+      "expected": hash.digest,
+      "actual": actual,
+      "expected_size": hash.size,
+      "size": bytes,
+    }),
+  );
+
+  // A verified copy matches the card, so the card's data is what changed
+  let what = if Algo::from_verify_mode(verify_mode).is_some() {
+    "card corruption"
+  } else {
+    "camera checksum mismatch"
+  };
+  Some(format!(
+    "{what}: {} does not match {file_name}",
+    if size_ok { hash.algo.name() } else { "size" }
+  ))
+}
@@ -0,0 +1,124 @@
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::{io::Read, path::Path};
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
+
+use crate::{uncached, watchdog::Watchdog};
+
+/* ----------------------------------- Algos ---------------------------------- */
+
+/// Recommended verify mode: xxh3 runs at multiple GB/s, so it costs little more
+/// than a size check while still catching corrupted bytes.
+pub const DEFAULT_VERIFY_MODE: &str = "xxh3";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algo {
+  Xxh3,
+  Xxh64,
+  Blake3,
+  Sha256,
+  Md5,
+  Crc32,
+}
+
+impl Algo {
+  /// Maps a verify_mode string to a hash algorithm; None for "none"/"size".
+  pub fn from_verify_mode(mode: &str) -> Option<Self> {
+    match mode {
+      "xxh3" => Some(Algo::Xxh3),
+      "xxh64" => Some(Algo::Xxh64),
+      "blake3" => Some(Algo::Blake3),
+      "sha256" => Some(Algo::Sha256),
+      "md5" => Some(Algo::Md5),
+      "crc32" => Some(Algo::Crc32),
+      _ => None,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Algo::Xxh3 => "xxh3",
+      Algo::Xxh64 => "xxh64",
+      Algo::Blake3 => "blake3",
+      Algo::Sha256 => "sha256",
+      Algo::Md5 => "md5",
+      Algo::Crc32 => "crc32",
+    }
+  }
+}
+
+/* ---------------------------------- Hasher ---------------------------------- */
+
+// BLAKE3 hashes chunks of this size or more on several threads
+const BLAKE3_PARALLEL_MIN: usize = 128 * 1024;
+
+pub enum Hasher {
+  Xxh3(Box<Xxh3>),
+  Xxh64(Xxh64),
+  Blake3(Box<blake3::Hasher>),
+  Sha256(Sha256),
+  Md5(Md5),
+  Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+  pub fn new(algo: Algo) -> Self {
+    match algo {
+      Algo::Xxh3 => Hasher::Xxh3(Box::new(Xxh3::new())),
+      Algo::Xxh64 => Hasher::Xxh64(Xxh64::new(0)),
+      Algo::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+      Algo::Sha256 => Hasher::Sha256(Sha256::new()),
+      Algo::Md5 => Hasher::Md5(Md5::new()),
+      Algo::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+    }
+  }
+
+  pub fn update(&mut self, data: &[u8]) {
+    match self {
+      Hasher::Xxh3(h) => h.update(data),
+      Hasher::Xxh64(h) => h.update(data),
+      // Spread over the rayon pool; below this size the threads cost more than they save
+      Hasher::Blake3(h) if data.len() >= BLAKE3_PARALLEL_MIN => {
+        h.update_rayon(data);
+      }
+      Hasher::Blake3(h) => {
+        h.update(data);
+      }
+      Hasher::Sha256(h) => h.update(data),
+      Hasher::Md5(h) => h.update(data),
+      Hasher::Crc32(h) => h.update(data),
+    }
+  }
+
+  /// Lowercase hex digest (crc32 as 8 hex digits, as camera checksum files write it).
+  pub fn finalize(self) -> String {
+    match self {
+      Hasher::Xxh3(h) => format!("{:016x}", h.digest()),
+      Hasher::Xxh64(h) => format!("{:016x}", h.digest()),
+      Hasher::Blake3(h) => h.finalize().to_hex().to_string(),
+      Hasher::Sha256(h) => hex::encode(h.finalize()),
+      Hasher::Md5(h) => hex::encode(h.finalize()),
+      Hasher::Crc32(h) => format!("{:08x}", h.finalize()),
+    }
+  }
+}
+
+/* ----------------------------------- Files ---------------------------------- */
+
+pub fn hash_file(path: &Path, algo: Algo, bypass_cache: bool, watchdog: &Watchdog) -> Result<String, String> {
+  let mut f = uncached::open(path, bypass_cache)?;
+  let mut hasher = Hasher::new(algo);
+  // Bigger reads give BLAKE3's threads more to split
+  let buf_len = if algo == Algo::Blake3 { 8 * 1024 * 1024 } else { 1024 * 1024 };
+  let mut aligned = uncached::AlignedBuf::new(buf_len);
+  let buf = aligned.as_mut_slice();
+  loop {
+    let n = f.read(buf).map_err(|e| format!("read error: {e}"))?;
+    if n == 0 {
+      break;
+    }
+    hasher.update(&buf[..n]);
+    watchdog.tick();
+  }
+  Ok(hasher.finalize())
+}
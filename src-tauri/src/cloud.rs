@@ -0,0 +1,54 @@
+use std::{fs::Metadata, path::Path};
+
+/* ---------------------------- Cloud placeholders ---------------------------- */
+/* OneDrive, iCloud Drive and Dropbox can leave "online-only" stubs on disk. Their
+   metadata reports the real size, but reading one first downloads it (slowly, or
+   not at all when offline), and older iCloud stubs are tiny hidden
+   ".name.ext.icloud" files standing in for the real one. The cloud_placeholders
+   option decides what a transfer does with them:
+     "hydrate" (default) read through and let the OS download them,
+     "skip"    record them as skipped,
+     "fail"    refuse to start while any are selected. */
+
+pub const POLICIES: &[&str] = &["hydrate", "skip", "fail"];
+
+// macOS: file contents live only in the cloud (sys/stat.h SF_DATALESS)
+#[cfg(target_os = "macos")]
+fn is_dataless(meta: &Metadata) -> bool {
+  use std::os::macos::fs::MetadataExt;
+  const SF_DATALESS: u32 = 0x4000_0000;
+  meta.st_flags() & SF_DATALESS != 0
+}
+
+// Windows: cloud files provider stubs (FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS,
+// FILE_ATTRIBUTE_RECALL_ON_OPEN, FILE_ATTRIBUTE_OFFLINE)
+#[cfg(windows)]
+fn is_dataless(meta: &Metadata) -> bool {
+  use std::os::windows::fs::MetadataExt;
+  const RECALL: u32 = 0x0040_0000 | 0x0004_0000 | 0x0000_1000;
+  meta.file_attributes() & RECALL != 0
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn is_dataless(_meta: &Metadata) -> bool {
+  false
+}
+
+fn is_icloud_stub(path: &Path) -> bool {
+  path
+    .file_name()
+    .map(|n| n.to_string_lossy())
+    .is_some_and(|n| n.starts_with('.') && n.ends_with(".icloud"))
+}
+
+/// "dataless" (hydrates when read) or "icloud_stub" (can't be hydrated by
+/// reading; the real file has to be downloaded first); None for ordinary files.
+pub fn placeholder_kind(path: &Path, meta: &Metadata) -> Option<&'static str> {
+  if is_icloud_stub(path) {
+    Some("icloud_stub")
+  } else if is_dataless(meta) {
+    Some("dataless")
+  } else {
+    None
+  }
+}
@@ -0,0 +1,135 @@
+use std::{path::Path, process::Command};
+use tauri::AppHandle;
+
+use crate::{
+  device,
+  events::{self, CompletionEvent},
+  logging,
+  transfer::TransferOptions,
+  TransferSummary,
+};
+
+/* ---------------------------------- Actions --------------------------------- */
+
+/// Shows `path` in Finder / Explorer / the default file manager.
+pub fn reveal(path: &Path) -> Result<(), String> {
+  let mut cmd = if cfg!(target_os = "macos") {
+    let mut c = Command::new("open");
+    c.arg(path);
+    c
+  } else if cfg!(windows) {
+    let mut c = Command::new("explorer");
+    c.arg(path);
+    c
+  } else {
+    let mut c = Command::new("xdg-open");
+    c.arg(path);
+    c
+  };
+  cmd.spawn().map(|_| ()).map_err(|e| format!("reveal failed: {e}"))
+}
+
+// The hook runs through the shell with the session described in env vars, e.g.
+// `rsync -a "$TRANSFERPILOT_SESSION_DIR" nas:/ingest/`.
+fn run_hook(hook: &str, job_id: &str, summary: &TransferSummary, dest: &str) -> Result<(), String> {
+  let mut cmd = if cfg!(windows) {
+    let mut c = Command::new("cmd");
+    c.args(["/C", hook]);
+    c
+  } else {
+    let mut c = Command::new("sh");
+    c.args(["-c", hook]);
+    c
+  };
+  let out = cmd
+    .env("TRANSFERPILOT_JOB_ID", job_id)
+    .env("TRANSFERPILOT_SESSION_DIR", &summary.output_session_dir)
+    .env("TRANSFERPILOT_DEST", dest)
+    .env("TRANSFERPILOT_TOTAL_FILES", summary.total_files.to_string())
+    .env("TRANSFERPILOT_TOTAL_BYTES", summary.total_bytes.to_string())
+    .env("TRANSFERPILOT_ERROR_FILES", summary.error_files.to_string())
+    .output()
+    .map_err(|e| format!("hook failed to start: {e}"))?;
+  if out.status.success() {
+    Ok(())
+  } else {
+    let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+    Err(format!("hook exited with {}: {stderr}", out.status))
+  }
+}
+
+// Ejects every destination the job wrote to
+fn eject_all(dests: &[String]) -> Result<(), String> {
+  dests.iter().try_for_each(|d| {
+    let mount = device::mount_point_for(d).unwrap_or_else(|| d.clone());
+    device::eject(&mount)
+  })
+}
+
+fn report(app: &AppHandle, job_id: &str, action: &str, res: Result<(), String>) {
+  match &res {
+    Ok(_) => logging::info(&format!("job {job_id}: on-complete {action} ok")),
+    Err(e) => logging::warn(&format!("job {job_id}: on-complete {action}: {e}")),
+  }
+  events::emit(
+    app,
+    events::ON_COMPLETE,
+    &CompletionEvent {
+      job_id: job_id.to_string(),
+      action: action.to_string(),
+      ok: res.is_ok(),
+      error: res.err(),
+    },
+  );
+}
+
+/// Runs the `on_complete` action for a job that finished with phase "done", then
+/// `auto_eject` if the run had no errors. `dests` are the destination roots the
+/// job wrote to (two for an offload); the summary describes the first. Runs on
+/// its own thread so a slow hook or eject doesn't hold up the command's return.
+pub fn run(
+  app: &AppHandle,
+  job_id: &str,
+  options: &TransferOptions,
+  summary: &TransferSummary,
+  dests: &[String],
+  error_files: u64,
+) {
+  let action = options.on_complete.clone();
+  let has_action = !action.is_empty() && action != "none";
+  // on_complete = "eject" already covers it
+  let auto_eject = options.auto_eject && action != "eject";
+  if !has_action && !auto_eject {
+    return;
+  }
+  let app = app.clone();
+  let job_id = job_id.to_string();
+  let hook = options.on_complete_hook.clone();
+  let summary = summary.clone();
+  let dests = dests.to_vec();
+
+  std::thread::spawn(move || {
+    if has_action {
+      let res = match action.as_str() {
+        "reveal" => reveal(Path::new(&summary.output_session_dir)),
+        "hook" => match hook.as_deref().filter(|h| !h.trim().is_empty()) {
+          Some(h) => run_hook(h, &job_id, &summary, dests.first().map(|s| s.as_str()).unwrap_or("")),
+          None => Err("on_complete is \"hook\" but no on_complete_hook is set".to_string()),
+        },
+        "eject" => eject_all(&dests),
+        other => Err(format!("unknown on_complete action: {other}")),
+      };
+      report(&app, &job_id, &action, res);
+    }
+
+    if auto_eject {
+      // A run with failures keeps its drive mounted so the files can be retried
+      let res = if error_files == 0 {
+        eject_all(&dests)
+      } else {
+        Err(format!("not ejected: {error_files} file(s) failed"))
+      };
+      report(&app, &job_id, "auto_eject", res);
+    }
+  });
+}
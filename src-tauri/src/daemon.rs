@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  env, fs,
+  path::PathBuf,
+  sync::{Mutex, OnceLock},
+};
+use tauri::{
+  menu::{Menu, MenuItem},
+  tray::TrayIconBuilder,
+  AppHandle, Manager,
+};
+
+use crate::{events, jobs::Jobs, logging, profiles::Profile, transfer, PickedItem};
+
+/* ---------------------------------- Types ----------------------------------- */
+/* Background mode for unattended ingest stations: closing the window only hides
+   it, the process keeps running in the tray, and card auto-import rules start
+   their transfers in the backend instead of waiting for the UI to apply them.
+   The GUI attaches by reading `get_daemon_status` and listening to the usual
+   engine events. */
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonSettings {
+  // Closing the window hides it instead of quitting
+  pub keep_running: bool,
+  // A mounted card bound to a profile with a destination starts copying on its own
+  pub auto_import: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonStatus {
+  pub settings: DaemonSettings,
+  // Launched with --background (no window shown at startup)
+  pub background_launch: bool,
+  pub running_jobs: Vec<String>,
+}
+
+pub const BACKGROUND_ARG: &str = "--background";
+
+pub fn is_background_launch() -> bool {
+  env::args().any(|a| a == BACKGROUND_ARG)
+}
+
+/// Whether closing the window should hide it. A login launch always stays resident,
+/// otherwise the first close would stop the watchers it was started to run.
+pub fn keeps_running() -> bool {
+  settings().keep_running || is_background_launch()
+}
+
+/* ----------------------------------- Store ---------------------------------- */
+
+static DAEMON_FILE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Option<PathBuf>> {
+  DAEMON_FILE.get_or_init(|| Mutex::new(None))
+}
+
+pub fn set_daemon_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  if let Ok(mut g) = slot().lock() {
+    *g = Some(path);
+  }
+}
+
+fn daemon_path() -> Result<PathBuf, String> {
+  slot()
+    .lock()
+    .ok()
+    .and_then(|g| g.clone())
+    .ok_or_else(|| "daemon settings not initialised".to_string())
+}
+
+pub fn settings() -> DaemonSettings {
+  daemon_path()
+    .ok()
+    .and_then(|p| fs::read_to_string(p).ok())
+    .and_then(|s| serde_json::from_str(&s).ok())
+    .unwrap_or_default()
+}
+
+pub fn save_settings(settings: &DaemonSettings) -> Result<(), String> {
+  let json = serde_json::to_string_pretty(settings).map_err(|e| format!("daemon settings json error: {e}"))?;
+  fs::write(daemon_path()?, json).map_err(|e| format!("daemon settings write error: {e}"))
+}
+
+pub fn status(jobs: &Jobs) -> DaemonStatus {
+  DaemonStatus {
+    settings: settings(),
+    background_launch: is_background_launch(),
+    running_jobs: jobs.ids(),
+  }
+}
+
+/* ------------------------------------ Tray ---------------------------------- */
+
+pub fn show_main_window(app: &AppHandle) {
+  if let Some(w) = app.get_webview_window("main") {
+    let _ = w.show();
+    let _ = w.set_focus();
+  }
+}
+
+/// Tray icon with Show / Quit, so a hidden window can be brought back and the
+/// background process can be stopped.
+pub fn install_tray(app: &AppHandle) -> tauri::Result<()> {
+  let show = MenuItem::with_id(app, "show", "Show TransferPilot", true, None::<&str>)?;
+  let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+  let menu = Menu::with_items(app, &[&show, &quit])?;
+
+  let mut tray = TrayIconBuilder::with_id("main")
+    .tooltip("TransferPilot")
+    .menu(&menu)
+    .on_menu_event(|app, event| match event.id.as_ref() {
+      "show" => show_main_window(app),
+      "quit" => app.exit(0),
+      _ => {}
+    });
+  if let Some(icon) = app.default_window_icon() {
+    tray = tray.icon(icon.clone());
+  }
+  tray.build(app)?;
+  Ok(())
+}
+
+/* -------------------------------- Auto-import ------------------------------- */
+
+/// Starts the profile's transfer from a freshly mounted card when auto-import is
+/// on and the profile has a destination. Returns the job id so the UI doesn't
+/// start the same import again.
+pub fn auto_import(app: &AppHandle, profile: &Profile, mount_point: &str) -> Option<String> {
+  if !settings().auto_import {
+    return None;
+  }
+  let dest = profile.dest_mount_point.clone().filter(|d| !d.trim().is_empty())?;
+
+  let job = app.state::<Jobs>().register(None).ok()?;
+  let job_id = job.id.clone();
+  logging::info(&format!(
+    "job {job_id}: auto-importing {mount_point} with profile {}",
+    profile.name
+  ));
+
+  let app = app.clone();
+  let profile = profile.clone();
+  let items = vec![PickedItem {
+    kind: "folder".to_string(),
+    path: mount_point.to_string(),
+    contents_only: true,
+  }];
+  tauri::async_runtime::spawn(async move {
+    events::job(&app, &job.id, "transfer", "queued", None);
+    let res = transfer::start_transfer(
+      app.clone(),
+      items,
+      dest,
+      profile.copy_mode,
+      profile.conflict_policy,
+      profile.verify_mode,
+      profile.options,
+      None,
+      job.clone(),
+    )
+    .await;
+    if let Err(e) = &res {
+      logging::warn(&format!("job {}: auto-import failed: {e}", job.id));
+    }
+    events::job_ended(&app, &job.id, "transfer", &res);
+    app.state::<Jobs>().remove(&job.id);
+  });
+  Some(job_id)
+}
@@ -0,0 +1,385 @@
+use std::{
+  process::Command,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  thread,
+  time::Duration,
+};
+use tauri::AppHandle;
+
+use crate::{
+  events::{self, DeviceEvent},
+  logging,
+};
+
+/* --------------------------------- Identity --------------------------------- */
+
+/// Filesystem UUID for a mount point, used to recognise the same drive after a replug
+/// (macOS may remount it at a different path, e.g. "/Volumes/Backup 1").
+pub fn volume_uuid(mount_point: &str) -> Option<String> {
+  if cfg!(target_os = "macos") {
+    let out = Command::new("diskutil").arg("info").arg(mount_point).output().ok()?;
+    let s = String::from_utf8_lossy(&out.stdout);
+    for line in s.lines() {
+      let line = line.trim();
+      if let Some(rest) = line.strip_prefix("Volume UUID:") {
+        let v = rest.trim();
+        if !v.is_empty() {
+          return Some(v.to_string());
+        }
+      }
+    }
+    None
+  } else {
+    let out = Command::new("findmnt")
+      .args(["-no", "UUID", "--target", mount_point])
+      .output()
+      .ok()?;
+    let v = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if v.is_empty() {
+      None
+    } else {
+      Some(v)
+    }
+  }
+}
+
+/// Mount point of the volume containing `path`.
+pub fn mount_point_for(path: &str) -> Option<String> {
+  let out = Command::new("df").arg("-k").arg(path).output().ok()?;
+  let s = String::from_utf8_lossy(&out.stdout);
+  let line = s.lines().nth(1)?;
+  let parts: Vec<&str> = line.split_whitespace().collect();
+  if parts.len() < 6 {
+    return None;
+  }
+  parts.last().map(|m| m.to_string())
+}
+
+/// Whether the drive holding `path` is a spinning disk. None when it can't be told.
+pub fn is_rotational(path: &str) -> Option<bool> {
+  if cfg!(target_os = "macos") {
+    let mount = mount_point_for(path)?;
+    let out = Command::new("diskutil").arg("info").arg(&mount).output().ok()?;
+    let s = String::from_utf8_lossy(&out.stdout);
+    for line in s.lines() {
+      if let Some(rest) = line.trim().strip_prefix("Solid State:") {
+        return Some(rest.trim() != "Yes");
+      }
+    }
+    None
+  } else {
+    let out = Command::new("findmnt")
+      .args(["-no", "SOURCE", "--target", path])
+      .output()
+      .ok()?;
+    let dev = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    if !dev.starts_with("/dev/") {
+      return None;
+    }
+    let out = Command::new("lsblk").args(["-ndo", "ROTA", &dev]).output().ok()?;
+    match String::from_utf8_lossy(&out.stdout).trim() {
+      "1" => Some(true),
+      "0" => Some(false),
+      _ => None,
+    }
+  }
+}
+
+/// Whether the volume at `mount_point` is on a removable or external device (USB
+/// stick, card reader, external disk). None when the platform can't tell; callers
+/// fall back to the OS drive type (GetDriveType on Windows, via sysinfo).
+pub fn is_removable(mount_point: &str) -> Option<bool> {
+  if cfg!(target_os = "macos") {
+    // diskutil reads the IOKit media properties
+    let out = Command::new("diskutil").arg("info").arg(mount_point).output().ok()?;
+    let s = String::from_utf8_lossy(&out.stdout);
+    let mut known = false;
+    for line in s.lines() {
+      let Some((key, value)) = line.trim().split_once(':') else {
+        continue;
+      };
+      let value = value.trim();
+      match key.trim() {
+        "Removable Media" => {
+          known = true;
+          if value == "Removable" {
+            return Some(true);
+          }
+        }
+        "Device Location" => {
+          known = true;
+          if value == "External" {
+            return Some(true);
+          }
+        }
+        "Protocol" if matches!(value, "USB" | "Secure Digital" | "Thunderbolt") => return Some(true),
+        _ => {}
+      }
+    }
+    known.then_some(false)
+  } else if cfg!(target_os = "linux") {
+    let out = Command::new("findmnt")
+      .args(["-no", "SOURCE", "--target", mount_point])
+      .output()
+      .ok()?;
+    let dev = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    let name = dev.strip_prefix("/dev/")?;
+    let sys = std::fs::canonicalize(format!("/sys/class/block/{name}")).ok()?;
+    // USB and SD/MMC attached disks count even when the kernel marks them fixed
+    let path = sys.to_string_lossy();
+    if path.contains("/usb") || path.contains("/mmc") {
+      return Some(true);
+    }
+    // The flag lives on the whole disk, not the partition
+    let disk = if sys.join("partition").exists() { sys.parent()?.to_path_buf() } else { sys };
+    let flag = std::fs::read_to_string(disk.join("removable")).ok()?;
+    Some(flag.trim() == "1")
+  } else {
+    None
+  }
+}
+
+/// Mount points currently reported by `df`.
+pub fn mounted_points() -> Vec<String> {
+  let out = match Command::new("df").arg("-k").output() {
+    Ok(o) => o,
+    Err(_) => return vec![],
+  };
+  let s = String::from_utf8_lossy(&out.stdout);
+  s.lines()
+    .skip(1)
+    .filter_map(|line| {
+      let parts: Vec<&str> = line.split_whitespace().collect();
+      if parts.len() < 6 {
+        return None;
+      }
+      parts.last().map(|m| m.to_string())
+    })
+    .collect()
+}
+
+pub fn is_mounted(mount_point: &str) -> bool {
+  let target = mount_point.trim_end_matches('/');
+  let target = if target.is_empty() { "/" } else { target };
+  mounted_points().iter().any(|m| m == target)
+}
+
+pub fn find_mount_by_uuid(uuid: &str) -> Option<String> {
+  mounted_points()
+    .into_iter()
+    .find(|m| volume_uuid(m).as_deref() == Some(uuid))
+}
+
+/* ----------------------------------- Eject ---------------------------------- */
+
+/// Unmounts (and, where the OS supports it, powers down) the volume at `mount_point`
+/// so it's safe to unplug.
+#[cfg(not(windows))]
+pub fn eject(mount_point: &str) -> Result<(), String> {
+  let out = if cfg!(target_os = "macos") {
+    Command::new("diskutil").args(["eject", mount_point]).output()
+  } else {
+    // Resolved before unmounting: afterwards the mount point belongs to the parent filesystem
+    let dev = source_device(mount_point)?;
+    let out = Command::new("udisksctl").args(["unmount", "-b", &dev]).output();
+    // Unmounting leaves the drive spun up; power it off too where udisks can
+    if out.as_ref().is_ok_and(|o| o.status.success()) {
+      let _ = Command::new("udisksctl").args(["power-off", "-b", &dev]).output();
+    }
+    out
+  }
+  .map_err(|e| format!("eject failed to start: {e}"))?;
+
+  if out.status.success() {
+    logging::info(&format!("ejected {mount_point}"));
+    Ok(())
+  } else {
+    let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+    Err(format!("eject {mount_point} failed: {stderr}"))
+  }
+}
+
+// Windows: lock the volume (fails while files on it are open), dismount it, then
+// eject the media. Closing the handle releases the lock.
+#[cfg(windows)]
+pub fn eject(mount_point: &str) -> Result<(), String> {
+  use std::{
+    ffi::c_void,
+    fs,
+    os::windows::{fs::OpenOptionsExt, io::AsRawHandle},
+    ptr,
+  };
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn DeviceIoControl(
+      device: *mut c_void,
+      code: u32,
+      in_buf: *const c_void,
+      in_len: u32,
+      out_buf: *mut c_void,
+      out_len: u32,
+      returned: *mut u32,
+      overlapped: *mut c_void,
+    ) -> i32;
+  }
+  const FSCTL_LOCK_VOLUME: u32 = 0x0009_0018;
+  const FSCTL_DISMOUNT_VOLUME: u32 = 0x0009_0020;
+  const IOCTL_STORAGE_MEDIA_REMOVAL: u32 = 0x002D_4804;
+  const IOCTL_STORAGE_EJECT_MEDIA: u32 = 0x002D_4808;
+  const FILE_SHARE_READ_WRITE: u32 = 0x1 | 0x2;
+  // Explorer or an indexer may hold the volume for a moment after the copy
+  const LOCK_TRIES: u32 = 10;
+
+  let drive = mount_point.trim_end_matches(['\\', '/']);
+  let volume = fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .share_mode(FILE_SHARE_READ_WRITE)
+    .open(format!(r"\\.\{drive}"))
+    .map_err(|e| format!("eject {mount_point} failed: open volume error: {e}"))?;
+  let ioctl = |code: u32, input: &[u8]| {
+    let mut returned = 0u32;
+    // SAFETY: the handle is open for the duration of the call; buffers are valid for their lengths
+    let ok = unsafe {
+      DeviceIoControl(
+        volume.as_raw_handle() as *mut c_void,
+        code,
+        if input.is_empty() { ptr::null() } else { input.as_ptr() as *const c_void },
+        input.len() as u32,
+        ptr::null_mut(),
+        0,
+        &mut returned,
+        ptr::null_mut(),
+      )
+    };
+    if ok != 0 {
+      Ok(())
+    } else {
+      Err(std::io::Error::last_os_error())
+    }
+  };
+
+  let mut tries = 0;
+  while let Err(e) = ioctl(FSCTL_LOCK_VOLUME, &[]) {
+    tries += 1;
+    if tries >= LOCK_TRIES {
+      return Err(format!("eject {mount_point} failed: volume is in use ({e})"));
+    }
+    thread::sleep(Duration::from_millis(500));
+  }
+  ioctl(FSCTL_DISMOUNT_VOLUME, &[]).map_err(|e| format!("eject {mount_point} failed: dismount error: {e}"))?;
+  // PREVENT_MEDIA_REMOVAL { PreventMediaRemoval: FALSE }
+  ioctl(IOCTL_STORAGE_MEDIA_REMOVAL, &[0]).map_err(|e| format!("eject {mount_point} failed: media removal error: {e}"))?;
+  ioctl(IOCTL_STORAGE_EJECT_MEDIA, &[]).map_err(|e| format!("eject {mount_point} failed: {e}"))?;
+
+  logging::info(&format!("ejected {mount_point}"));
+  Ok(())
+}
+
+#[cfg(not(windows))]
+fn source_device(mount_point: &str) -> Result<String, String> {
+  let out = Command::new("findmnt")
+    .args(["-no", "SOURCE", "--target", mount_point])
+    .output()
+    .map_err(|e| format!("findmnt failed: {e}"))?;
+  let dev = String::from_utf8_lossy(&out.stdout).trim().to_string();
+  if dev.starts_with("/dev/") {
+    Ok(dev)
+  } else {
+    Err(format!("{mount_point} is not backed by a block device"))
+  }
+}
+
+/* ------------------------------- Reattachment ------------------------------- */
+
+/// Blocks until a volume with `uuid` is mounted again (returns its mount point),
+/// or `cancel` is set (returns None). Without a UUID we can only wait for the
+/// original mount point to come back.
+pub fn wait_for_reattach(
+  app: &AppHandle,
+  job_id: &str,
+  mount_point: &str,
+  uuid: Option<&str>,
+  cancel: &Arc<AtomicBool>,
+) -> Option<String> {
+  logging::warn(&format!("destination {mount_point} disappeared; waiting for it to return"));
+  events::emit(
+    app,
+    events::DEVICE_REMOVED,
+    &DeviceEvent {
+      job_id: job_id.to_string(),
+      mount_point: mount_point.to_string(),
+      uuid: uuid.map(|u| u.to_string()),
+    },
+  );
+
+  loop {
+    if cancel.load(Ordering::SeqCst) {
+      return None;
+    }
+    thread::sleep(Duration::from_secs(2));
+
+    let found = match uuid {
+      Some(u) => find_mount_by_uuid(u),
+      None => is_mounted(mount_point).then(|| mount_point.to_string()),
+    };
+
+    if let Some(m) = found {
+      logging::info(&format!("destination reattached at {m}"));
+      events::emit(
+        app,
+        events::DEVICE_RESTORED,
+        &DeviceEvent {
+          job_id: job_id.to_string(),
+          mount_point: m.clone(),
+          uuid: uuid.map(|u| u.to_string()),
+        },
+      );
+      return Some(m);
+    }
+  }
+}
+
+/// Blocks until a volume with `uuid` is mounted (returns its mount point), or `cancel`
+/// is set. Used to start a queued job the moment its bound drive is plugged in.
+pub fn wait_for_device(app: &AppHandle, job_id: &str, uuid: &str, cancel: &Arc<AtomicBool>) -> Option<String> {
+  let mut announced = false;
+  loop {
+    if cancel.load(Ordering::SeqCst) {
+      return None;
+    }
+
+    if let Some(m) = find_mount_by_uuid(uuid) {
+      logging::info(&format!("device {uuid} available at {m}"));
+      events::emit(
+        app,
+        events::DEVICE_READY,
+        &DeviceEvent {
+          job_id: job_id.to_string(),
+          mount_point: m.clone(),
+          uuid: Some(uuid.to_string()),
+        },
+      );
+      return Some(m);
+    }
+
+    if !announced {
+      logging::info(&format!("waiting for device {uuid}"));
+      events::emit(
+        app,
+        events::WAITING_FOR_DEVICE,
+        &DeviceEvent {
+          job_id: job_id.to_string(),
+          mount_point: String::new(),
+          uuid: Some(uuid.to_string()),
+        },
+      );
+      announced = true;
+    }
+    thread::sleep(Duration::from_secs(2));
+  }
+}
@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::PathBuf};
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+use crate::{
+  checksum::{self, Algo},
+  hash_cache, logging, transfer,
+  watchdog::Watchdog,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+  pub checksum: String,
+  // Size of each copy
+  pub bytes: u64,
+  // Sorted, so the oldest session (earliest Transfers/<day>/<run>) comes first
+  pub paths: Vec<String>,
+  // Space freed by keeping one copy: bytes * (paths - 1)
+  pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateReport {
+  pub root: String,
+  pub algo: String,
+  pub files_scanned: u64,
+  pub bytes_scanned: u64,
+  // Files that had to be read; the rest were unique by size or came from the cache
+  pub hashed_files: u64,
+  pub cached_files: u64,
+  pub duplicate_files: u64,
+  pub reclaimable_bytes: u64,
+  // Largest savings first
+  pub groups: Vec<DuplicateGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateProgress {
+  pub files_done: u64,
+  pub files_total: u64,
+  pub current_path: String,
+}
+
+/* ---------------------------------- Finder ---------------------------------- */
+
+/// Finds files under `root` with identical contents. Only files sharing a size
+/// with another file are hashed (xxh3), and digests are reused from the hash
+/// cache when the file is unchanged since it was last read.
+pub fn find_duplicates(app: &AppHandle, root: &str) -> Result<DuplicateReport, String> {
+  let root_path = PathBuf::from(root);
+  if !root_path.is_dir() {
+    return Err(format!("{root} is not a folder"));
+  }
+  let algo = Algo::Xxh3;
+
+  let mut by_size: HashMap<u64, Vec<(PathBuf, fs::Metadata)>> = HashMap::new();
+  let mut files_scanned = 0u64;
+  let mut bytes_scanned = 0u64;
+  for e in WalkDir::new(&root_path).into_iter().filter_map(|e| e.ok()) {
+    if !e.file_type().is_file() {
+      continue;
+    }
+    let name = e.file_name().to_string_lossy();
+    if transfer::SESSION_FILES.contains(&name.as_ref()) {
+      continue;
+    }
+    let Ok(meta) = e.metadata() else {
+      continue;
+    };
+    files_scanned += 1;
+    bytes_scanned += meta.len();
+    if meta.len() == 0 {
+      continue;
+    }
+    by_size.entry(meta.len()).or_default().push((e.path().to_path_buf(), meta));
+  }
+
+  let candidates: Vec<(PathBuf, fs::Metadata)> = by_size
+    .into_values()
+    .filter(|v| v.len() > 1)
+    .flatten()
+    .collect();
+  let files_total = candidates.len() as u64;
+  logging::info(&format!(
+    "duplicate scan of {root}: {files_scanned} file(s), {files_total} share a size"
+  ));
+
+  let watchdog = Watchdog::disabled();
+  let mut by_hash: HashMap<(u64, String), Vec<String>> = HashMap::new();
+  let mut hashed_files = 0u64;
+  let mut cached_files = 0u64;
+  for (i, (path, meta)) in candidates.iter().enumerate() {
+    let _ = app.emit(
+      "dupes://progress",
+      DuplicateProgress {
+        files_done: i as u64,
+        files_total,
+        current_path: path.to_string_lossy().to_string(),
+      },
+    );
+
+    let digest = match hash_cache::lookup(path, meta, algo) {
+      Some(d) => {
+        cached_files += 1;
+        d
+      }
+      None => match checksum::hash_file(path, algo, false, &watchdog) {
+        Ok(d) => {
+          hashed_files += 1;
+          hash_cache::store(path, meta, algo, &d);
+          d
+        }
+        Err(e) => {
+          logging::warn(&format!("{}: {e}", path.display()));
+          continue;
+        }
+      },
+    };
+    by_hash
+      .entry((meta.len(), digest))
+      .or_default()
+      .push(path.to_string_lossy().to_string());
+  }
+  hash_cache::save();
+
+  let mut groups: Vec<DuplicateGroup> = by_hash
+    .into_iter()
+    .filter(|(_, paths)| paths.len() > 1)
+    .map(|((bytes, checksum), mut paths)| {
+      paths.sort();
+      DuplicateGroup {
+        checksum,
+        bytes,
+        reclaimable_bytes: bytes * (paths.len() as u64 - 1),
+        paths,
+      }
+    })
+    .collect();
+  groups.sort_by(|a, b| b.reclaimable_bytes.cmp(&a.reclaimable_bytes).then_with(|| a.paths.cmp(&b.paths)));
+
+  Ok(DuplicateReport {
+    root: root.to_string(),
+    algo: algo.name().to_string(),
+    files_scanned,
+    bytes_scanned,
+    hashed_files,
+    cached_files,
+    duplicate_files: groups.iter().map(|g| g.paths.len() as u64 - 1).sum(),
+    reclaimable_bytes: groups.iter().map(|g| g.reclaimable_bytes).sum(),
+    groups,
+  })
+}
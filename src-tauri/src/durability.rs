@@ -0,0 +1,96 @@
+use std::{
+  collections::BTreeSet,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use crate::logging;
+
+/* ---------------------------------- Policy ---------------------------------- */
+/* When copied bytes are forced out of the OS write cache onto the drive.
+   per_file: fsync each file as it finishes (slowest, nothing is ever in doubt)
+   per_batch: fsync every BATCH_FILES files / BATCH_BYTES bytes
+   end_of_session: fsync everything once, after the last file
+   none: leave it to the OS (not safe to unplug until it flushes on its own)
+   Every policy but none treats a failed fsync as a failed file, and ends with an
+   fsync of each directory we created files in so the entries themselves are durable. */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+  PerFile,
+  PerBatch,
+  EndOfSession,
+  None,
+}
+
+pub const BATCH_FILES: usize = 64;
+pub const BATCH_BYTES: u64 = 512 * 1024 * 1024;
+
+impl Policy {
+  pub fn parse(s: &str) -> Result<Self, String> {
+    match s {
+      "" | "per_file" => Ok(Policy::PerFile),
+      "per_batch" => Ok(Policy::PerBatch),
+      "end_of_session" => Ok(Policy::EndOfSession),
+      "none" => Ok(Policy::None),
+      other => Err(format!("unknown durability policy: {other}")),
+    }
+  }
+
+  /// Deferred policies sync after the file is closed, so a move (which deletes the
+  /// source once verified) always syncs per file.
+  pub fn for_copy_mode(self, copy_mode: &str) -> Self {
+    match self {
+      Policy::PerBatch | Policy::EndOfSession if copy_mode == "move" => Policy::PerFile,
+      p => p,
+    }
+  }
+
+  pub fn is_deferred(self) -> bool {
+    matches!(self, Policy::PerBatch | Policy::EndOfSession)
+  }
+}
+
+/* ----------------------------------- Sync ----------------------------------- */
+
+/// Reopens a closed file and fsyncs it; on unix this flushes every dirty page of
+/// the inode, not just ones written through this handle.
+pub fn sync_file(path: &Path) -> Result<(), String> {
+  fs::OpenOptions::new()
+    .write(true)
+    .open(path)
+    // preserve_metadata may already have made it read-only; unix fsyncs a read handle too
+    .or_else(|e| if cfg!(unix) { fs::File::open(path) } else { Err(e) })
+    .and_then(|f| f.sync_all())
+    .map_err(|e| format!("sync error: {e}"))
+}
+
+#[cfg(unix)]
+fn sync_dir(path: &Path) -> std::io::Result<()> {
+  fs::File::open(path)?.sync_all()
+}
+
+// Directory handles can't be fsynced on Windows; NTFS journals the entries itself.
+#[cfg(not(unix))]
+fn sync_dir(_path: &Path) -> std::io::Result<()> {
+  Ok(())
+}
+
+/// Fsyncs the parent directory of every file, plus `extra` dirs (the session dir
+/// and its Transfers/<day> parents). Returns how many failed.
+pub fn sync_dirs<'a>(files: impl IntoIterator<Item = &'a Path>, extra: &[PathBuf]) -> usize {
+  let mut dirs: BTreeSet<PathBuf> = files
+    .into_iter()
+    .filter_map(|f| f.parent().map(|p| p.to_path_buf()))
+    .collect();
+  dirs.extend(extra.iter().cloned());
+
+  let mut failed = 0;
+  for d in &dirs {
+    if let Err(e) = sync_dir(d) {
+      logging::error(&format!("directory sync failed for {}: {e}", d.display()));
+      failed += 1;
+    }
+  }
+  failed
+}
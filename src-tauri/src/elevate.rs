@@ -0,0 +1,130 @@
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use crate::logging;
+
+/* --------------------------------- Elevation -------------------------------- */
+/* Files that failed with permission denied are copied again by a short-lived
+   helper running with administrator rights: pkexec on Linux, the standard
+   authorization prompt (osascript "with administrator privileges") on macOS, a
+   UAC prompt on Windows. Only the listed files are touched, so the app itself
+   never has to run as root. */
+
+// Copies each (source, dest) pair, creating parent dirs, and hands the copies back
+// to the invoking user so they aren't left root-owned. Pairs are passed as
+// positional args, so no path ever goes through shell quoting.
+const UNIX_SCRIPT: &str = r#"uid=$1; gid=$2; shift 2
+while [ $# -ge 2 ]; do
+  mkdir -p "$(dirname "$2")" && cp -p -- "$1" "$2" && chown "$uid:$gid" "$2" || exit 1
+  shift 2
+done"#;
+
+fn id(flag: &str) -> Result<String, String> {
+  let out = Command::new("id")
+    .arg(flag)
+    .output()
+    .map_err(|e| format!("id error: {e}"))?;
+  Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+fn sh_quote(s: &str) -> String {
+  format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn ps_quote(s: &str) -> String {
+  format!("'{}'", s.replace('\'', "''"))
+}
+
+fn unix_args(pairs: &[(PathBuf, PathBuf)]) -> Result<Vec<String>, String> {
+  let mut args = vec![id("-u")?, id("-g")?];
+  for (src, dst) in pairs {
+    args.push(src.to_string_lossy().to_string());
+    args.push(dst.to_string_lossy().to_string());
+  }
+  Ok(args)
+}
+
+fn copy_linux(pairs: &[(PathBuf, PathBuf)]) -> Result<std::process::Output, String> {
+  Command::new("pkexec")
+    .args(["/bin/sh", "-c", UNIX_SCRIPT, "sh"])
+    .args(unix_args(pairs)?)
+    .output()
+    .map_err(|e| format!("pkexec failed to start: {e}"))
+}
+
+fn copy_macos(pairs: &[(PathBuf, PathBuf)]) -> Result<std::process::Output, String> {
+  // `do shell script` takes a single command string, so here the args are quoted
+  let mut cmd = format!("/bin/sh -c {} sh", sh_quote(UNIX_SCRIPT));
+  for a in unix_args(pairs)? {
+    cmd.push(' ');
+    cmd.push_str(&sh_quote(&a));
+  }
+  let apple = format!(
+    "do shell script \"{}\" with administrator privileges",
+    cmd.replace('\\', "\\\\").replace('"', "\\\"")
+  );
+  Command::new("osascript")
+    .args(["-e", &apple])
+    .output()
+    .map_err(|e| format!("osascript failed to start: {e}"))
+}
+
+fn copy_windows(pairs: &[(PathBuf, PathBuf)]) -> Result<std::process::Output, String> {
+  // The elevated PowerShell runs a script file; the outer one waits for it and
+  // passes its exit code through.
+  let mut script = String::from("$ErrorActionPreference = 'Stop'\n");
+  for (src, dst) in pairs {
+    let dir = dst.parent().unwrap_or(Path::new(""));
+    script.push_str(&format!(
+      "New-Item -ItemType Directory -Force -Path {} | Out-Null\nCopy-Item -LiteralPath {} -Destination {} -Force\n",
+      ps_quote(&dir.to_string_lossy()),
+      ps_quote(&src.to_string_lossy()),
+      ps_quote(&dst.to_string_lossy()),
+    ));
+  }
+  let script_path = env::temp_dir().join(format!("transferpilot-elevated-{}.ps1", uuid::Uuid::new_v4()));
+  fs::write(&script_path, script).map_err(|e| format!("elevated script write error: {e}"))?;
+
+  let outer = format!(
+    "$p = Start-Process powershell -Verb RunAs -Wait -PassThru -WindowStyle Hidden -ArgumentList '-NoProfile','-ExecutionPolicy','Bypass','-File',{}; exit $p.ExitCode",
+    ps_quote(&format!("\"{}\"", script_path.to_string_lossy()))
+  );
+  let out = Command::new("powershell")
+    .args(["-NoProfile", "-Command", &outer])
+    .output()
+    .map_err(|e| format!("powershell failed to start: {e}"));
+  let _ = fs::remove_file(&script_path);
+  out
+}
+
+/// Copies `pairs` (source, dest) with administrator rights after a single OS
+/// authorization prompt. Fails as a whole if the prompt is dismissed.
+pub fn copy_elevated(pairs: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+  if pairs.is_empty() {
+    return Ok(());
+  }
+  logging::info(&format!("requesting elevation to copy {} file(s)", pairs.len()));
+
+  let out = if cfg!(target_os = "macos") {
+    copy_macos(pairs)?
+  } else if cfg!(windows) {
+    copy_windows(pairs)?
+  } else {
+    copy_linux(pairs)?
+  };
+
+  if out.status.success() {
+    Ok(())
+  } else {
+    let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+    // pkexec exits 126 when the prompt is dismissed, osascript reports -128
+    if out.status.code() == Some(126) || stderr.contains("-128") {
+      Err("elevation was cancelled".to_string())
+    } else {
+      Err(format!("elevated copy failed ({}): {stderr}", out.status))
+    }
+  }
+}
@@ -0,0 +1,72 @@
+use std::{
+  fs::{self, OpenOptions},
+  io::{Seek, SeekFrom, Write},
+  path::Path,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+/* ------------------------------- Secure erase ------------------------------- */
+/* Used by "secure move": once the copy is hash-verified, the source is
+   overwritten in place before it's unlinked. This only reaches the same physical
+   blocks on spinning disks. SSDs and flash cards remap writes (wear levelling),
+   so old data can survive in spare blocks; preflight says so. */
+
+pub const MAX_PASSES: u32 = 7;
+
+const BUF_BYTES: usize = 1024 * 1024;
+
+// Last pass is always zeros; earlier ones alternate random data and 0xFF
+fn fill(buf: &mut [u8], pass: u32, passes: u32, state: &mut u64) {
+  if pass + 1 == passes {
+    buf.fill(0);
+  } else if pass % 2 == 0 {
+    for chunk in buf.chunks_mut(8) {
+      // xorshift64: not cryptographic, only needs to be unlike the old contents
+      *state ^= *state << 13;
+      *state ^= *state >> 7;
+      *state ^= *state << 17;
+      let bytes = state.to_le_bytes();
+      chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+  } else {
+    buf.fill(0xFF);
+  }
+}
+
+/// Overwrites `path` `passes` times (capped at MAX_PASSES), syncing after each
+/// pass, then renames it to a random name and deletes it.
+pub fn secure_remove(path: &Path, passes: u32) -> Result<(), String> {
+  let passes = passes.clamp(1, MAX_PASSES);
+  let len = fs::metadata(path).map_err(|e| format!("secure erase metadata error: {e}"))?.len();
+  let mut f = OpenOptions::new()
+    .write(true)
+    .open(path)
+    .map_err(|e| format!("secure erase open error: {e}"))?;
+
+  let mut state = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_nanos() as u64)
+    .unwrap_or(0x9E37_79B9_7F4A_7C15)
+    | 1;
+  let mut buf = vec![0u8; BUF_BYTES];
+  for pass in 0..passes {
+    f.seek(SeekFrom::Start(0)).map_err(|e| format!("secure erase seek error: {e}"))?;
+    let mut left = len;
+    while left > 0 {
+      let n = left.min(BUF_BYTES as u64) as usize;
+      fill(&mut buf[..n], pass, passes, &mut state);
+      f.write_all(&buf[..n]).map_err(|e| format!("secure erase write error: {e}"))?;
+      left -= n as u64;
+    }
+    f.sync_all().map_err(|e| format!("secure erase sync error: {e}"))?;
+  }
+  drop(f);
+
+  // The directory entry keeps the name, so replace that too before unlinking
+  let scrubbed = path.with_file_name(uuid::Uuid::new_v4().simple().to_string());
+  let target = match fs::rename(path, &scrubbed) {
+    Ok(_) => scrubbed,
+    Err(_) => path.to_path_buf(),
+  };
+  fs::remove_file(&target).map_err(|e| format!("secure erase remove error: {e}"))
+}
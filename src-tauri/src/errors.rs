@@ -0,0 +1,97 @@
+/* ------------------------------ Classification ------------------------------ */
+
+/// A user-meaningful error class plus a machine-readable suggestion the UI can turn
+/// into a one-click fix.
+///
+/// class: permission_denied | disk_full | device_removed | path_too_long | file_in_use |
+///        read_only | verify_mismatch | network | io_error | other
+/// suggestion: retry_elevated | free_space | reconnect_device | shorten_path |
+///             close_other_apps | choose_writable_dest | retry | check_media | none
+pub struct ErrorClass {
+  pub class: &'static str,
+  pub suggestion: &'static str,
+}
+
+fn class(class: &'static str, suggestion: &'static str) -> ErrorClass {
+  ErrorClass { class, suggestion }
+}
+
+// Engine errors are strings like "write error: No space left on device (os error 28)".
+fn os_error_code(msg: &str) -> Option<i32> {
+  let start = msg.rfind("(os error ")? + "(os error ".len();
+  let end = msg[start..].find(')')? + start;
+  msg[start..end].parse().ok()
+}
+
+fn classify_code(code: i32) -> Option<ErrorClass> {
+  let c = if cfg!(windows) {
+    match code {
+      5 => class("permission_denied", "retry_elevated"),
+      39 | 112 => class("disk_full", "free_space"),
+      19 => class("read_only", "choose_writable_dest"),
+      206 => class("path_too_long", "shorten_path"),
+      32 | 33 => class("file_in_use", "close_other_apps"),
+      21 | 1167 => class("device_removed", "reconnect_device"),
+      53 | 64 | 121 => class("network", "retry"),
+      23 | 1117 => class("io_error", "check_media"),
+      _ => return None,
+    }
+  } else {
+    match code {
+      1 | 13 => class("permission_denied", "retry_elevated"),
+      28 | 69 | 122 => class("disk_full", "free_space"),
+      30 => class("read_only", "choose_writable_dest"),
+      36 | 63 => class("path_too_long", "shorten_path"),
+      16 | 26 => class("file_in_use", "close_other_apps"),
+      6 | 19 => class("device_removed", "reconnect_device"),
+      54 | 60 | 64 | 104 | 110 | 112 => class("network", "retry"),
+      5 => class("io_error", "check_media"),
+      _ => return None,
+    }
+  };
+  Some(c)
+}
+
+/// Classifies an engine error message. Uses the OS error number when the message
+/// carries one, otherwise falls back to the wording.
+pub fn classify(msg: &str) -> ErrorClass {
+  let m = msg.to_lowercase();
+
+  // Engine-generated errors carry no OS code
+  if m.contains("verify failed") || m.contains("mismatch") {
+    return class("verify_mismatch", "retry");
+  }
+  if m.contains("not enough free space") {
+    return class("disk_full", "free_space");
+  }
+  if let Some(c) = os_error_code(msg).and_then(classify_code) {
+    return c;
+  }
+
+  if m.contains("permission denied") || m.contains("access is denied") || m.contains("not permitted") {
+    class("permission_denied", "retry_elevated")
+  } else if m.contains("no space left") || m.contains("disk full") || m.contains("quota exceeded") {
+    class("disk_full", "free_space")
+  } else if m.contains("read-only file system") {
+    class("read_only", "choose_writable_dest")
+  } else if m.contains("name too long") || m.contains("path too long") {
+    class("path_too_long", "shorten_path")
+  } else if m.contains("used by another process") || m.contains("resource busy") {
+    class("file_in_use", "close_other_apps")
+  } else if m.contains("no such device") || m.contains("device not configured") || m.contains("disappeared") {
+    class("device_removed", "reconnect_device")
+  } else if m.contains("timed out") || m.contains("connection reset") || m.contains("host is down") {
+    class("network", "retry")
+  } else if m.contains("input/output error") {
+    class("io_error", "check_media")
+  } else {
+    class("other", "none")
+  }
+}
+
+/// Errors a second attempt at the same file can plausibly get past: flaky media,
+/// a busy file, a network blip. Not ones that will just happen again (permissions,
+/// full disk, bad path) or that have their own handling (device removed, verify).
+pub fn is_retryable(msg: &str) -> bool {
+  matches!(classify(msg).class, "io_error" | "network" | "file_in_use" | "other")
+}
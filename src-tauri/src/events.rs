@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+  bottleneck::IoStats,
+  transfer::{ManifestItem, OffloadItem},
+};
+
+/* ---------------------------------- Schema ---------------------------------- */
+/* Every event the engine sends is defined here. Payloads go out wrapped with a
+   `schema_version` field (flattened in beside the payload's own fields), bumped
+   whenever a field is removed or changes meaning. Adding a field doesn't bump it. */
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Job lifecycle (JobEvent), separate from byte progress
+pub const JOB: &str = "transfer://job";
+// Byte progress, also sent on transfer://progress/{job_id} (job_topic)
+pub const PROGRESS: &str = "transfer://progress";
+pub const MANIFEST_ROW: &str = "transfer://manifest-row";
+pub const OFFLOAD_ITEM: &str = "transfer://offload-item";
+pub const PLAN_STALE: &str = "transfer://plan-stale";
+pub const STALLED: &str = "transfer://stalled";
+pub const FILE_ERROR: &str = "transfer://file-error";
+pub const ON_COMPLETE: &str = "transfer://on-complete";
+pub const DEVICE_REMOVED: &str = "transfer://device-removed";
+pub const DEVICE_RESTORED: &str = "transfer://device-restored";
+pub const DEVICE_READY: &str = "transfer://device-ready";
+pub const WAITING_FOR_DEVICE: &str = "transfer://waiting-for-device";
+
+#[derive(Debug, Clone, Serialize)]
+struct Versioned<'a, T: Serialize> {
+  schema_version: u32,
+  #[serde(flatten)]
+  payload: &'a T,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JobScoped<'a, T: Serialize> {
+  job_id: &'a str,
+  #[serde(flatten)]
+  payload: &'a T,
+}
+
+/// Emits a payload that doesn't carry its own job_id, tagged with the job it
+/// belongs to. With several jobs running every engine event must say whose it is.
+pub fn emit_for_job<T: Serialize>(app: &AppHandle, topic: &str, job_id: &str, payload: &T) {
+  emit(app, topic, &JobScoped { job_id, payload });
+}
+
+/// `topic/job_id`: a job's own copy of a busy topic (progress), so a window
+/// following one job of several isn't sent every other job's ticks. None when the
+/// id has characters Tauri won't take in an event name; the shared topic still works.
+pub fn job_topic(topic: &str, job_id: &str) -> Option<String> {
+  let ok = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':');
+  job_id.chars().all(ok).then(|| format!("{topic}/{job_id}"))
+}
+
+pub fn emit<T: Serialize>(app: &AppHandle, topic: &str, payload: &T) {
+  let _ = app.emit(
+    topic,
+    Versioned {
+      schema_version: SCHEMA_VERSION,
+      payload,
+    },
+  );
+}
+
+/* --------------------------------- Payloads --------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEvent {
+  pub job_id: String,
+  pub kind: String,  // "transfer" | "offload" | "verify"
+  pub state: String, // "queued" | "started" | "finished" | "failed"
+  pub error: Option<String>,
+}
+
+// Sent with emit_for_job, so it arrives with a job_id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+  pub phase: String, // "scanning" | "copying" | "verifying" | "paused" | "done" | "stopped" | "cancelled" | "error"
+  pub current_file: u64, // 1-based
+  pub total_files: u64,
+  pub current_path: String,
+  pub bytes_done: u64,
+  pub bytes_total: u64,
+  pub percent: f64, // 0..=100
+  // While copying: which side (source or destination) is holding things up
+  #[serde(default)]
+  pub io: Option<IoStats>,
+  // Smoothed throughput (EWMA over a few seconds) and the time left at that rate;
+  // set by emit_progress. eta_seconds is None until there's a rate to go on.
+  #[serde(default)]
+  pub bytes_per_sec: u64,
+  #[serde(default)]
+  pub eta_seconds: Option<u64>,
+  // Since the job was registered
+  #[serde(default)]
+  pub elapsed_ms: u64,
+}
+
+// A manifest row as it's recorded (including its checksum when verification
+// hashed it). `row_index` is its position in that session's manifest: a row can
+// be sent again when it changes later (e.g. a deferred fsync fails), and an
+// offload sends one row per destination session.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ManifestRowEvent<'a> {
+  pub(crate) job_id: &'a str,
+  pub(crate) session_dir: String,
+  pub(crate) row_index: usize,
+  #[serde(flatten)]
+  pub(crate) item: &'a ManifestItem,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OffloadItemEvent<'a> {
+  pub job_id: &'a str,
+  pub item: &'a OffloadItem,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanStaleEvent {
+  pub job_id: String,
+  pub expected_files: u64,
+  pub expected_bytes: u64,
+  pub actual_files: u64,
+  pub actual_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StallEvent {
+  pub job_id: String,
+  pub current_path: String,
+  pub stalled_secs: u64,
+}
+
+// A file failed. With will_retry it's about to be tried again (retry_policy or a
+// network retry); otherwise its manifest row is now an error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileErrorEvent {
+  pub job_id: String,
+  pub source: String,
+  pub dest: String,
+  pub error: String,
+  // errors::classify class, e.g. "io_error" | "disk_full" | "verify_mismatch"
+  pub error_class: String,
+  pub will_retry: bool,
+  // Which try failed, 1-based
+  pub attempt: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionEvent {
+  pub job_id: String,
+  pub action: String,
+  pub ok: bool,
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceEvent {
+  pub job_id: String,
+  pub mount_point: String,
+  pub uuid: Option<String>,
+}
+
+/* --------------------------------- Lifecycle -------------------------------- */
+
+pub fn job(app: &AppHandle, job_id: &str, kind: &str, state: &str, error: Option<String>) {
+  emit(
+    app,
+    JOB,
+    &JobEvent {
+      job_id: job_id.to_string(),
+      kind: kind.to_string(),
+      state: state.to_string(),
+      error,
+    },
+  );
+}
+
+/// "finished" or "failed" for a job's final result.
+pub fn job_ended<T>(app: &AppHandle, job_id: &str, kind: &str, res: &Result<T, String>) {
+  match res {
+    Ok(_) => job(app, job_id, kind, "finished", None),
+    Err(e) => job(app, job_id, kind, "failed", Some(e.clone())),
+  }
+}
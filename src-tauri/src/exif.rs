@@ -0,0 +1,218 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use std::{fs, io::Read, path::Path};
+
+/* ----------------------------------- Types ---------------------------------- */
+/* Minimal EXIF reader for rename templates: JPEG (APP1), TIFF-based raws (ARW,
+   NEF, CR2, DNG, ORF, RW2, PEF…) and Fuji RAF (via its embedded JPEG). Only
+   IFD0 and the Exif sub-IFD are read, from the head of the file. */
+
+#[derive(Debug, Clone, Default)]
+pub struct Exif {
+  pub make: Option<String>,
+  pub model: Option<String>,
+  pub lens: Option<String>,
+  pub iso: Option<u32>,
+  // DateTimeOriginal, else DateTime; camera local time
+  pub taken_at: Option<chrono::DateTime<Local>>,
+}
+
+// IFD0 and the Exif IFD sit near the start; no need to read whole raws
+const HEAD_BYTES: u64 = 1024 * 1024;
+
+const TAG_THUMB_OFFSET: u16 = 0x0201;
+const TAG_THUMB_LENGTH: u16 = 0x0202;
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_DATETIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_ISO: u16 = 0x8827;
+const TAG_DATETIME_ORIGINAL: u16 = 0x9003;
+const TAG_LENS_MODEL: u16 = 0xA434;
+
+pub fn read(path: &Path) -> Option<Exif> {
+  with_tiff(path, parse_tiff)
+}
+
+/// The small JPEG preview cameras embed in IFD1 (or IFD0 on some raws), if it
+/// lies within the head of the file.
+pub fn thumbnail(path: &Path) -> Option<Vec<u8>> {
+  with_tiff(path, parse_thumbnail)
+}
+
+fn with_tiff<R>(path: &Path, parse: impl FnOnce(&[u8]) -> Option<R>) -> Option<R> {
+  let mut head = vec![];
+  fs::File::open(path).ok()?.take(HEAD_BYTES).read_to_end(&mut head).ok()?;
+
+  let tiff = if head.starts_with(&[0xFF, 0xD8]) {
+    jpeg_tiff(&head)?
+  } else if head.starts_with(b"FUJIFILMCCD-RAW") {
+    let off = be32(&head, 84)? as usize;
+    jpeg_tiff(head.get(off..)?)?
+  } else if head.starts_with(b"II") || head.starts_with(b"MM") {
+    &head[..]
+  } else {
+    return None;
+  };
+  parse(tiff)
+}
+
+/* ---------------------------------- Parsing --------------------------------- */
+
+fn be32(b: &[u8], pos: usize) -> Option<u32> {
+  let s = b.get(pos..pos + 4)?;
+  Some(u32::from_be_bytes([s[0], s[1], s[2], s[3]]))
+}
+
+// The TIFF block inside a JPEG's "Exif\0\0" APP1 segment
+fn jpeg_tiff(b: &[u8]) -> Option<&[u8]> {
+  let mut pos = 2;
+  while pos + 4 <= b.len() && b[pos] == 0xFF {
+    let marker = b[pos + 1];
+    let len = u16::from_be_bytes([b[pos + 2], b[pos + 3]]) as usize;
+    // Start of scan: no metadata past here
+    if marker == 0xDA {
+      return None;
+    }
+    let seg = b.get(pos + 4..pos + 2 + len)?;
+    if marker == 0xE1 && seg.starts_with(b"Exif\0\0") {
+      return Some(&seg[6..]);
+    }
+    pos += 2 + len;
+  }
+  None
+}
+
+struct Tiff<'a> {
+  b: &'a [u8],
+  le: bool,
+}
+
+impl Tiff<'_> {
+  fn u16(&self, pos: usize) -> Option<u16> {
+    let s = self.b.get(pos..pos + 2)?;
+    Some(if self.le {
+      u16::from_le_bytes([s[0], s[1]])
+    } else {
+      u16::from_be_bytes([s[0], s[1]])
+    })
+  }
+
+  fn u32(&self, pos: usize) -> Option<u32> {
+    let s = self.b.get(pos..pos + 4)?;
+    Some(if self.le {
+      u32::from_le_bytes([s[0], s[1], s[2], s[3]])
+    } else {
+      u32::from_be_bytes([s[0], s[1], s[2], s[3]])
+    })
+  }
+
+  // (tag, type, count, value-or-offset position) for each entry of the IFD at `off`
+  fn entries(&self, off: usize) -> Vec<(u16, u16, u32, usize)> {
+    let Some(n) = self.u16(off) else {
+      return vec![];
+    };
+    (0..n as usize)
+      .filter_map(|i| {
+        let e = off + 2 + i * 12;
+        Some((self.u16(e)?, self.u16(e + 2)?, self.u32(e + 4)?, e + 8))
+      })
+      .collect()
+  }
+
+  // Offset of the IFD chained after the one at `off` (0 = none)
+  fn next_ifd(&self, off: usize) -> Option<usize> {
+    let n = self.u16(off)? as usize;
+    self.u32(off + 2 + n * 12).map(|v| v as usize)
+  }
+
+  fn ascii(&self, typ: u16, count: u32, at: usize) -> Option<String> {
+    if typ != 2 {
+      return None;
+    }
+    let count = count as usize;
+    let pos = if count <= 4 { at } else { self.u32(at)? as usize };
+    let raw = self.b.get(pos..pos + count)?;
+    let s = String::from_utf8_lossy(raw)
+      .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+      .to_string();
+    (!s.is_empty()).then_some(s)
+  }
+
+  fn number(&self, typ: u16, at: usize) -> Option<u32> {
+    match typ {
+      3 => self.u16(at).map(|v| v as u32),
+      4 => self.u32(at),
+      _ => None,
+    }
+  }
+}
+
+fn tiff(b: &[u8]) -> Option<Tiff<'_>> {
+  let le = match b.get(0..2)? {
+    b"II" => true,
+    b"MM" => false,
+    _ => return None,
+  };
+  Some(Tiff { b, le })
+}
+
+fn parse_thumbnail(b: &[u8]) -> Option<Vec<u8>> {
+  let t = tiff(b)?;
+  let ifd0 = t.u32(4)? as usize;
+  let ifd1 = t.next_ifd(ifd0).filter(|&o| o != 0);
+
+  for ifd in [ifd1, Some(ifd0)].into_iter().flatten() {
+    let (mut off, mut len) = (None, None);
+    for (tag, typ, _, at) in t.entries(ifd) {
+      match tag {
+        TAG_THUMB_OFFSET => off = t.number(typ, at),
+        TAG_THUMB_LENGTH => len = t.number(typ, at),
+        _ => {}
+      }
+    }
+    if let (Some(off), Some(len)) = (off, len) {
+      let jpeg = b.get(off as usize..off as usize + len as usize);
+      if let Some(jpeg) = jpeg.filter(|j| j.starts_with(&[0xFF, 0xD8])) {
+        return Some(jpeg.to_vec());
+      }
+    }
+  }
+  None
+}
+
+fn parse_tiff(b: &[u8]) -> Option<Exif> {
+  let t = tiff(b)?;
+  let ifd0 = t.u32(4)? as usize;
+
+  let mut exif = Exif::default();
+  let mut date_time = None;
+  let mut date_original = None;
+  let mut exif_ifd = None;
+
+  for (tag, typ, count, at) in t.entries(ifd0) {
+    match tag {
+      TAG_MAKE => exif.make = t.ascii(typ, count, at),
+      TAG_MODEL => exif.model = t.ascii(typ, count, at),
+      TAG_DATETIME => date_time = t.ascii(typ, count, at),
+      TAG_EXIF_IFD => exif_ifd = t.number(typ, at),
+      _ => {}
+    }
+  }
+  if let Some(off) = exif_ifd {
+    for (tag, typ, count, at) in t.entries(off as usize) {
+      match tag {
+        TAG_ISO => exif.iso = t.number(typ, at),
+        TAG_DATETIME_ORIGINAL => date_original = t.ascii(typ, count, at),
+        TAG_LENS_MODEL => exif.lens = t.ascii(typ, count, at),
+        _ => {}
+      }
+    }
+  }
+
+  // "2025:06:01 14:03:22", no zone: the camera's clock
+  exif.taken_at = date_original
+    .or(date_time)
+    .and_then(|s| NaiveDateTime::parse_from_str(&s, "%Y:%m:%d %H:%M:%S").ok())
+    .and_then(|n| Local.from_local_datetime(&n).earliest());
+  Some(exif)
+}
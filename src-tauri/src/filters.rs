@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::transfer::TransferOptions;
+
+/* ---------------------------------- Globs ----------------------------------- */
+// include_globs / exclude_globs, applied while scanning so preflight and the copy
+// see the same files. Paths are matched with "/" separators, relative to the
+// picked folder and starting with its name ("DCIM/100CANON/IMG_0001.CR3"); a
+// loose file is just its name.
+//   *    any run of characters within one folder or file name
+//   ?    one character
+//   [ab] one of the listed characters ([a-z] ranges, [!ab] negated)
+//   **   any number of folders
+// Patterns float: "*.raw" and "node_modules/**" match at any depth. Start one
+// with "/" to pin it to the top ("/DCIM/**"). An excluded folder isn't walked at
+// all. With any include_globs, only files matching one of them are kept.
+// Matching ignores case except on Linux.
+
+struct Glob(Vec<String>);
+
+impl Glob {
+  fn new(pattern: &str) -> Option<Self> {
+    let p = pattern.trim().replace('\\', "/");
+    if p.is_empty() {
+      return None;
+    }
+    let p = fold_case(&p);
+    let segs: Vec<String> = match p.strip_prefix('/') {
+      Some(anchored) => anchored.split('/').map(str::to_string).collect(),
+      None => std::iter::once("**".to_string())
+        .chain(p.split('/').map(str::to_string))
+        .collect(),
+    };
+    Some(Glob(segs.into_iter().filter(|s| !s.is_empty()).collect()))
+  }
+
+  fn matches(&self, path: &[&str]) -> bool {
+    match_segments(&self.0, path)
+  }
+
+  // A pattern ending in "/**" names a folder whose whole contents go
+  fn folder(&self) -> Option<Glob> {
+    match self.0.split_last() {
+      Some((last, rest)) if last == "**" && !rest.is_empty() && rest.iter().any(|s| s != "**") => {
+        Some(Glob(rest.to_vec()))
+      }
+      _ => None,
+    }
+  }
+}
+
+fn fold_case(s: &str) -> String {
+  if cfg!(target_os = "linux") {
+    s.to_string()
+  } else {
+    s.to_lowercase()
+  }
+}
+
+fn match_segments(pat: &[String], path: &[&str]) -> bool {
+  match pat.split_first() {
+    None => path.is_empty(),
+    Some((p, rest)) if p == "**" => (0..=path.len()).any(|i| match_segments(rest, &path[i..])),
+    Some((p, rest)) => match path.split_first() {
+      Some((s, path_rest)) => match_name(p.as_bytes(), s.as_bytes()) && match_segments(rest, path_rest),
+      None => false,
+    },
+  }
+}
+
+// One folder or file name against one pattern segment (bytes, so UTF-8 passes
+// through; "?" matches a single byte of a multi-byte character)
+fn match_name(pat: &[u8], name: &[u8]) -> bool {
+  match pat.first() {
+    None => name.is_empty(),
+    Some(b'*') => (0..=name.len()).any(|i| match_name(&pat[1..], &name[i..])),
+    Some(b'?') => !name.is_empty() && match_name(&pat[1..], &name[1..]),
+    Some(b'[') => match (class_end(pat), name.first()) {
+      (Some(end), Some(&c)) => class_matches(&pat[1..end], c) && match_name(&pat[end + 1..], &name[1..]),
+      (Some(_), None) => false,
+      // Unclosed "[" is a literal
+      (None, Some(&c)) => c == b'[' && match_name(&pat[1..], &name[1..]),
+      (None, None) => false,
+    },
+    Some(&c) => name.first() == Some(&c) && match_name(&pat[1..], &name[1..]),
+  }
+}
+
+fn class_end(pat: &[u8]) -> Option<usize> {
+  // "]" right after "[" or "[!" is part of the class
+  let start = if pat.get(1) == Some(&b'!') { 2 } else { 1 };
+  pat.iter().skip(start + 1).position(|&b| b == b']').map(|i| i + start + 1)
+}
+
+fn class_matches(class: &[u8], c: u8) -> bool {
+  let (negated, class) = match class.split_first() {
+    Some((b'!', rest)) => (true, rest),
+    _ => (false, class),
+  };
+  let mut hit = false;
+  let mut i = 0;
+  while i < class.len() {
+    if i + 2 < class.len() && class[i + 1] == b'-' {
+      hit |= (class[i]..=class[i + 2]).contains(&c);
+      i += 3;
+    } else {
+      hit |= class[i] == c;
+      i += 1;
+    }
+  }
+  hit != negated
+}
+
+/* ---------------------------------- Hidden ---------------------------------- */
+/* skip_hidden leaves out dotfiles and dot-folders, the litter Finder and
+   Explorer drop on every drive they touch, and on Windows anything with the
+   hidden or system attribute. Only inside picked folders: a hidden file picked
+   on its own is copied. */
+
+const JUNK: &[&str] = &[
+  "thumbs.db",
+  "ehthumbs.db",
+  "desktop.ini",
+  "__macosx",
+  "$recycle.bin",
+  "system volume information",
+];
+
+#[cfg(windows)]
+fn hidden_attr(path: &Path) -> bool {
+  use std::os::windows::fs::MetadataExt;
+  const HIDDEN_OR_SYSTEM: u32 = 0x2 | 0x4;
+  std::fs::symlink_metadata(path).is_ok_and(|m| m.file_attributes() & HIDDEN_OR_SYSTEM != 0)
+}
+
+#[cfg(not(windows))]
+fn hidden_attr(_path: &Path) -> bool {
+  false
+}
+
+pub fn is_hidden(path: &Path) -> bool {
+  let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+  name.starts_with('.') || JUNK.contains(&name.as_str()) || hidden_attr(path)
+}
+
+/* ---------------------------------- Filters --------------------------------- */
+
+// What the filters left out of a scan. Files inside a skipped folder aren't
+// counted (the point is not to walk it), only the folder itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Excluded {
+  pub files: u64,
+  pub bytes: u64,
+  pub folders: u64,
+}
+
+pub struct Filters {
+  include: Vec<Glob>,
+  exclude: Vec<Glob>,
+  exclude_folders: Vec<Glob>,
+  skip_hidden: bool,
+}
+
+fn segments(rel: &str) -> Vec<&str> {
+  rel.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+fn rel_string(rel: &Path) -> String {
+  fold_case(&rel.to_string_lossy().replace('\\', "/"))
+}
+
+impl Filters {
+  pub fn new(options: &TransferOptions) -> Self {
+    let exclude: Vec<Glob> = options.exclude_globs.iter().filter_map(|p| Glob::new(p)).collect();
+    Filters {
+      include: options.include_globs.iter().filter_map(|p| Glob::new(p)).collect(),
+      exclude_folders: exclude.iter().filter_map(Glob::folder).collect(),
+      exclude,
+      skip_hidden: options.skip_hidden,
+    }
+  }
+
+  /// skip_hidden is on and `path` (inside a picked folder) is hidden or junk.
+  pub fn hides(&self, path: &Path) -> bool {
+    self.skip_hidden && is_hidden(path)
+  }
+
+  /// Whether a file at `rel` (see the header for what it's relative to) is copied.
+  pub fn keeps_file(&self, rel: &Path) -> bool {
+    let rel = rel_string(rel);
+    let segs = segments(&rel);
+    if self.exclude.iter().any(|g| g.matches(&segs)) {
+      return false;
+    }
+    self.include.is_empty() || self.include.iter().any(|g| g.matches(&segs))
+  }
+
+  /// Whether the folder at `rel` should be walked at all.
+  pub fn walks_folder(&self, rel: &Path) -> bool {
+    let rel = rel_string(rel);
+    let segs = segments(&rel);
+    !self
+      .exclude
+      .iter()
+      .chain(&self.exclude_folders)
+      .any(|g| g.matches(&segs))
+  }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use sysinfo::Disks;
+
+use crate::netfs;
+
+/* ----------------------------- Destination limits --------------------------- */
+/* Cards and thumb drives are usually FAT32 or exFAT, and plenty of "portable"
+   SSDs ship as exFAT or NTFS. Those filesystems refuse things the source
+   happily holds: a 4 GiB+ clip on FAT32, a "14:32:05.wav" name, or a deep
+   folder tree past Windows' 260 characters. The copy would fail file by file
+   halfway through; preflight checks the planned destination paths up front. */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightWarning {
+  // "too_large" | "invalid_name" | "name_too_long" | "path_too_long"
+  pub kind: String,
+  // Source path
+  pub path: String,
+  pub message: String,
+}
+
+// FAT32 stores sizes in 32 bits
+const FAT_MAX_FILE: u64 = 4 * 1024 * 1024 * 1024 - 1;
+
+// Characters Windows-family filesystems reject in names
+const WINDOWS_INVALID: &[char] = &['"', '*', ':', '<', '>', '?', '\\', '|'];
+
+const WINDOWS_RESERVED: &[&str] = &[
+  "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9", "LPT1", "LPT2",
+  "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Family {
+  Fat,
+  ExFat,
+  Ntfs,
+  Other,
+}
+
+fn family(fs_type: &str) -> Family {
+  match fs_type {
+    "msdos" | "vfat" | "fat" | "fat16" | "fat32" => Family::Fat,
+    "exfat" | "fuseblk.exfat" => Family::ExFat,
+    "ntfs" | "ntfs3" | "ntfs-3g" | "fuseblk" | "ufsd_ntfs" => Family::Ntfs,
+    _ => Family::Other,
+  }
+}
+
+/// Filesystem type of `mount_point`, lowercased: from the mount table, else sysinfo
+/// (Windows has no `mount`).
+pub fn dest_fs_type(mount_point: &str) -> Option<String> {
+  netfs::fs_type_for(mount_point).or_else(|| {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+      .list()
+      .iter()
+      .filter(|d| Path::new(mount_point).starts_with(d.mount_point()))
+      .max_by_key(|d| d.mount_point().as_os_str().len())
+      .map(|d| d.file_system().to_string_lossy().to_lowercase())
+      .filter(|t| !t.is_empty())
+  })
+}
+
+pub struct Limits {
+  fs_type: String,
+  family: Family,
+  // Longest name, in bytes (UTF-8) or UTF-16 units on Windows-family filesystems
+  name_max: usize,
+  // Longest full path, counted the same way
+  path_max: usize,
+}
+
+impl Limits {
+  pub fn for_mount(mount_point: &str) -> Self {
+    let fs_type = dest_fs_type(mount_point).unwrap_or_default();
+    let family = family(&fs_type);
+    // The host's own limit (Windows without long path support: MAX_PATH)
+    let host_max = if cfg!(windows) {
+      260
+    } else if cfg!(target_os = "macos") {
+      1024
+    } else {
+      4096
+    };
+    Limits {
+      path_max: if family == Family::Fat { host_max.min(260) } else { host_max },
+      name_max: 255,
+      fs_type,
+      family,
+    }
+  }
+
+  fn windows_names(&self) -> bool {
+    self.family != Family::Other
+  }
+
+  fn len(&self, s: &str) -> usize {
+    if self.windows_names() {
+      s.encode_utf16().count()
+    } else {
+      s.len()
+    }
+  }
+
+  /// Problems copying a `bytes`-long file from `src` to `dst`, in the order they'd be hit.
+  pub fn check(&self, src: &Path, dst: &Path, bytes: u64) -> Vec<PreflightWarning> {
+    let path = src.to_string_lossy().to_string();
+    let warn = |kind: &str, message: String| PreflightWarning {
+      kind: kind.to_string(),
+      path: path.clone(),
+      message,
+    };
+    let mut out = vec![];
+
+    if self.family == Family::Fat && bytes > FAT_MAX_FILE {
+      out.push(warn(
+        "too_large",
+        format!("{bytes} bytes is over the 4 GiB FAT32 file size limit; use an exFAT or NTFS drive"),
+      ));
+    }
+
+    let name = dst.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if self.windows_names() {
+      let stem = name.split('.').next().unwrap_or("").trim_end().to_uppercase();
+      if let Some(c) = name.chars().find(|c| WINDOWS_INVALID.contains(c) || (*c as u32) < 0x20) {
+        out.push(warn("invalid_name", format!("\"{name}\" contains {c:?}, which {} doesn't allow", self.fs_type)));
+      } else if name.ends_with('.') || name.ends_with(' ') {
+        out.push(warn("invalid_name", format!("\"{name}\" ends with a dot or space, which {} drops", self.fs_type)));
+      } else if WINDOWS_RESERVED.contains(&stem.as_str()) {
+        out.push(warn("invalid_name", format!("\"{name}\" is a reserved device name on Windows")));
+      }
+    }
+    if self.len(&name) > self.name_max {
+      out.push(warn(
+        "name_too_long",
+        format!("file name is {} characters; {} allows {}", self.len(&name), self.fs_type, self.name_max),
+      ));
+    }
+
+    let full = self.len(&dst.to_string_lossy());
+    if full > self.path_max {
+      out.push(warn(
+        "path_too_long",
+        format!("destination path would be {full} characters; the limit is {}", self.path_max),
+      ));
+    }
+    out
+  }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+  sync::{Mutex, OnceLock},
+};
+
+use crate::{checksum::Algo, logging};
+
+/* ----------------------------------- Types ---------------------------------- */
+/* hash_cache.json in the app data dir remembers digests of files we've already
+   read, keyed by path. An entry is only trusted while the file's size and mtime
+   still match, so re-scanning a large archive only hashes what changed. */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedHash {
+  size: u64,
+  // Modification time as nanoseconds since the epoch
+  mtime_ns: u128,
+  algo: String,
+  checksum: String,
+}
+
+struct CacheState {
+  path: Option<PathBuf>,
+  entries: HashMap<String, CachedHash>,
+  dirty: bool,
+}
+
+static CACHE: OnceLock<Mutex<CacheState>> = OnceLock::new();
+
+fn state() -> &'static Mutex<CacheState> {
+  CACHE.get_or_init(|| {
+    Mutex::new(CacheState {
+      path: None,
+      entries: HashMap::new(),
+      dirty: false,
+    })
+  })
+}
+
+pub fn set_cache_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  let entries = fs::read_to_string(&path)
+    .ok()
+    .and_then(|s| serde_json::from_str(&s).ok())
+    .unwrap_or_default();
+  if let Ok(mut g) = state().lock() {
+    g.path = Some(path);
+    g.entries = entries;
+    g.dirty = false;
+  }
+}
+
+fn mtime_ns(meta: &fs::Metadata) -> Option<u128> {
+  meta
+    .modified()
+    .ok()?
+    .duration_since(std::time::UNIX_EPOCH)
+    .ok()
+    .map(|d| d.as_nanos())
+}
+
+/* ---------------------------------- Lookup ---------------------------------- */
+
+/// Cached digest of `path`, if one was stored with the same algorithm and the file
+/// hasn't changed size or mtime since.
+pub fn lookup(path: &Path, meta: &fs::Metadata, algo: Algo) -> Option<String> {
+  let mtime = mtime_ns(meta)?;
+  let g = state().lock().ok()?;
+  g.entries
+    .get(&*path.to_string_lossy())
+    .filter(|e| e.size == meta.len() && e.mtime_ns == mtime && e.algo == algo.name())
+    .map(|e| e.checksum.clone())
+}
+
+pub fn store(path: &Path, meta: &fs::Metadata, algo: Algo, checksum: &str) {
+  let Some(mtime) = mtime_ns(meta) else {
+    return;
+  };
+  if let Ok(mut g) = state().lock() {
+    g.entries.insert(
+      path.to_string_lossy().to_string(),
+      CachedHash {
+        size: meta.len(),
+        mtime_ns: mtime,
+        algo: algo.name().to_string(),
+        checksum: checksum.to_string(),
+      },
+    );
+    g.dirty = true;
+  }
+}
+
+/// Writes the cache back to disk if anything was stored since the last save.
+pub fn save() {
+  let Ok(mut g) = state().lock() else {
+    return;
+  };
+  if !g.dirty {
+    return;
+  }
+  let Some(path) = g.path.clone() else {
+    return;
+  };
+  let res = serde_json::to_string(&g.entries)
+    .map_err(|e| e.to_string())
+    .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+  match res {
+    Ok(_) => g.dirty = false,
+    Err(e) => logging::warn(&format!("hash cache write error: {e}")),
+  }
+}
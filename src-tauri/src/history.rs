@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::PathBuf,
+  sync::{Mutex, OnceLock},
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+/// One finished session, as history.db stores it (and history.jsonl once did). An
+/// offload writes one record per destination drive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+  pub job_id: String,
+  pub kind: String, // "transfer" | "offload"
+  pub started_at: String,
+  pub finished_at: String,
+  pub duration_ms: u64,
+  pub outcome: String, // "done" | "stopped" | "cancelled"
+  pub session_dir: String,
+  pub dest_mount_point: String,
+  #[serde(default)]
+  pub dest_uuid: Option<String>,
+  pub copy_mode: String,
+  pub verify_mode: String,
+  pub total_files: u64,
+  pub total_bytes: u64,
+  pub copied_files: u64,
+  pub moved_files: u64,
+  pub skipped_files: u64,
+  pub error_files: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsRange {
+  // Inclusive, "YYYY-MM-DD" (or a full RFC 3339 timestamp); None = unbounded
+  pub from: Option<String>,
+  pub to: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonthStats {
+  pub month: String, // "YYYY-MM"
+  pub sessions: u64,
+  pub files: u64,
+  pub bytes: u64,
+  pub error_files: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeviceStats {
+  // Volume UUID when known, else the mount point
+  pub device: String,
+  pub mount_point: String,
+  pub sessions: u64,
+  pub files: u64,
+  pub bytes: u64,
+  pub error_files: u64,
+  pub error_rate: f64, // error_files / files, 0..=1
+  pub avg_bytes_per_sec: f64,
+  pub last_used: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferStats {
+  pub sessions: u64,
+  pub files: u64,
+  pub bytes: u64,
+  pub error_files: u64,
+  pub error_rate: f64,
+  pub cancelled_sessions: u64,
+  pub avg_bytes_per_sec: f64,
+  // Oldest month first
+  pub by_month: Vec<MonthStats>,
+  // Most bytes first
+  pub by_device: Vec<DeviceStats>,
+}
+
+/* ------------------------------- Legacy store ------------------------------- */
+/* Sessions used to be appended to history.jsonl; history.db (history_db.rs) is
+   the only store now. The file is read once, to import it, then renamed to
+   history.jsonl.imported so it's never imported twice. */
+
+static HISTORY_FILE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Option<PathBuf>> {
+  HISTORY_FILE.get_or_init(|| Mutex::new(None))
+}
+
+fn history_file() -> Result<PathBuf, String> {
+  slot()
+    .lock()
+    .ok()
+    .and_then(|g| g.clone())
+    .ok_or_else(|| "history store not initialised".to_string())
+}
+
+/// Where an older version kept history.jsonl; call before history_db::set_db_file.
+pub fn set_history_file(path: PathBuf) {
+  if let Ok(mut g) = slot().lock() {
+    *g = Some(path);
+  }
+}
+
+/// Sessions in history.jsonl; empty once it has been imported.
+pub(crate) fn read_records() -> Result<Vec<HistoryRecord>, String> {
+  let path = history_file()?;
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+  let s = fs::read_to_string(&path).map_err(|e| format!("history read error: {e}"))?;
+  Ok(
+    s.lines()
+      .filter(|l| !l.trim().is_empty())
+      .filter_map(|l| serde_json::from_str(l).ok())
+      .collect(),
+  )
+}
+
+/// Renames history.jsonl out of the way; kept rather than deleted, as a backup.
+pub(crate) fn retire_history_file() -> Result<(), String> {
+  let path = history_file()?;
+  if !path.exists() {
+    return Ok(());
+  }
+  fs::rename(&path, path.with_extension("jsonl.imported")).map_err(|e| format!("history rename error: {e}"))
+}
@@ -0,0 +1,478 @@
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::{Mutex, OnceLock},
+};
+
+use crate::{
+  history::{self, DeviceStats, HistoryRecord, MonthStats, StatsRange, TransferStats},
+  logging,
+  transfer::{ManifestItem, TransferOptions},
+};
+
+/* --------------------------------- History DB ------------------------------- */
+/* The transfer history, in history.db (SQLite, app data dir): every session's
+   record, the settings it ran with, and one row per file, so past transfers can
+   be searched by file name without the drive their manifest.json is on. Older
+   versions kept history.jsonl instead; it's imported once, without files for
+   those sessions, and then retired (see history.rs). */
+
+const SCHEMA: &str = "
+  CREATE TABLE IF NOT EXISTS sessions (
+    id INTEGER PRIMARY KEY,
+    job_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    outcome TEXT NOT NULL,
+    session_dir TEXT NOT NULL,
+    dest_mount_point TEXT NOT NULL,
+    dest_uuid TEXT,
+    copy_mode TEXT NOT NULL,
+    verify_mode TEXT NOT NULL,
+    total_files INTEGER NOT NULL,
+    total_bytes INTEGER NOT NULL,
+    copied_files INTEGER NOT NULL,
+    moved_files INTEGER NOT NULL,
+    skipped_files INTEGER NOT NULL,
+    error_files INTEGER NOT NULL,
+    settings TEXT
+  );
+  CREATE INDEX IF NOT EXISTS sessions_started ON sessions(started_at);
+  CREATE TABLE IF NOT EXISTS files (
+    session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    source TEXT NOT NULL,
+    dest TEXT NOT NULL,
+    bytes INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    error_class TEXT,
+    checksum TEXT,
+    checksum_algo TEXT,
+    retries INTEGER NOT NULL DEFAULT 0
+  );
+  CREATE INDEX IF NOT EXISTS files_session ON files(session_id);
+  CREATE INDEX IF NOT EXISTS files_name ON files(name);
+";
+
+// query_history without a limit
+const DEFAULT_LIMIT: u32 = 200;
+
+/* ----------------------------------- Types ---------------------------------- */
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryFilter {
+  // Inclusive, "YYYY-MM-DD" (or a full RFC 3339 timestamp), as in StatsRange
+  pub from: Option<String>,
+  pub to: Option<String>,
+  // Volume UUID or mount point
+  pub device: Option<String>,
+  // Part of a file name (case-insensitive for ASCII); only sessions with a match
+  pub filename: Option<String>,
+  // "done" | "stopped" | "cancelled"
+  pub outcome: Option<String>,
+  // Newest first, this many at most (default 200)
+  pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistorySession {
+  pub id: i64,
+  #[serde(flatten)]
+  pub record: HistoryRecord,
+  // Files whose name matched the filename filter (0 without one)
+  pub matched_files: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryFile {
+  pub name: String,
+  pub source: String,
+  pub dest: String,
+  pub bytes: u64,
+  pub status: String,
+  pub error: Option<String>,
+  pub error_class: Option<String>,
+  pub checksum: Option<String>,
+  pub checksum_algo: Option<String>,
+  pub retries: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionDetail {
+  #[serde(flatten)]
+  pub session: HistorySession,
+  // TransferOptions it ran with; None for sessions imported from history.jsonl
+  pub settings: Option<serde_json::Value>,
+  pub files: Vec<HistoryFile>,
+}
+
+/* ----------------------------------- Store ---------------------------------- */
+
+static DB_FILE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Option<PathBuf>> {
+  DB_FILE.get_or_init(|| Mutex::new(None))
+}
+
+/// Call after history::set_history_file: a history.jsonl still there is imported.
+pub fn set_db_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  if let Ok(mut g) = slot().lock() {
+    *g = Some(path);
+  }
+  if let Err(e) = open().and_then(|mut conn| import_jsonl(&mut conn)) {
+    logging::warn(&format!("history db error: {e}"));
+  }
+}
+
+fn open() -> Result<Connection, String> {
+  let path = slot()
+    .lock()
+    .ok()
+    .and_then(|g| g.clone())
+    .ok_or_else(|| "history db not initialised".to_string())?;
+  let conn = Connection::open(&path).map_err(|e| format!("history db open error: {e}"))?;
+  conn
+    .execute_batch(&format!("PRAGMA foreign_keys = ON; {SCHEMA}"))
+    .map_err(|e| format!("history db schema error: {e}"))?;
+  Ok(conn)
+}
+
+fn import_jsonl(conn: &mut Connection) -> Result<(), String> {
+  let records = history::read_records()?;
+  let count: i64 = conn
+    .query_row("SELECT COUNT(*) FROM sessions", [], |r| r.get(0))
+    .map_err(|e| format!("history db error: {e}"))?;
+  // Versions that had both stores wrote every session to each, so a database with
+  // sessions already holds the file's
+  if count == 0 && !records.is_empty() {
+    let tx = conn.transaction().map_err(|e| format!("history db error: {e}"))?;
+    for rec in &records {
+      insert_session(&tx, rec, None)?;
+    }
+    tx.commit().map_err(|e| format!("history db error: {e}"))?;
+    logging::info(&format!("history db: imported {} sessions from history.jsonl", records.len()));
+  }
+  history::retire_history_file()
+}
+
+fn insert_session(conn: &Connection, rec: &HistoryRecord, settings: Option<&str>) -> Result<i64, String> {
+  conn
+    .execute(
+      "INSERT INTO sessions (job_id, kind, started_at, finished_at, duration_ms, outcome, session_dir,
+         dest_mount_point, dest_uuid, copy_mode, verify_mode, total_files, total_bytes, copied_files,
+         moved_files, skipped_files, error_files, settings)
+       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+      params![
+        rec.job_id,
+        rec.kind,
+        rec.started_at,
+        rec.finished_at,
+        rec.duration_ms as i64,
+        rec.outcome,
+        rec.session_dir,
+        rec.dest_mount_point,
+        rec.dest_uuid,
+        rec.copy_mode,
+        rec.verify_mode,
+        rec.total_files as i64,
+        rec.total_bytes as i64,
+        rec.copied_files as i64,
+        rec.moved_files as i64,
+        rec.skipped_files as i64,
+        rec.error_files as i64,
+        settings,
+      ],
+    )
+    .map_err(|e| format!("history db insert error: {e}"))?;
+  Ok(conn.last_insert_rowid())
+}
+
+/// Adds a finished session with its settings and per-file rows. Failures are
+/// logged, never raised: history isn't worth failing a transfer over.
+pub(crate) fn record(rec: &HistoryRecord, options: &TransferOptions, rows: &[ManifestItem]) {
+  if let Err(e) = try_record(rec, options, rows) {
+    logging::warn(&format!("history db write error: {e}"));
+  }
+}
+
+fn try_record(rec: &HistoryRecord, options: &TransferOptions, rows: &[ManifestItem]) -> Result<(), String> {
+  let settings = serde_json::to_string(options).map_err(|e| format!("settings json error: {e}"))?;
+  let mut conn = open()?;
+  let tx = conn.transaction().map_err(|e| format!("history db error: {e}"))?;
+  let id = insert_session(&tx, rec, Some(&settings))?;
+  {
+    let mut stmt = tx
+      .prepare(
+        "INSERT INTO files (session_id, name, source, dest, bytes, status, error, error_class, checksum,
+           checksum_algo, retries)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+      )
+      .map_err(|e| format!("history db error: {e}"))?;
+    for row in rows {
+      let name = Path::new(&row.source)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| row.source.clone());
+      stmt
+        .execute(params![
+          id,
+          name,
+          row.source,
+          row.dest,
+          row.bytes as i64,
+          row.status,
+          row.error,
+          row.error_class,
+          row.checksum,
+          row.checksum_algo,
+          row.retries,
+        ])
+        .map_err(|e| format!("history db insert error: {e}"))?;
+    }
+  }
+  tx.commit().map_err(|e| format!("history db error: {e}"))
+}
+
+/* ---------------------------------- Queries --------------------------------- */
+
+const SESSION_COLUMNS: &str = "s.id, s.job_id, s.kind, s.started_at, s.finished_at, s.duration_ms, s.outcome,
+  s.session_dir, s.dest_mount_point, s.dest_uuid, s.copy_mode, s.verify_mode, s.total_files, s.total_bytes,
+  s.copied_files, s.moved_files, s.skipped_files, s.error_files";
+
+fn session_from_row(r: &Row, matched_files: u64) -> rusqlite::Result<HistorySession> {
+  Ok(HistorySession {
+    id: r.get(0)?,
+    record: HistoryRecord {
+      job_id: r.get(1)?,
+      kind: r.get(2)?,
+      started_at: r.get(3)?,
+      finished_at: r.get(4)?,
+      duration_ms: r.get::<_, i64>(5)? as u64,
+      outcome: r.get(6)?,
+      session_dir: r.get(7)?,
+      dest_mount_point: r.get(8)?,
+      dest_uuid: r.get(9)?,
+      copy_mode: r.get(10)?,
+      verify_mode: r.get(11)?,
+      total_files: r.get::<_, i64>(12)? as u64,
+      total_bytes: r.get::<_, i64>(13)? as u64,
+      copied_files: r.get::<_, i64>(14)? as u64,
+      moved_files: r.get::<_, i64>(15)? as u64,
+      skipped_files: r.get::<_, i64>(16)? as u64,
+      error_files: r.get::<_, i64>(17)? as u64,
+    },
+    matched_files,
+  })
+}
+
+// RFC 3339 local timestamps sort lexically by date, so ranges compare the date part
+fn day(s: &Option<String>) -> Option<String> {
+  s.as_deref().map(|d| d.get(..10).unwrap_or(d).to_string())
+}
+
+fn get_u64(r: &Row, i: usize) -> rusqlite::Result<u64> {
+  Ok(r.get::<_, i64>(i)? as u64)
+}
+
+// LIKE treats % and _ as wildcards; a file name is taken literally
+fn like_pattern(s: &str) -> String {
+  let escaped = s.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+  format!("%{escaped}%")
+}
+
+/// Past sessions matching `filter`, newest first.
+pub fn query(filter: &HistoryFilter) -> Result<Vec<HistorySession>, String> {
+  let conn = open()?;
+  let pattern = filter.filename.as_deref().filter(|f| !f.trim().is_empty()).map(like_pattern);
+
+  let sql = format!(
+    "SELECT {SESSION_COLUMNS},
+       (SELECT COUNT(*) FROM files f WHERE f.session_id = s.id AND ?5 IS NOT NULL AND f.name LIKE ?5 ESCAPE '\\')
+         AS matched
+     FROM sessions s
+     WHERE (?1 IS NULL OR substr(s.started_at, 1, 10) >= ?1)
+       AND (?2 IS NULL OR substr(s.started_at, 1, 10) <= ?2)
+       AND (?3 IS NULL OR s.dest_uuid = ?3 OR s.dest_mount_point = ?3)
+       AND (?4 IS NULL OR s.outcome = ?4)
+       AND (?5 IS NULL OR matched > 0)
+     ORDER BY s.started_at DESC, s.id DESC
+     LIMIT ?6"
+  );
+  let mut stmt = conn.prepare(&sql).map_err(|e| format!("history db query error: {e}"))?;
+  let rows = stmt
+    .query_map(
+      params![
+        day(&filter.from),
+        day(&filter.to),
+        filter.device,
+        filter.outcome,
+        pattern,
+        filter.limit.unwrap_or(DEFAULT_LIMIT),
+      ],
+      |r| session_from_row(r, r.get::<_, i64>(18)? as u64),
+    )
+    .map_err(|e| format!("history db query error: {e}"))?;
+  rows
+    .collect::<rusqlite::Result<Vec<_>>>()
+    .map_err(|e| format!("history db query error: {e}"))
+}
+
+/// One session with its settings and every file row.
+pub fn session_detail(id: i64) -> Result<SessionDetail, String> {
+  let conn = open()?;
+  let found = conn
+    .query_row(
+      &format!("SELECT {SESSION_COLUMNS}, s.settings FROM sessions s WHERE s.id = ?1"),
+      params![id],
+      |r| Ok((session_from_row(r, 0)?, r.get::<_, Option<String>>(18)?)),
+    )
+    .optional()
+    .map_err(|e| format!("history db query error: {e}"))?;
+  let (session, settings) = found.ok_or_else(|| format!("no history session with id {id}"))?;
+
+  let mut stmt = conn
+    .prepare(
+      "SELECT name, source, dest, bytes, status, error, error_class, checksum, checksum_algo, retries
+       FROM files WHERE session_id = ?1 ORDER BY rowid",
+    )
+    .map_err(|e| format!("history db query error: {e}"))?;
+  let files = stmt
+    .query_map(params![id], |r| {
+      Ok(HistoryFile {
+        name: r.get(0)?,
+        source: r.get(1)?,
+        dest: r.get(2)?,
+        bytes: r.get::<_, i64>(3)? as u64,
+        status: r.get(4)?,
+        error: r.get(5)?,
+        error_class: r.get(6)?,
+        checksum: r.get(7)?,
+        checksum_algo: r.get(8)?,
+        retries: r.get(9)?,
+      })
+    })
+    .map_err(|e| format!("history db query error: {e}"))?
+    .collect::<rusqlite::Result<Vec<_>>>()
+    .map_err(|e| format!("history db query error: {e}"))?;
+
+  Ok(SessionDetail {
+    session,
+    settings: settings.and_then(|s| serde_json::from_str(&s).ok()),
+    files,
+  })
+}
+
+/* ----------------------------------- Stats ---------------------------------- */
+
+// Sessions started between ?1 and ?2 (either may be NULL)
+const IN_RANGE: &str =
+  "(?1 IS NULL OR substr(started_at, 1, 10) >= ?1) AND (?2 IS NULL OR substr(started_at, 1, 10) <= ?2)";
+
+fn rate(bytes: u64, ms: u64) -> f64 {
+  if ms == 0 {
+    0.0
+  } else {
+    bytes as f64 / (ms as f64 / 1000.0)
+  }
+}
+
+fn ratio(n: u64, d: u64) -> f64 {
+  if d == 0 {
+    0.0
+  } else {
+    n as f64 / d as f64
+  }
+}
+
+/// Totals over the history in `range`: per month, per destination device, and overall.
+pub fn stats(range: &StatsRange) -> Result<TransferStats, String> {
+  let conn = open()?;
+  let (from, to) = (day(&range.from), day(&range.to));
+  let err = |e: rusqlite::Error| format!("history db stats error: {e}");
+
+  let mut out = conn
+    .query_row(
+      &format!(
+        "SELECT COUNT(*), COALESCE(SUM(total_files), 0), COALESCE(SUM(total_bytes), 0),
+           COALESCE(SUM(error_files), 0), COALESCE(SUM(outcome = 'cancelled'), 0), COALESCE(SUM(duration_ms), 0)
+         FROM sessions WHERE {IN_RANGE}"
+      ),
+      params![from, to],
+      |r| {
+        let (files, bytes, error_files) = (get_u64(r, 1)?, get_u64(r, 2)?, get_u64(r, 3)?);
+        Ok(TransferStats {
+          sessions: get_u64(r, 0)?,
+          files,
+          bytes,
+          error_files,
+          error_rate: ratio(error_files, files),
+          cancelled_sessions: get_u64(r, 4)?,
+          avg_bytes_per_sec: rate(bytes, get_u64(r, 5)?),
+          ..Default::default()
+        })
+      },
+    )
+    .map_err(err)?;
+
+  let mut stmt = conn
+    .prepare(&format!(
+      "SELECT CASE WHEN length(started_at) >= 7 THEN substr(started_at, 1, 7) ELSE 'unknown' END AS month,
+         COUNT(*), SUM(total_files), SUM(total_bytes), SUM(error_files)
+       FROM sessions WHERE {IN_RANGE}
+       GROUP BY month ORDER BY month"
+    ))
+    .map_err(err)?;
+  out.by_month = stmt
+    .query_map(params![from, to], |r| {
+      Ok(MonthStats {
+        month: r.get(0)?,
+        sessions: get_u64(r, 1)?,
+        files: get_u64(r, 2)?,
+        bytes: get_u64(r, 3)?,
+        error_files: get_u64(r, 4)?,
+      })
+    })
+    .map_err(err)?
+    .collect::<rusqlite::Result<Vec<_>>>()
+    .map_err(err)?;
+
+  // With a single max() in the query, SQLite takes the bare dest_mount_point from
+  // that row: a drive can remount elsewhere, so show where it was seen last
+  let mut stmt = conn
+    .prepare(&format!(
+      "SELECT COALESCE(dest_uuid, dest_mount_point) AS device, dest_mount_point, COUNT(*), SUM(total_files),
+         SUM(total_bytes), SUM(error_files), SUM(duration_ms), MAX(finished_at)
+       FROM sessions WHERE {IN_RANGE}
+       GROUP BY device ORDER BY SUM(total_bytes) DESC, device"
+    ))
+    .map_err(err)?;
+  out.by_device = stmt
+    .query_map(params![from, to], |r| {
+      let (files, bytes, error_files) = (get_u64(r, 3)?, get_u64(r, 4)?, get_u64(r, 5)?);
+      Ok(DeviceStats {
+        device: r.get(0)?,
+        mount_point: r.get(1)?,
+        sessions: get_u64(r, 2)?,
+        files,
+        bytes,
+        error_files,
+        error_rate: ratio(error_files, files),
+        avg_bytes_per_sec: rate(bytes, get_u64(r, 6)?),
+        last_used: r.get(7)?,
+      })
+    })
+    .map_err(err)?
+    .collect::<rusqlite::Result<Vec<_>>>()
+    .map_err(err)?;
+
+  Ok(out)
+}
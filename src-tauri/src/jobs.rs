@@ -0,0 +1,276 @@
+use std::{
+  collections::{HashMap, HashSet},
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicBool, AtomicU8, Ordering},
+    Arc, Mutex,
+  },
+  thread,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  bottleneck::{IoMeter, IoStats},
+  speed::{SpeedMeter, ThroughputSample},
+  throttle::RateLimiter,
+  watchdog, Preflight,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+/// Control surface for one running transfer. Commands flip these flags; the
+/// engine polls them between files (or between chunks, for hard cancel).
+pub struct JobControl {
+  pub id: String,
+  // Hard cancel: abort mid-file
+  pub cancel: Arc<AtomicBool>,
+  // Soft cancel: finish + verify the in-flight file, then end the session
+  pub stop_after_current: AtomicBool,
+  // Held mid-file until resumed; the copy loop keeps its byte position
+  pub paused: AtomicBool,
+  // Pending answer to a `transfer://stalled` event
+  pub stall_action: Arc<AtomicU8>,
+  // Item ids (source paths) the user asked to skip, pending or in flight
+  pub skipped: Mutex<HashSet<String>>,
+  // Item ids to jump to the front of the pending queue, in request order
+  pub priority: Mutex<Vec<String>>,
+  // Session dirs this job is writing into (two for an offload)
+  pub session_dirs: Mutex<Vec<PathBuf>>,
+  // Read vs write timings, for the bottleneck shown in progress
+  pub io: Mutex<IoMeter>,
+  // Cap on this job's own throughput (max_bytes_per_sec; set_transfer_limit changes it mid-run)
+  pub limit: RateLimiter,
+  // Speed and ETA for the progress payload, plus the samples behind get_transfer_samples
+  pub speed: Mutex<SpeedMeter>,
+}
+
+impl JobControl {
+  fn new(id: String) -> Self {
+    JobControl {
+      id,
+      cancel: Arc::new(AtomicBool::new(false)),
+      stop_after_current: AtomicBool::new(false),
+      paused: AtomicBool::new(false),
+      stall_action: Arc::new(AtomicU8::new(watchdog::STALL_NONE)),
+      skipped: Mutex::new(HashSet::new()),
+      priority: Mutex::new(vec![]),
+      session_dirs: Mutex::new(vec![]),
+      io: Mutex::new(IoMeter::default()),
+      limit: RateLimiter::new(0),
+      speed: Mutex::new(SpeedMeter::default()),
+    }
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.cancel.load(Ordering::SeqCst)
+  }
+
+  pub fn should_stop(&self) -> bool {
+    self.stop_after_current.load(Ordering::SeqCst)
+  }
+
+  pub fn is_paused(&self) -> bool {
+    self.paused.load(Ordering::SeqCst)
+  }
+
+  pub fn skip(&self, item_id: &str) {
+    if let Ok(mut g) = self.skipped.lock() {
+      g.insert(item_id.to_string());
+    }
+  }
+
+  pub fn is_skipped(&self, item_id: &str) -> bool {
+    self.skipped.lock().map(|g| g.contains(item_id)).unwrap_or(false)
+  }
+
+  pub fn prioritize(&self, item_id: &str) {
+    if let Ok(mut g) = self.priority.lock() {
+      g.retain(|x| x != item_id);
+      g.push(item_id.to_string());
+    }
+  }
+
+  pub fn add_session_dir(&self, dir: &Path) {
+    if let Ok(mut g) = self.session_dirs.lock() {
+      g.push(dir.to_path_buf());
+    }
+  }
+
+  pub fn record_read(&self, bytes: u64, took: Duration) {
+    if let Ok(mut m) = self.io.lock() {
+      m.read(bytes, took);
+    }
+  }
+
+  pub fn record_write(&self, bytes: u64, took: Duration) {
+    if let Ok(mut m) = self.io.lock() {
+      m.write(bytes, took);
+    }
+  }
+
+  pub fn io_stats(&self) -> Option<IoStats> {
+    self.io.lock().ok().and_then(|m| m.stats())
+  }
+
+  /// (bytes_per_sec, eta_seconds, elapsed_ms) as of this progress sample.
+  pub fn speed_sample(&self, phase: &str, bytes_done: u64, bytes_total: u64) -> (u64, Option<u64>, u64) {
+    self
+      .speed
+      .lock()
+      .map(|mut m| m.sample(phase, bytes_done, bytes_total))
+      .unwrap_or((0, None, 0))
+  }
+
+  /// Throughput so far, about one sample a second.
+  pub fn speed_samples(&self) -> Vec<ThroughputSample> {
+    self.speed.lock().map(|m| m.samples()).unwrap_or_default()
+  }
+
+  /// Drains pending priority requests (oldest first).
+  pub fn take_priorities(&self) -> Vec<String> {
+    self
+      .priority
+      .lock()
+      .map(|mut g| std::mem::take(&mut *g))
+      .unwrap_or_default()
+  }
+}
+
+/* --------------------------------- Registry --------------------------------- */
+
+#[derive(Default)]
+pub struct Jobs(Mutex<HashMap<String, Arc<JobControl>>>);
+
+impl Jobs {
+  /// Registers a job under `id` (or a fresh UUID) and returns its controls. An id
+  /// that's still running is refused: replacing it would orphan the running job's
+  /// cancel/pause, and whichever finished first would remove the other.
+  pub fn register(&self, id: Option<String>) -> Result<Arc<JobControl>, String> {
+    let id = id
+      .filter(|s| !s.trim().is_empty())
+      .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let job = Arc::new(JobControl::new(id.clone()));
+    let mut g = self.0.lock().map_err(|_| "job registry poisoned".to_string())?;
+    if g.contains_key(&id) {
+      return Err(format!("job id already running: {id}"));
+    }
+    g.insert(id, job.clone());
+    Ok(job)
+  }
+
+  /// For commands whose caller picks the id, so it knows which job's events and
+  /// controls are its own before the command returns.
+  pub fn register_as(&self, id: String) -> Result<Arc<JobControl>, String> {
+    if id.trim().is_empty() {
+      return Err("job id is required".to_string());
+    }
+    self.register(Some(id))
+  }
+
+  pub fn remove(&self, id: &str) {
+    if let Ok(mut g) = self.0.lock() {
+      g.remove(id);
+    }
+  }
+
+  pub fn get(&self, id: &str) -> Result<Arc<JobControl>, String> {
+    self
+      .0
+      .lock()
+      .map_err(|_| "job registry poisoned".to_string())?
+      .get(id)
+      .cloned()
+      .ok_or_else(|| format!("no running job with id {id}"))
+  }
+
+  pub fn ids(&self) -> Vec<String> {
+    self.0.lock().map(|g| g.keys().cloned().collect()).unwrap_or_default()
+  }
+
+  /// Session dirs that running jobs are still writing into.
+  pub fn active_session_dirs(&self) -> Vec<PathBuf> {
+    let Ok(g) = self.0.lock() else {
+      return vec![];
+    };
+    g.values()
+      .flat_map(|j| j.session_dirs.lock().map(|d| d.clone()).unwrap_or_default())
+      .collect()
+  }
+
+  /// `Some(id)` targets one job; `None` targets every running job.
+  pub fn select(&self, id: Option<&str>) -> Result<Vec<Arc<JobControl>>, String> {
+    match id {
+      Some(id) => Ok(vec![self.get(id)?]),
+      None => Ok(
+        self
+          .0
+          .lock()
+          .map_err(|_| "job registry poisoned".to_string())?
+          .values()
+          .cloned()
+          .collect(),
+      ),
+    }
+  }
+}
+
+/* --------------------------------- Preflight -------------------------------- */
+/* Preflights aren't jobs (nothing is written), but a scan of a big network share
+   can run for minutes, so each one gets its own cancel flag. */
+
+pub struct PreflightControl {
+  pub id: String,
+  pub cancel: AtomicBool,
+  // Where the scan leaves its result (partial, if it was cancelled)
+  pub result: Mutex<Option<Preflight>>,
+}
+
+impl PreflightControl {
+  pub fn is_cancelled(&self) -> bool {
+    self.cancel.load(Ordering::SeqCst)
+  }
+
+  /// Waits (up to `timeout`) for the scan to publish its result.
+  pub fn wait_result(&self, timeout: Duration) -> Option<Preflight> {
+    let deadline = Instant::now() + timeout;
+    loop {
+      if let Some(p) = self.result.lock().ok().and_then(|g| g.clone()) {
+        return Some(p);
+      }
+      if Instant::now() >= deadline {
+        return None;
+      }
+      thread::sleep(Duration::from_millis(20));
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct Preflights(Mutex<HashMap<String, Arc<PreflightControl>>>);
+
+impl Preflights {
+  pub fn register(&self, id: Option<String>) -> Arc<PreflightControl> {
+    let id = id
+      .filter(|s| !s.trim().is_empty())
+      .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let ctl = Arc::new(PreflightControl {
+      id: id.clone(),
+      cancel: AtomicBool::new(false),
+      result: Mutex::new(None),
+    });
+    if let Ok(mut g) = self.0.lock() {
+      g.insert(id, ctl.clone());
+    }
+    ctl
+  }
+
+  pub fn remove(&self, id: &str) {
+    if let Ok(mut g) = self.0.lock() {
+      g.remove(id);
+    }
+  }
+
+  pub fn get(&self, id: &str) -> Option<Arc<PreflightControl>> {
+    self.0.lock().ok()?.get(id).cloned()
+  }
+}
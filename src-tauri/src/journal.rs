@@ -0,0 +1,74 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+  fs::{self, OpenOptions},
+  io::Write,
+  path::{Path, PathBuf},
+  sync::Mutex,
+};
+
+use crate::logging;
+
+/* ---------------------------------- Journal --------------------------------- */
+/* manifest.jsonl in the session dir gets one line per finished file, flushed to disk
+   as we go, so a crash or power cut still leaves a record of what was copied.
+   A row may appear more than once (e.g. "copied" then "moved" after a batch verify);
+   the last line for a given source wins. */
+
+pub const JOURNAL_FILE: &str = "manifest.jsonl";
+
+pub struct Journal {
+  path: PathBuf,
+  file: Mutex<fs::File>,
+}
+
+impl Journal {
+  pub fn open(session_dir: &Path) -> Result<Self, String> {
+    let path = session_dir.join(JOURNAL_FILE);
+    let file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&path)
+      .map_err(|e| format!("journal open error: {e}"))?;
+    Ok(Journal {
+      path,
+      file: Mutex::new(file),
+    })
+  }
+
+  pub fn append<T: Serialize>(&self, row: &T) {
+    let line = match serde_json::to_string(row) {
+      Ok(l) => l,
+      Err(e) => {
+        logging::error(&format!("journal serialize error: {e}"));
+        return;
+      }
+    };
+    if let Ok(mut f) = self.file.lock() {
+      if let Err(e) = writeln!(f, "{line}").and_then(|_| f.sync_data()) {
+        logging::error(&format!("journal write error: {e}"));
+      }
+    }
+  }
+
+  pub fn session_dir(&self) -> &Path {
+    self.path.parent().unwrap_or(Path::new(""))
+  }
+
+  /// Called once manifest.json has been written; the journal is then redundant.
+  pub fn finish(self) {
+    let path = self.path.clone();
+    drop(self);
+    let _ = fs::remove_file(path);
+  }
+}
+
+/// Reads back a journal, skipping a torn final line from an interrupted write.
+pub fn read_rows<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+  let s = fs::read_to_string(path).map_err(|e| format!("journal read error: {e}"))?;
+  Ok(
+    s.lines()
+      .filter(|l| !l.trim().is_empty())
+      .filter_map(|l| serde_json::from_str(l).ok())
+      .collect(),
+  )
+}
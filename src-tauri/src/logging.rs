@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  cell::Cell,
+  fs::OpenOptions,
+  io::Write,
+  path::PathBuf,
+  sync::{
+    atomic::{AtomicU8, Ordering},
+    Mutex, OnceLock,
+  },
+};
+
+/* ----------------------------------- Levels ---------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+  Error = 1,
+  Warn = 2,
+  Info = 3,
+  Debug = 4,
+  Trace = 5,
+}
+
+impl LogLevel {
+  pub fn parse(s: &str) -> Result<Self, String> {
+    match s.trim().to_lowercase().as_str() {
+      "error" => Ok(LogLevel::Error),
+      "warn" | "warning" => Ok(LogLevel::Warn),
+      "info" => Ok(LogLevel::Info),
+      "debug" => Ok(LogLevel::Debug),
+      "trace" => Ok(LogLevel::Trace),
+      other => Err(format!("unknown log level: {other}")),
+    }
+  }
+
+  fn from_u8(v: u8) -> Self {
+    match v {
+      1 => LogLevel::Error,
+      2 => LogLevel::Warn,
+      4 => LogLevel::Debug,
+      5 => LogLevel::Trace,
+      _ => LogLevel::Info,
+    }
+  }
+
+  fn label(self) -> &'static str {
+    match self {
+      LogLevel::Error => "ERROR",
+      LogLevel::Warn => "WARN",
+      LogLevel::Info => "INFO",
+      LogLevel::Debug => "DEBUG",
+      LogLevel::Trace => "TRACE",
+    }
+  }
+}
+
+/* ----------------------------------- State ----------------------------------- */
+
+// Global level set by `set_log_level`; a transfer with `debug: true` raises the
+// effective level for its own duration without touching this value.
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+static LOG_FILE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+thread_local! {
+  // Set by a DebugGuard on the thread running a `debug: true` job and on the
+  // workers it spawns, so other jobs running meanwhile keep the global level
+  static THREAD_DEBUG: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn set_level(level: LogLevel) {
+  LEVEL.store(level as u8, Ordering::SeqCst);
+}
+
+pub fn level() -> LogLevel {
+  let base = LEVEL.load(Ordering::SeqCst);
+  if debug_active() {
+    LogLevel::from_u8(base.max(LogLevel::Debug as u8))
+  } else {
+    LogLevel::from_u8(base)
+  }
+}
+
+pub fn enabled(level: LogLevel) -> bool {
+  level <= self::level()
+}
+
+pub fn set_log_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = std::fs::create_dir_all(parent);
+  }
+  let slot = LOG_FILE.get_or_init(|| Mutex::new(None));
+  if let Ok(mut g) = slot.lock() {
+    *g = Some(path);
+  }
+}
+
+/// Raises the effective level to Debug on this thread until dropped (per-transfer
+/// debug flag). A job's worker threads take their own guard from `debug_active()`.
+pub struct DebugGuard {
+  prev: bool,
+}
+
+impl DebugGuard {
+  pub fn new(active: bool) -> Self {
+    let prev = THREAD_DEBUG.with(|d| d.replace(active || d.get()));
+    DebugGuard { prev }
+  }
+}
+
+impl Drop for DebugGuard {
+  fn drop(&mut self) {
+    THREAD_DEBUG.with(|d| d.set(self.prev));
+  }
+}
+
+/// Whether this thread is running a debug job.
+pub fn debug_active() -> bool {
+  THREAD_DEBUG.with(|d| d.get())
+}
+
+/* ---------------------------------- Writers ---------------------------------- */
+
+pub fn write(level: LogLevel, msg: &str) {
+  if !enabled(level) {
+    return;
+  }
+
+  let line = format!(
+    "{} [{}] {}",
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+    level.label(),
+    msg
+  );
+  eprintln!("{line}");
+
+  if let Some(slot) = LOG_FILE.get() {
+    if let Ok(g) = slot.lock() {
+      if let Some(path) = g.as_ref() {
+        if let Ok(mut f) = OpenOptions::new().create(true).append(true).open(path) {
+          let _ = writeln!(f, "{line}");
+        }
+      }
+    }
+  }
+}
+
+pub fn error(msg: &str) {
+  write(LogLevel::Error, msg);
+}
+
+pub fn warn(msg: &str) {
+  write(LogLevel::Warn, msg);
+}
+
+pub fn info(msg: &str) {
+  write(LogLevel::Info, msg);
+}
+
+pub fn debug(msg: &str) {
+  write(LogLevel::Debug, msg);
+}
+
+pub fn trace(msg: &str) {
+  write(LogLevel::Trace, msg);
+}
@@ -1,13 +1,61 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod adaptive;
+mod audio_tags;
+mod audit;
+mod autostart;
+mod bottleneck;
+mod bursts;
+mod camera_hashes;
+mod checksum;
+mod cloud;
+mod completion;
+mod daemon;
+mod device;
+mod dupes;
+mod durability;
+mod elevate;
+mod erase;
+mod errors;
+mod events;
+mod exif;
+mod filters;
+mod fscompat;
+mod hash_cache;
+mod history;
+mod history_db;
+mod jobs;
+mod journal;
+mod logging;
+mod naming;
+mod netfs;
+mod partials;
+mod paths;
+mod permissions;
+mod plan;
+mod preserve;
+mod preview;
+mod profiles;
+mod queue;
+mod quota;
+mod report;
+mod session_state;
+mod sessions;
+mod space;
+mod speed;
+mod throttle;
+mod timestamps;
 mod transfer;
+mod uncached;
+mod video_meta;
+mod volumes;
+mod watchdog;
 
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
-use tauri::{State};
+use std::sync::atomic::Ordering;
+use tauri::{Manager, State};
 
-#[derive(Clone)]
-struct CancelFlag(Arc<AtomicBool>);
+use jobs::{Jobs, Preflights};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeInfo {
@@ -17,12 +65,16 @@ pub struct VolumeInfo {
   pub total_bytes: u64,
   pub avail_bytes: u64,
   pub removable: Option<bool>,
+  pub uuid: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PickedItem {
   pub kind: String, // "file" | "folder"
   pub path: String,
+  // Folder picks only: copy what's inside without the <folder_basename>/ wrapper
+  #[serde(default)]
+  pub contents_only: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,15 +83,51 @@ pub struct Preflight {
   pub total_folders: u64,
   pub total_bytes: u64,
   pub dest_avail_bytes: u64,
+  // Free space kept back on the destination (TransferOptions reserve_bytes / reserve_percent)
+  pub reserve_bytes: u64,
+  // Still to be written to the same drive by running jobs
+  pub dest_reserved_bytes: u64,
   pub will_fit: bool,
   pub by_category: std::collections::HashMap<String, u64>,
   pub by_extension: std::collections::HashMap<String, u64>,
+  // Camera/MHL checksum files found on the source, and how many files they cover
+  pub camera_checksum_files: Vec<String>,
+  pub camera_hashed_files: u64,
+  // Set when the destination drive has a Transfers quota
+  pub quota: Option<quota::QuotaCheck>,
+  // Biggest files first, so the one that breaks will_fit is easy to spot and drop
+  pub largest_files: Vec<transfer::LargeFile>,
+  // Online-only cloud files (OneDrive/iCloud/Dropbox); paths capped at 20
+  pub placeholder_files: u64,
+  pub placeholder_paths: Vec<String>,
+  // A test file could be written to (and removed from) the destination
+  pub writable: bool,
+  pub write_error: Option<String>,
+  // Destination is an SMB/NFS/WebDAV mount
+  pub dest_network: bool,
+  // Things worth telling the user before they start (not blocking)
+  pub notes: Vec<String>,
+  // Files the destination's filesystem would refuse (FAT32 4 GiB limit, bad names, long paths); capped at 100
+  pub warnings: Vec<fscompat::PreflightWarning>,
+  // Left out by include_globs / exclude_globs / skip_hidden
+  pub excluded: filters::Excluded,
+  // Stopped by cancel_preflight; the totals above cover only what was scanned
+  pub cancelled: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferSummary {
+  // Job that produced it (empty in summaries written before this existed)
+  #[serde(default)]
+  pub job_id: String,
+  // Local time with offset (RFC 3339)
   pub started_at: String,
   pub finished_at: String,
+  // The same instants in UTC (empty in summaries written before these existed)
+  #[serde(default)]
+  pub started_at_utc: String,
+  #[serde(default)]
+  pub finished_at_utc: String,
   pub duration_ms: u64,
   pub total_files: u64,
   pub total_bytes: u64,
@@ -47,56 +135,187 @@ pub struct TransferSummary {
   pub moved_files: u64,
   pub skipped_files: u64,
   pub error_files: u64,
+  // Copied, but a hard cancel ended the batch verify pass before it got to them
+  #[serde(default)]
+  pub unverified_files: u64,
   pub output_session_dir: String,
+  // Planned only (TransferOptions dry_run); output_session_dir holds the would-be manifest
+  #[serde(default)]
+  pub dry_run: bool,
+  // Throughput over the run (see get_transfer_samples); none for a dry run
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub samples: Vec<speed::ThroughputSample>,
 }
 
 #[tauri::command]
-fn cancel_transfer(flag: State<CancelFlag>) {
-  flag.0.store(true, Ordering::SeqCst);
+fn cancel_transfer(job_id: Option<String>, jobs: State<Jobs>) -> Result<(), String> {
+  for job in jobs.select(job_id.as_deref())? {
+    job.cancel.store(true, Ordering::SeqCst);
+  }
+  Ok(())
 }
 
+/// Holds the in-flight file where it is; resume_transfer carries on from the same byte.
 #[tauri::command]
-fn list_volumes() -> Result<Vec<VolumeInfo>, String> {
-  use std::process::Command;
+fn pause_transfer(job_id: Option<String>, jobs: State<Jobs>) -> Result<(), String> {
+  for job in jobs.select(job_id.as_deref())? {
+    logging::info(&format!("job {}: paused", job.id));
+    job.paused.store(true, Ordering::SeqCst);
+  }
+  Ok(())
+}
 
-  // macOS/Linux: df -k gives 1K blocks, parse mount points + available
-  let out = Command::new("df")
-    .arg("-k")
-    .output()
-    .map_err(|e| format!("failed to run df: {e}"))?;
+#[tauri::command]
+fn resume_transfer(job_id: Option<String>, jobs: State<Jobs>) -> Result<(), String> {
+  for job in jobs.select(job_id.as_deref())? {
+    logging::info(&format!("job {}: resumed", job.id));
+    job.paused.store(false, Ordering::SeqCst);
+  }
+  Ok(())
+}
 
-  let s = String::from_utf8_lossy(&out.stdout);
-  let mut vols: Vec<VolumeInfo> = vec![];
+/// Changes the throughput cap of running jobs (0 = none); takes effect on the next chunk.
+#[tauri::command]
+fn set_transfer_limit(job_id: Option<String>, bytes_per_sec: u64, jobs: State<Jobs>) -> Result<(), String> {
+  for job in jobs.select(job_id.as_deref())? {
+    logging::info(&format!("job {}: throughput limit {bytes_per_sec} B/s", job.id));
+    job.limit.set_rate(bytes_per_sec);
+  }
+  Ok(())
+}
 
-  for (i, line) in s.lines().enumerate() {
-    if i == 0 { continue; } // header
-    // Typical df line: Filesystem 1024-blocks Used Available Capacity iused ifree %iused Mounted on
-    // We care about Available and Mounted on; mount point is the last column(s)
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() < 6 { continue; }
+/// Throughput of a running job so far, about one sample a second, for a speed
+/// chart. Once it ends, its TransferSummary has the full list.
+#[tauri::command]
+fn get_transfer_samples(job_id: String, jobs: State<Jobs>) -> Result<Vec<speed::ThroughputSample>, String> {
+  Ok(jobs.get(&job_id)?.speed_samples())
+}
 
-    // Heuristic: available is usually column 3 or 4 depending; on macOS it's 3rd index = "Available"
-    // Example: parts[0]=Filesystem parts[1]=1024-blocks parts[2]=Used parts[3]=Available parts[4]=Capacity ... parts[last]=Mounted
-    let avail_kb = parts.get(3).and_then(|x| x.parse::<u64>().ok()).unwrap_or(0);
-    let mount_point = parts.last().unwrap_or(&"").to_string();
+#[tauri::command]
+fn stop_after_current(job_id: Option<String>, jobs: State<Jobs>) -> Result<(), String> {
+  for job in jobs.select(job_id.as_deref())? {
+    logging::info(&format!("job {}: stop requested after current file", job.id));
+    job.stop_after_current.store(true, Ordering::SeqCst);
+  }
+  Ok(())
+}
 
-    if mount_point.is_empty() { continue; }
+/// Skips one file (item_id = its source path, as reported in progress `current_path`).
+#[tauri::command]
+fn skip_item(job_id: String, item_id: String, jobs: State<Jobs>) -> Result<(), String> {
+  let job = jobs.get(&job_id)?;
+  logging::info(&format!("job {job_id}: skip requested for {item_id}"));
+  job.skip(&item_id);
+  Ok(())
+}
 
-    vols.push(VolumeInfo {
-      name: mount_point.clone(),
-      mount_point,
-      fs_type: None,
-      total_bytes: 0,
-      avail_bytes: avail_kb * 1024,
-      removable: None,
-    });
+/// Moves a pending file (item_id = its source path) to the front of the job's queue.
+#[tauri::command]
+fn prioritize_item(job_id: String, item_id: String, jobs: State<Jobs>) -> Result<(), String> {
+  let job = jobs.get(&job_id)?;
+  job.prioritize(&item_id);
+  Ok(())
+}
+
+#[tauri::command]
+fn resolve_stall(job_id: Option<String>, action: String, jobs: State<Jobs>) -> Result<(), String> {
+  let a = watchdog::parse_stall_action(&action)?;
+  for job in jobs.select(job_id.as_deref())? {
+    job.stall_action.store(a, Ordering::SeqCst);
+  }
+  Ok(())
+}
+
+#[tauri::command]
+fn read_audit_log() -> Result<Vec<audit::AuditEntry>, String> {
+  audit::read_log()
+}
+
+#[tauri::command]
+fn verify_audit_log() -> Result<audit::AuditVerifyReport, String> {
+  audit::verify_log()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyModeInfo {
+  pub id: String,
+  pub label: String,
+  pub recommended: bool,
+}
+
+#[tauri::command]
+fn list_verify_modes() -> Vec<VerifyModeInfo> {
+  [
+    ("none", "None"),
+    ("size", "Size only"),
+    ("xxh3", "Fast checksum (xxh3)"),
+    ("xxh64", "xxHash64 (MHL-compatible)"),
+    ("blake3", "BLAKE3 (fast, cryptographic)"),
+    ("sha256", "SHA-256"),
+    ("md5", "MD5"),
+    ("crc32", "CRC32"),
+  ]
+  .iter()
+  .map(|(id, label)| VerifyModeInfo {
+    id: id.to_string(),
+    label: label.to_string(),
+    recommended: *id == checksum::DEFAULT_VERIFY_MODE,
+  })
+  .collect()
+}
+
+#[tauri::command]
+fn get_stats(range: Option<history::StatsRange>) -> Result<history::TransferStats, String> {
+  history_db::stats(&range.unwrap_or_default())
+}
+
+/// Past sessions by date, destination drive, outcome or file name, newest first.
+#[tauri::command]
+fn query_history(filter: Option<history_db::HistoryFilter>) -> Result<Vec<history_db::HistorySession>, String> {
+  history_db::query(&filter.unwrap_or_default())
+}
+
+/// One past session (an id from query_history) with its settings and per-file results.
+#[tauri::command]
+fn get_session_detail(id: i64) -> Result<history_db::SessionDetail, String> {
+  history_db::session_detail(id)
+}
+
+#[tauri::command]
+fn set_log_level(level: String) -> Result<(), String> {
+  let lvl = logging::LogLevel::parse(&level)?;
+  logging::set_level(lvl);
+  logging::info(&format!("log level set to {level}"));
+  Ok(())
+}
+
+/// Unmounts the volume at `mount_point` so it can be unplugged. Refused while a
+/// running job is still writing to it.
+#[tauri::command]
+async fn eject_volume(mount_point: String, jobs: State<'_, Jobs>) -> Result<(), String> {
+  let mount = device::mount_point_for(&mount_point).unwrap_or_else(|| mount_point.clone());
+  if let Some(dir) = jobs
+    .active_session_dirs()
+    .into_iter()
+    .find(|d| device::mount_point_for(&d.to_string_lossy()).as_deref() == Some(mount.as_str()))
+  {
+    return Err(format!("a transfer is still writing to {mount} ({})", dir.display()));
   }
+  device::eject(&mount)
+}
 
+/// Mounted volumes; `removable_only` keeps USB sticks, cards and external disks.
+#[tauri::command]
+fn list_volumes(removable_only: Option<bool>, volumes: State<'_, volumes::Volumes>) -> Result<Vec<VolumeInfo>, String> {
+  let mut vols = volumes.list()?;
+  if removable_only.unwrap_or(false) {
+    vols.retain(|v| v.removable == Some(true));
+  }
   Ok(vols)
 }
 
 #[tauri::command]
-async fn pick_files(app: tauri::AppHandle) -> Result<Vec<transfer::QueueItem>, String> {
+async fn pick_files(app: tauri::AppHandle, queue: State<'_, queue::Queue>) -> Result<queue::QueueAdd, String> {
   use tauri_plugin_dialog::DialogExt;
   use tokio::sync::oneshot;
 
@@ -110,24 +329,12 @@ async fn pick_files(app: tauri::AppHandle) -> Result<Vec<transfer::QueueItem>, S
     });
 
   let picked = rx.await.map_err(|e| format!("dialog receive error: {e}"))?;
-
-  let mut out = vec![];
-  if let Some(paths) = picked {
-    for p in paths {
-      out.push(transfer::QueueItem {
-        id: uuid::Uuid::new_v4().to_string(),
-        kind: "file".to_string(),
-        path: p.to_string(),
-        size_bytes: None,
-        file_count: None,
-      });
-    }
-  }
-  Ok(out)
+  let paths = picked.unwrap_or_default().into_iter().map(|p| p.to_string()).collect();
+  queue.add(paths, Some("file"))
 }
 
 #[tauri::command]
-async fn pick_folders(app: tauri::AppHandle) -> Result<Vec<transfer::QueueItem>, String> {
+async fn pick_folders(app: tauri::AppHandle, queue: State<'_, queue::Queue>) -> Result<queue::QueueAdd, String> {
   use tauri_plugin_dialog::DialogExt;
   use tokio::sync::oneshot;
 
@@ -141,25 +348,47 @@ async fn pick_folders(app: tauri::AppHandle) -> Result<Vec<transfer::QueueItem>,
     });
 
   let picked = rx.await.map_err(|e| format!("dialog receive error: {e}"))?;
+  let paths = picked.unwrap_or_default().into_iter().map(|p| p.to_string()).collect();
+  queue.add(paths, Some("folder"))
+}
 
-  let mut out = vec![];
-  if let Some(paths) = picked {
-    for p in paths {
-      out.push(transfer::QueueItem {
-        id: uuid::Uuid::new_v4().to_string(),
-        kind: "folder".to_string(),
-        path: p.to_string(),
-        size_bytes: None,
-        file_count: None,
-      });
-    }
-  }
-  Ok(out)
+#[tauri::command]
+async fn preflight_scan(
+  items: Vec<PickedItem>,
+  dest_mount_point: String,
+  options: Option<transfer::TransferOptions>,
+  preflight_id: Option<String>,
+  preflights: State<'_, Preflights>,
+  reservations: State<'_, space::Reservations>,
+) -> Result<Preflight, String> {
+  let ctl = preflights.register(preflight_id);
+  let reserved = reservations.held(&space::volume_key(&dest_mount_point), None);
+  // The tree walk and space probe can run for minutes: keep them off the async runtime's workers
+  let res = {
+    let ctl = ctl.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+      transfer::preflight_scan(items, dest_mount_point, options.unwrap_or_default(), reserved, &ctl)
+    })
+    .await
+    .map_err(|e| format!("preflight task error: {e}"))
+    .and_then(|r| r)
+  };
+  preflights.remove(&ctl.id);
+  res
 }
 
+// Returns what the scan had gathered when it stopped (everything, if it had
+// already finished); None when no scan with that id is running
 #[tauri::command]
-fn preflight_scan(items: Vec<PickedItem>, dest_mount_point: String) -> Result<Preflight, String> {
-  transfer::preflight_scan(items, dest_mount_point)
+async fn cancel_preflight(preflight_id: String, preflights: State<'_, Preflights>) -> Result<Option<Preflight>, String> {
+  let Some(ctl) = preflights.get(&preflight_id) else {
+    return Ok(None);
+  };
+  ctl.cancel.store(true, Ordering::SeqCst);
+  // Polls with thread::sleep, so keep it off the async runtime's workers
+  tauri::async_runtime::spawn_blocking(move || ctl.wait_result(std::time::Duration::from_secs(5)))
+    .await
+    .map_err(|e| format!("preflight wait error: {e}"))
 }
 
 #[tauri::command]
@@ -170,53 +399,469 @@ async fn start_transfer(
   copy_mode: String,
   conflict_policy: String,
   verify_mode: String,
-  flag: State<'_, CancelFlag>,
+  options: Option<transfer::TransferOptions>,
+  preflight: Option<transfer::PreflightTotals>,
+  job_id: String,
+  jobs: State<'_, Jobs>,
 ) -> Result<TransferSummary, String> {
-  flag.0.store(false, Ordering::SeqCst);
-  transfer::start_transfer(app, items, dest_mount_point, copy_mode, conflict_policy, verify_mode, flag.0.clone()).await
+  let job = jobs.register_as(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  let res = transfer::start_transfer(
+    app.clone(),
+    items,
+    dest_mount_point,
+    copy_mode,
+    conflict_policy,
+    verify_mode,
+    options.unwrap_or_default(),
+    preflight,
+    job.clone(),
+  )
+  .await;
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
 }
 
 #[tauri::command]
-fn add_dropped_paths(paths: Vec<String>) -> Result<Vec<transfer::QueueItem>, String> {
-  use std::path::Path;
+async fn start_transfer_on_device(
+  app: tauri::AppHandle,
+  items: Vec<PickedItem>,
+  device_uuid: String,
+  copy_mode: String,
+  conflict_policy: String,
+  verify_mode: String,
+  options: Option<transfer::TransferOptions>,
+  job_id: String,
+  jobs: State<'_, Jobs>,
+) -> Result<TransferSummary, String> {
+  let job = jobs.register_as(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  // The drive may not turn up for hours: wait on a blocking thread, not a runtime worker
+  let waiter = {
+    let (app, job) = (app.clone(), job.clone());
+    tauri::async_runtime::spawn_blocking(move || device::wait_for_device(&app, &job.id, &device_uuid, &job.cancel))
+  };
+  let res = match waiter.await.map_err(|e| format!("device wait error: {e}")) {
+    Err(e) => Err(e),
+    Ok(Some(dest_mount_point)) => {
+      transfer::start_transfer(
+        app.clone(),
+        items,
+        dest_mount_point,
+        copy_mode,
+        conflict_policy,
+        verify_mode,
+        options.unwrap_or_default(),
+        None,
+        job.clone(),
+      )
+      .await
+    }
+    Ok(None) => Err("cancelled while waiting for device".to_string()),
+  };
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
+}
 
-  let mut out: Vec<transfer::QueueItem> = vec![];
+#[tauri::command]
+async fn start_from_manifest(
+  app: tauri::AppHandle,
+  path: String,
+  new_dest: String,
+  verify_mode: Option<String>,
+  options: Option<transfer::TransferOptions>,
+  job_id: String,
+  jobs: State<'_, Jobs>,
+) -> Result<TransferSummary, String> {
+  let job = jobs.register_as(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  let res = transfer::start_from_manifest(app.clone(), path, new_dest, verify_mode, options, job.clone()).await;
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
+}
 
-  for p in paths {
-    if p.trim().is_empty() { continue; }
-    let path = Path::new(&p);
+#[tauri::command]
+async fn retry_failed(
+  app: tauri::AppHandle,
+  session_dir: String,
+  copy_mode: Option<String>,
+  verify_mode: Option<String>,
+  options: Option<transfer::TransferOptions>,
+  job_id: String,
+  jobs: State<'_, Jobs>,
+) -> Result<TransferSummary, String> {
+  let job = jobs.register_as(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  let res = transfer::retry_failed(app.clone(), session_dir, copy_mode, verify_mode, options, job.clone()).await;
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
+}
 
-    let kind = if path.is_dir() {
-      "folder"
-    } else {
-      "file"
-    };
+/// Re-hashes every file a session copied and reports ones missing or changed.
+#[tauri::command]
+async fn verify_session(
+  app: tauri::AppHandle,
+  session_dir: String,
+  job_id: Option<String>,
+  jobs: State<'_, Jobs>,
+) -> Result<transfer::SessionVerifyReport, String> {
+  let job = jobs.register(job_id)?;
+  events::job(&app, &job.id, "verify", "queued", None);
+  // Re-hashing a whole session can take hours: keep it off the async runtime's workers
+  let res = {
+    let (app, job) = (app.clone(), job.clone());
+    tauri::async_runtime::spawn_blocking(move || transfer::verify_session(&app, session_dir, &job))
+      .await
+      .map_err(|e| format!("verify task error: {e}"))
+      .and_then(|r| r)
+  };
+  events::job_ended(&app, &job.id, "verify", &res);
+  jobs.remove(&job.id);
+  res
+}
 
-    out.push(transfer::QueueItem {
-      id: uuid::Uuid::new_v4().to_string(),
-      kind: kind.to_string(),
-      path: p,
-      size_bytes: None,
-      file_count: None,
-    });
-  }
+/// Re-copies a session's permission-denied files behind an OS admin prompt.
+#[tauri::command]
+async fn retry_elevated(
+  app: tauri::AppHandle,
+  session_dir: String,
+  job_id: Option<String>,
+  jobs: State<'_, Jobs>,
+) -> Result<TransferSummary, String> {
+  let job = jobs.register(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  // Waits on the admin prompt, then re-reads every file it copied
+  let res = {
+    let (app, job) = (app.clone(), job.clone());
+    tauri::async_runtime::spawn_blocking(move || transfer::retry_elevated(&app, session_dir, &job))
+      .await
+      .map_err(|e| format!("elevated retry task error: {e}"))
+      .and_then(|r| r)
+  };
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
+}
 
-  Ok(out)
+#[tauri::command]
+async fn resume_session(
+  app: tauri::AppHandle,
+  session_dir: String,
+  job_id: String,
+  jobs: State<'_, Jobs>,
+) -> Result<TransferSummary, String> {
+  let job = jobs.register_as(job_id)?;
+  events::job(&app, &job.id, "transfer", "queued", None);
+  let res = transfer::resume_session(app.clone(), session_dir, job.clone()).await;
+  events::job_ended(&app, &job.id, "transfer", &res);
+  jobs.remove(&job.id);
+  res
+}
+
+#[tauri::command]
+async fn start_offload(
+  app: tauri::AppHandle,
+  items: Vec<PickedItem>,
+  primary_dest: String,
+  backup_dest: String,
+  verify_mode: String,
+  options: Option<transfer::TransferOptions>,
+  job_id: Option<String>,
+  jobs: State<'_, Jobs>,
+) -> Result<transfer::OffloadReport, String> {
+  let job = jobs.register(job_id)?;
+  events::job(&app, &job.id, "offload", "queued", None);
+  let res = transfer::start_offload(
+    app.clone(),
+    items,
+    primary_dest,
+    backup_dest,
+    verify_mode,
+    options.unwrap_or_default(),
+    job.clone(),
+  )
+  .await;
+  events::job_ended(&app, &job.id, "offload", &res);
+  jobs.remove(&job.id);
+  res
+}
+
+#[tauri::command]
+fn get_daemon_status(jobs: State<Jobs>) -> daemon::DaemonStatus {
+  daemon::status(&jobs)
+}
+
+#[tauri::command]
+fn set_daemon_settings(settings: daemon::DaemonSettings) -> Result<(), String> {
+  daemon::save_settings(&settings)
+}
+
+#[tauri::command]
+fn get_launch_at_login() -> bool {
+  autostart::is_enabled()
+}
+
+/// Starts the app hidden in the tray at login, with watchers and auto-import armed.
+#[tauri::command]
+fn set_launch_at_login(enabled: bool) -> Result<(), String> {
+  autostart::set_enabled(enabled)
+}
+
+#[tauri::command]
+fn list_profiles() -> Result<Vec<profiles::Profile>, String> {
+  profiles::list_profiles()
+}
+
+#[tauri::command]
+fn save_profile(profile: profiles::Profile) -> Result<profiles::Profile, String> {
+  profiles::save_profile(profile)
+}
+
+#[tauri::command]
+fn delete_profile(id: String) -> Result<(), String> {
+  profiles::delete_profile(&id)
+}
+
+#[tauri::command]
+fn profile_for_card(uuid: String) -> Result<Option<profiles::Profile>, String> {
+  profiles::profile_for_card(&uuid)
+}
+
+#[tauri::command]
+fn export_plan(path: String, plan: plan::TransferPlan) -> Result<(), String> {
+  plan::export_plan(&path, plan)
+}
+
+#[tauri::command]
+fn import_plan(path: String) -> Result<plan::PlanImport, String> {
+  plan::import_plan(&path)
+}
+
+#[tauri::command]
+async fn get_item_preview(path: String) -> Result<preview::ItemPreview, String> {
+  preview::item_preview(&path)
+}
+
+#[tauri::command]
+fn get_file_info(path: String) -> Result<preview::FileInfo, String> {
+  preview::file_info(&path)
+}
+
+#[tauri::command]
+async fn find_duplicates(app: tauri::AppHandle, root: String) -> Result<dupes::DuplicateReport, String> {
+  // Hashes the whole tree: keep it off the async runtime's workers
+  tauri::async_runtime::spawn_blocking(move || dupes::find_duplicates(&app, &root))
+    .await
+    .map_err(|e| format!("duplicate scan task error: {e}"))?
+}
+
+#[tauri::command]
+async fn generate_delivery_report(
+  session_dir: String,
+  options: Option<report::ReportOptions>,
+) -> Result<report::DeliveryReport, String> {
+  // Reads the manifests and renders HTML/PDF: keep it off the async runtime's workers
+  tauri::async_runtime::spawn_blocking(move || report::generate(&session_dir, &options.unwrap_or_default()))
+    .await
+    .map_err(|e| format!("report task error: {e}"))?
+}
+
+/// Writes a hashdeep-compatible audit file (md5 or sha256) into the session folder.
+#[tauri::command]
+async fn export_hashdeep(session_dir: String, algo: Option<String>) -> Result<report::HashdeepExport, String> {
+  report::export_hashdeep(&session_dir, algo)
+}
+
+/// Writes the session manifest as manifest.csv ("csv") into the session folder.
+#[tauri::command]
+async fn export_manifest(session_dir: String, format: Option<String>) -> Result<report::ManifestExport, String> {
+  report::export_manifest(&session_dir, format.as_deref().unwrap_or("csv"))
+}
+
+/// Copies a session summary ("text" | "markdown") or checksum list ("checksums")
+/// to the clipboard, returning the copied text.
+#[tauri::command]
+fn copy_summary_to_clipboard(app: tauri::AppHandle, session_dir: String, format: String) -> Result<String, String> {
+  use tauri_plugin_clipboard_manager::ClipboardExt;
+
+  let text = report::clipboard_text(&session_dir, &format)?;
+  app
+    .clipboard()
+    .write_text(text.clone())
+    .map_err(|e| format!("clipboard error: {e}"))?;
+  Ok(text)
+}
+
+/// Previous sessions on a destination drive (Transfers/<day>/<run>), newest first.
+#[tauri::command]
+async fn list_sessions(mount_point: String, jobs: State<'_, Jobs>) -> Result<Vec<sessions::SessionInfo>, String> {
+  sessions::list_sessions(&mount_point, &jobs.active_session_dirs())
+}
+
+#[tauri::command]
+fn open_session_dir(path: String) -> Result<(), String> {
+  sessions::open_session_dir(&path)
+}
+
+/// Lists leftovers from interrupted sessions on a destination; pass `confirm`
+/// (paths from a previous call) to delete them.
+#[tauri::command]
+async fn clean_partials(
+  dest_mount: String,
+  confirm: Option<Vec<String>>,
+  jobs: State<'_, Jobs>,
+) -> Result<partials::PartialsReport, String> {
+  partials::clean_partials(&dest_mount, confirm, &jobs.active_session_dirs())
+}
+
+/// Reports missing macOS privacy access (Full Disk Access, Files and Folders) for
+/// the given transfer paths before a transfer starts.
+#[tauri::command]
+fn check_permissions(paths: Option<Vec<String>>) -> permissions::PermissionReport {
+  permissions::check_permissions(&paths.unwrap_or_default())
+}
+
+#[tauri::command]
+fn get_dest_quota(dest_mount: String) -> Result<Option<quota::Quota>, String> {
+  quota::get_quota(&dest_mount)
+}
+
+#[tauri::command]
+fn set_dest_quota(dest_mount: String, quota: Option<quota::Quota>) -> Result<(), String> {
+  quota::set_quota(&dest_mount, quota)
+}
+
+/// Expands ~, resolves `input` against `base` when relative, and reports whether
+/// (and as what) it exists.
+#[tauri::command]
+fn normalize_path(input: String, base: Option<String>) -> Result<paths::NormalizedPath, String> {
+  paths::normalize_path(&input, base.as_deref())
+}
+
+#[tauri::command]
+fn add_dropped_paths(paths: Vec<String>, queue: State<queue::Queue>) -> Result<queue::QueueAdd, String> {
+  queue.add(paths, None)
+}
+
+#[tauri::command]
+fn remove_queue_items(ids: Vec<String>, queue: State<queue::Queue>) {
+  queue.remove(&ids);
+}
+
+#[tauri::command]
+fn clear_queue(queue: State<queue::Queue>) {
+  queue.clear();
+}
+
+#[tauri::command]
+fn list_queue(queue: State<queue::Queue>) -> Vec<transfer::QueueItem> {
+  queue.list()
 }
 
 fn main() {
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
-    .manage(CancelFlag(Arc::new(AtomicBool::new(false))))
+    .plugin(tauri_plugin_clipboard_manager::init())
+    .manage(Jobs::default())
+    .manage(Preflights::default())
+    .manage(volumes::Volumes::default())
+    .manage(space::Reservations::default())
+    .manage(queue::Queue::default())
+    .setup(|app| {
+      if let Ok(dir) = app.path().app_log_dir() {
+        logging::set_log_file(dir.join("transferpilot.log"));
+      }
+      if let Ok(dir) = app.path().app_data_dir() {
+        audit::set_audit_file(dir.join("audit.log"));
+        profiles::set_profiles_file(dir.join("profiles.json"));
+        hash_cache::set_cache_file(dir.join("hash_cache.json"));
+        history::set_history_file(dir.join("history.jsonl"));
+        history_db::set_db_file(dir.join("history.db"));
+        daemon::set_daemon_file(dir.join("daemon.json"));
+      }
+      daemon::install_tray(app.handle())?;
+      if daemon::is_background_launch() {
+        if let Some(w) = app.get_webview_window("main") {
+          let _ = w.hide();
+        }
+      }
+      profiles::spawn_card_watcher(app.handle().clone());
+      volumes::spawn_volume_watcher(app.handle().clone());
+      Ok(())
+    })
+    .on_window_event(|window, event| {
+      // In background mode the window only hides; jobs and watchers keep running
+      if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        if daemon::keeps_running() {
+          api.prevent_close();
+          let _ = window.hide();
+        }
+      }
+    })
     .invoke_handler(tauri::generate_handler![
       list_volumes,
+      eject_volume,
       pick_files,
       pick_folders,
       preflight_scan,
+      cancel_preflight,
       start_transfer,
+      start_transfer_on_device,
+      start_from_manifest,
+      retry_failed,
+      retry_elevated,
+      verify_session,
+      resume_session,
+      start_offload,
       cancel_transfer,
-      add_dropped_paths
+      pause_transfer,
+      resume_transfer,
+      set_transfer_limit,
+      get_transfer_samples,
+      stop_after_current,
+      skip_item,
+      prioritize_item,
+      normalize_path,
+      add_dropped_paths,
+      remove_queue_items,
+      clear_queue,
+      list_queue,
+      get_item_preview,
+      get_file_info,
+      find_duplicates,
+      get_stats,
+      query_history,
+      get_session_detail,
+      generate_delivery_report,
+      copy_summary_to_clipboard,
+      export_hashdeep,
+      export_manifest,
+      clean_partials,
+      list_sessions,
+      open_session_dir,
+      check_permissions,
+      get_dest_quota,
+      set_dest_quota,
+      set_log_level,
+      list_verify_modes,
+      get_daemon_status,
+      set_daemon_settings,
+      get_launch_at_login,
+      set_launch_at_login,
+      list_profiles,
+      save_profile,
+      delete_profile,
+      profile_for_card,
+      export_plan,
+      import_plan,
+      read_audit_log,
+      verify_audit_log,
+      resolve_stall
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
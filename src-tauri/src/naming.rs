@@ -0,0 +1,358 @@
+use std::{
+  cell::OnceCell,
+  path::{Component, Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  audio_tags::{self, AudioTags},
+  exif::{self, Exif},
+  video_meta,
+};
+
+/* ---------------------------------- Tokens ---------------------------------- */
+/* Layout/rename templates are plain strings with {token} placeholders, e.g.
+   layout "{project}/{reel}" and rename "{reel}_{clip}_{stem}". Tokens:
+
+     {project}  options.project
+     {reel}     camera roll, e.g. A001 (from clip/folder names, the DCIM folder,
+                or else the picked folder's name)
+     {clip}     clip number, e.g. C003 (A001C003…, XDROOT/M4ROOT C0001) or the
+                DCIM file number
+     {folder}   picked folder name (empty for loose files)
+     {subpath}  directories between the picked folder and the file
+     {stem}     original file name without extension
+     {ext}      original extension, without the dot
+     {date}     today, YYYY-MM-DD
+     {artist}   audio album artist, else track artist (ID3/Vorbis tags)
+     {album}    audio album
+     {title}    audio track title
+     {track}    audio track number, zero-padded to 2 digits
+     {shot_date}  capture date, YYYY-MM-DD: MP4/MOV/MKV creation time for clips,
+                  EXIF for stills, else the file's modification time
+     {shot_time}  capture time, HHMMSS
+     {shot_year}  capture year, YYYY
+     {shot_month} capture month, MM
+     {exif_date}    EXIF capture date, YYYY-MM-DD (falls back to {shot_date})
+     {exif_time}    EXIF capture time, HHMMSS (falls back to {shot_time})
+     {camera_make}  EXIF Make, e.g. SONY
+     {camera_model} EXIF Model, e.g. ILCE-7M4
+     {lens}         EXIF LensModel
+     {iso}          EXIF ISO speed
+
+   An unknown token is left as written so a typo shows up in the output. A token
+   with no value is dropped along with the separator after it, so
+   "{exif_date}_{camera_model}_{stem}" gives "2025-06-01_DSC01234" without a model. */
+
+/// Source-side facts a template can draw on; values are computed on demand.
+pub struct NameSource<'a> {
+  src: &'a Path,
+  // <picked folder>/<relative path>, as in FileEntry::folder_rel
+  folder_rel: Option<&'a Path>,
+  project: Option<&'a str>,
+  // Read from the file the first time a tag token is used
+  audio: OnceCell<Option<AudioTags>>,
+  exif: OnceCell<Option<Exif>>,
+  shot_at: OnceCell<Option<DateTime<Local>>>,
+}
+
+impl<'a> NameSource<'a> {
+  pub fn new(src: &'a Path, folder_rel: Option<&'a Path>, project: Option<&'a str>) -> Self {
+    NameSource {
+      src,
+      folder_rel,
+      project,
+      audio: OnceCell::new(),
+      exif: OnceCell::new(),
+      shot_at: OnceCell::new(),
+    }
+  }
+
+  pub fn token(&self, name: &str) -> Option<Option<String>> {
+    let v = match name {
+      "project" => self.project.map(|s| s.to_string()),
+      "reel" => self.reel(),
+      "clip" => self.clip(),
+      "folder" => self.top_folder(),
+      "subpath" => self.subpath(),
+      "stem" => self.src.file_stem().map(|s| s.to_string_lossy().to_string()),
+      "ext" => self.src.extension().map(|s| s.to_string_lossy().to_string()),
+      "date" => Some(chrono::Local::now().format("%Y-%m-%d").to_string()),
+      "artist" => self.audio().and_then(|t| t.album_artist.clone().or_else(|| t.artist.clone())),
+      "album" => self.audio().and_then(|t| t.album.clone()),
+      "title" => self.audio().and_then(|t| t.title.clone()),
+      "track" => self.audio().and_then(|t| t.track.as_deref().map(track_number)),
+      "shot_date" => self.shot_at().map(|t| t.format("%Y-%m-%d").to_string()),
+      "shot_time" => self.shot_at().map(|t| t.format("%H%M%S").to_string()),
+      "shot_year" => self.shot_at().map(|t| t.format("%Y").to_string()),
+      "shot_month" => self.shot_at().map(|t| t.format("%m").to_string()),
+      "exif_date" => self.exif_taken_at().map(|t| t.format("%Y-%m-%d").to_string()),
+      "exif_time" => self.exif_taken_at().map(|t| t.format("%H%M%S").to_string()),
+      "camera_make" => self.exif().and_then(|e| e.make.clone()),
+      "camera_model" => self.exif().and_then(|e| e.model.clone()),
+      "lens" => self.exif().and_then(|e| e.lens.clone()),
+      "iso" => self.exif().and_then(|e| e.iso.map(|v| v.to_string())),
+      _ => return None,
+    };
+    Some(v)
+  }
+
+  fn shot_at(&self) -> Option<DateTime<Local>> {
+    *self.shot_at.get_or_init(|| {
+      video_meta::creation_time(self.src)
+        .map(|t| t.with_timezone(&Local))
+        .or_else(|| self.exif().and_then(|e| e.taken_at))
+        .or_else(|| {
+          let mtime = std::fs::metadata(self.src).and_then(|m| m.modified()).ok()?;
+          Some(DateTime::<Local>::from(mtime))
+        })
+    })
+  }
+
+  fn exif_taken_at(&self) -> Option<DateTime<Local>> {
+    self.exif().and_then(|e| e.taken_at).or_else(|| self.shot_at())
+  }
+
+  fn exif(&self) -> Option<&Exif> {
+    self.exif.get_or_init(|| exif::read(self.src)).as_ref()
+  }
+
+  fn audio(&self) -> Option<&AudioTags> {
+    self.audio.get_or_init(|| audio_tags::read(self.src)).as_ref()
+  }
+
+  fn top_folder(&self) -> Option<String> {
+    self
+      .folder_rel?
+      .components()
+      .next()
+      .map(|c| c.as_os_str().to_string_lossy().to_string())
+  }
+
+  fn subpath(&self) -> Option<String> {
+    let rel = self.folder_rel?;
+    let dirs: Vec<String> = rel
+      .parent()?
+      .components()
+      .skip(1)
+      .map(|c| c.as_os_str().to_string_lossy().to_string())
+      .collect();
+    (!dirs.is_empty()).then(|| dirs.join("/"))
+  }
+
+  // Directory names above the file, nearest first
+  fn ancestors(&self) -> Vec<String> {
+    self
+      .src
+      .parent()
+      .map(|p| {
+        p.components()
+          .rev()
+          .filter_map(|c| match c {
+            Component::Normal(s) => Some(s.to_string_lossy().to_string()),
+            _ => None,
+          })
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+
+  fn dcim_folder(&self) -> Option<String> {
+    let dirs = self.ancestors();
+    let i = dirs.iter().position(|d| d.eq_ignore_ascii_case("DCIM"))?;
+    // DCIM/<100CANON>/IMG_0001.JPG: the folder right under DCIM
+    i.checked_sub(1).map(|j| dirs[j].clone())
+  }
+
+  fn reel(&self) -> Option<String> {
+    let stem = self.src.file_stem()?.to_string_lossy().to_string();
+    if let Some(r) = reel_in(&stem) {
+      return Some(r);
+    }
+    if let Some(r) = self.ancestors().iter().find_map(|d| reel_in(d)) {
+      return Some(r);
+    }
+    if let Some(d) = self.dcim_folder() {
+      return Some(d);
+    }
+    // Cards are often labelled with their roll (A001), so the picked folder is a fair guess
+    self
+      .top_folder()
+      .or_else(|| self.ancestors().into_iter().next())
+  }
+
+  fn clip(&self) -> Option<String> {
+    let stem = self.src.file_stem()?.to_string_lossy().to_string();
+    if let Some(c) = clip_in(&stem) {
+      return Some(c);
+    }
+    if let Some(c) = self.ancestors().first().and_then(|d| clip_in(d)) {
+      return Some(c);
+    }
+    // DCF names end in the frame number: IMG_1234, DSC01234
+    if self.dcim_folder().is_some() {
+      let digits: String = stem.chars().rev().take_while(|c| c.is_ascii_digit()).collect();
+      if !digits.is_empty() {
+        return Some(digits.chars().rev().collect());
+      }
+    }
+    None
+  }
+}
+
+// Camera roll id: an uppercase letter and three digits at a word start (A001, B012_…)
+fn reel_in(s: &str) -> Option<String> {
+  let b = s.as_bytes();
+  (0..b.len().saturating_sub(3)).find_map(|i| {
+    let word_start = i == 0 || !b[i - 1].is_ascii_alphanumeric();
+    let shape = b[i].is_ascii_uppercase() && b[i + 1..i + 4].iter().all(|c| c.is_ascii_digit());
+    let ends = !matches!(b.get(i + 4), Some(c) if c.is_ascii_digit());
+    (word_start && shape && ends).then(|| s[i..i + 4].to_string())
+  })
+}
+
+// Clip id: "C" and 3-4 digits, either leading or right after a roll (A001C003, A001_C003)
+fn clip_in(s: &str) -> Option<String> {
+  let b = s.as_bytes();
+  (0..b.len()).find_map(|i| {
+    if b[i] != b'C' {
+      return None;
+    }
+    let after_ok = i == 0 || b[i - 1].is_ascii_digit() || b[i - 1] == b'_';
+    let n = b[i + 1..].iter().take_while(|c| c.is_ascii_digit()).count();
+    (after_ok && (3..=4).contains(&n)).then(|| s[i..i + 1 + n].to_string())
+  })
+}
+
+// "3/12" -> "03"
+fn track_number(t: &str) -> String {
+  let n = t.split('/').next().unwrap_or(t).trim();
+  match n.parse::<u32>() {
+    Ok(n) => format!("{n:02}"),
+    Err(_) => n.to_string(),
+  }
+}
+
+/* --------------------------------- Expansion -------------------------------- */
+
+// Values land inside a single path component
+fn sanitize(v: &str) -> String {
+  v.chars()
+    .map(|c| if matches!(c, '/' | '\\' | ':') { '_' } else { c })
+    .collect::<String>()
+    .trim()
+    .to_string()
+}
+
+/// Replaces each {token} via `lookup`. `lookup` returns None for an unknown token
+/// (kept verbatim) and Some(None) for a known token with no value (dropped).
+pub fn expand(template: &str, mut lookup: impl FnMut(&str) -> Option<Option<String>>) -> String {
+  let mut out = String::new();
+  let mut rest = template;
+  while let Some(open) = rest.find('{') {
+    let Some(close) = rest[open..].find('}') else {
+      break;
+    };
+    out.push_str(&rest[..open]);
+    let name = &rest[open + 1..open + close];
+    rest = match lookup(name) {
+      Some(v) => {
+        // {subpath} is the one token allowed to span directories
+        let v = v.unwrap_or_default();
+        if name == "subpath" {
+          out.push_str(&v.split('/').map(sanitize).collect::<Vec<_>>().join("/"));
+        } else {
+          out.push_str(&sanitize(&v));
+        }
+        let after = &rest[open + close + 1..];
+        // Empty value: don't leave "__" or a leading "_" behind
+        let dangling = out.is_empty() || out.ends_with(is_separator);
+        match after.chars().next() {
+          Some(c) if v.is_empty() && dangling && is_separator(c) => &after[c.len_utf8()..],
+          None if v.is_empty() => {
+            while out.ends_with(is_separator) {
+              out.pop();
+            }
+            after
+          }
+          _ => after,
+        }
+      }
+      None => {
+        out.push_str(&rest[open..=open + close]);
+        &rest[open + close + 1..]
+      }
+    };
+  }
+  out.push_str(rest);
+  out
+}
+
+fn is_separator(c: char) -> bool {
+  matches!(c, '_' | '-' | '.' | ' ')
+}
+
+/// Expands a layout template into a relative directory, dropping empty, "." and ".."
+/// segments so a missing value can't escape the session dir.
+pub fn layout_dir(template: &str, source: &NameSource) -> PathBuf {
+  expand(template, |t| source.token(t))
+    .split('/')
+    .map(|s| s.trim())
+    .filter(|s| !s.is_empty() && *s != "." && *s != "..")
+    .collect()
+}
+
+/* ---------------------------------- Routing --------------------------------- */
+
+/// Sends files by extension to a fixed directory, e.g. ["wav"] -> "Audio/Location".
+/// `dir` is a layout template, so tokens work here too ("Footage/{reel}").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteRule {
+  // Without the dot, any case: ["wav", "bwf"]
+  pub extensions: Vec<String>,
+  pub dir: String,
+}
+
+/// Directory from the first rule matching the file's extension.
+pub fn route_dir(rules: &[RouteRule], source: &NameSource) -> Option<PathBuf> {
+  let ext = source.src.extension()?.to_string_lossy().to_lowercase();
+  rules
+    .iter()
+    .find(|r| r.extensions.iter().any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(&ext)))
+    .map(|r| layout_dir(&r.dir, source))
+}
+
+/// Expands a rename template into a file name; the original extension is kept.
+pub fn file_name(template: &str, source: &NameSource) -> String {
+  let stem = sanitize(&expand(template, |t| source.token(t)));
+  let stem = if stem.is_empty() || stem == "." || stem == ".." {
+    source
+      .src
+      .file_stem()
+      .map(|s| s.to_string_lossy().to_string())
+      .unwrap_or_else(|| "file".to_string())
+  } else {
+    stem
+  };
+  match source.src.extension() {
+    Some(ext) => format!("{stem}.{}", ext.to_string_lossy()),
+    None => stem,
+  }
+}
+
+/// Music/<artist>/<album> from the file's tags, for the organize_audio mode.
+pub fn audio_dir(source: &NameSource) -> PathBuf {
+  let value = |token: &str, fallback: &str| {
+    let v = source.token(token).flatten().map(|v| sanitize(&v)).unwrap_or_default();
+    if v.is_empty() || v == "." || v == ".." {
+      fallback.to_string()
+    } else {
+      v
+    }
+  };
+  PathBuf::from("Music")
+    .join(value("artist", "Unknown Artist"))
+    .join(value("album", "Unknown Album"))
+}
@@ -0,0 +1,89 @@
+use std::{process::Command, thread, time::Duration};
+
+use crate::logging;
+
+/* --------------------------------- Detection -------------------------------- */
+
+const NETWORK_FS: &[&str] = &[
+  "smbfs", "cifs", "smb3", "nfs", "nfs4", "afpfs", "webdav", "davfs", "fuse.davfs2", "fuse.sshfs", "9p",
+];
+
+/// Filesystem type of the mount holding `path`, from `mount` output. Handles both
+/// the Linux ("dev on /mnt type cifs (rw,...)") and macOS
+/// ("//user@host/share on /Volumes/share (smbfs, nodev, ...)") formats.
+pub fn fs_type_for(path: &str) -> Option<String> {
+  let out = Command::new("mount").output().ok()?;
+  let s = String::from_utf8_lossy(&out.stdout);
+
+  // Longest mount point containing `path` wins, so /Volumes/x beats /
+  let mut best: Option<(usize, String)> = None;
+  for line in s.lines() {
+    let Some((_, rest)) = line.split_once(" on ") else {
+      continue;
+    };
+    let (mount, fs_type) = if let Some((m, after)) = rest.split_once(" type ") {
+      (m, after.split_whitespace().next().unwrap_or(""))
+    } else if let Some((m, after)) = rest.rsplit_once(" (") {
+      (m, after.split([',', ')']).next().unwrap_or("").trim())
+    } else {
+      continue;
+    };
+    let within = path == mount || mount == "/" || path.starts_with(&format!("{}/", mount.trim_end_matches('/')));
+    if within && mount.len() > best.as_ref().map_or(0, |(len, _)| *len) {
+      best = Some((mount.len(), fs_type.to_lowercase()));
+    }
+  }
+  best.map(|(_, t)| t)
+}
+
+pub fn is_network_fs(fs_type: &str) -> bool {
+  NETWORK_FS.contains(&fs_type)
+}
+
+pub fn is_network_path(path: &str) -> bool {
+  fs_type_for(path).is_some_and(|t| is_network_fs(&t))
+}
+
+/* ---------------------------------- Tuning ---------------------------------- */
+/* Every round trip costs milliseconds on a share, so network destinations get
+   bigger writes and fewer fsyncs, and a dropped connection is retried rather
+   than failing the file outright. */
+
+pub const LOCAL_BUF_BYTES: usize = 1024 * 1024;
+pub const NETWORK_BUF_BYTES: usize = 8 * 1024 * 1024;
+
+// Attempts per file after the first, for errors that look like a network blip
+pub const NETWORK_RETRIES: u32 = 3;
+
+pub fn buf_bytes(network: bool) -> usize {
+  if network {
+    NETWORK_BUF_BYTES
+  } else {
+    LOCAL_BUF_BYTES
+  }
+}
+
+/// Errors worth retrying on a network mount (timeouts, resets, stale handles).
+pub fn is_transient(err: &str) -> bool {
+  let e = err.to_lowercase();
+  [
+    "timed out",
+    "connection reset",
+    "connection aborted",
+    "broken pipe",
+    "temporarily unavailable",
+    "host is down",
+    "network is unreachable",
+    "stale",
+    "input/output error",
+  ]
+  .iter()
+  .any(|p| e.contains(p))
+}
+
+/// Sleeps before retry `attempt` (1-based): 1s, 2s, 4s...
+pub fn backoff(attempt: u32) {
+  let secs = 1u64 << attempt.saturating_sub(1).min(5);
+  logging::debug(&format!("network retry {attempt}: waiting {secs}s"));
+  thread::sleep(Duration::from_secs(secs));
+}
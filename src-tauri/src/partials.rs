@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashSet,
+  fs,
+  path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+use crate::{audit, logging, sessions, transfer};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialFile {
+  pub path: String,
+  pub bytes: u64,
+  pub session_dir: String,
+  pub reason: String, // "cancelled" | "incomplete" | "unrecorded" | "temp"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialsReport {
+  pub files: Vec<PartialFile>,
+  pub total_bytes: u64,
+  // Files actually deleted (only when confirmed paths were passed in)
+  pub removed_files: u64,
+  pub removed_bytes: u64,
+  // Sessions skipped because a running job is still writing to them
+  pub active_sessions: Vec<String>,
+}
+
+const TEMP_EXTS: &[&str] = &["part", "partial", "tmp", "crdownload"];
+
+/* ---------------------------------- Scanning -------------------------------- */
+
+fn file_len(p: &Path) -> Option<u64> {
+  fs::metadata(p).ok().filter(|m| m.is_file()).map(|m| m.len())
+}
+
+fn scan_session(dir: &Path, out: &mut Vec<PartialFile>) {
+  let session = dir.to_string_lossy().to_string();
+  let mut push = |path: &Path, bytes: u64, reason: &str| {
+    out.push(PartialFile {
+      path: path.to_string_lossy().to_string(),
+      bytes,
+      session_dir: session.clone(),
+      reason: reason.to_string(),
+    })
+  };
+
+  let rows = transfer::load_manifest_rows(dir).unwrap_or_default();
+  let mut seen: HashSet<PathBuf> = HashSet::new();
+  let mut recorded: HashSet<PathBuf> = HashSet::new();
+
+  for r in &rows {
+    let dest = PathBuf::from(&r.dest);
+    recorded.insert(dest.clone());
+    let Some(len) = file_len(&dest) else {
+      continue;
+    };
+    // Rows are per source; a later success at the same dest wins
+    if matches!(r.status.as_str(), "copied" | "moved" | "skipped") {
+      continue;
+    }
+    let reason = match r.status.as_str() {
+      "cancelled" => "cancelled",
+      "error" if len != r.bytes => "incomplete",
+      _ => continue,
+    };
+    if seen.insert(dest.clone()) {
+      push(&dest, len, reason);
+    }
+  }
+
+  // A crash leaves only the journal, which doesn't know about the file that was in flight
+  let crashed = !dir.join("manifest.json").exists();
+
+  for e in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+    if !e.file_type().is_file() {
+      continue;
+    }
+    let path = e.path();
+    if seen.contains(path) {
+      continue;
+    }
+    let name = e.file_name().to_string_lossy();
+    if e.depth() == 1 && transfer::SESSION_FILES.contains(&name.as_ref()) {
+      continue;
+    }
+    let ext = path
+      .extension()
+      .and_then(|s| s.to_str())
+      .unwrap_or("")
+      .to_lowercase();
+    let reason = if TEMP_EXTS.contains(&ext.as_str()) {
+      "temp"
+    } else if crashed && !recorded.contains(path) {
+      "unrecorded"
+    } else {
+      continue;
+    };
+    let len = e.metadata().map(|m| m.len()).unwrap_or(0);
+    seen.insert(path.to_path_buf());
+    push(path, len, reason);
+  }
+}
+
+/// Finds leftovers from interrupted sessions under `dest_mount`'s Transfers tree:
+/// files a cancelled or failed copy left short, temp files, and (for sessions that
+/// crashed before writing manifest.json) files the journal never recorded.
+/// Nothing is deleted unless `confirm` lists paths from an earlier scan; of those,
+/// only ones that still show up as partial are removed.
+pub fn clean_partials(dest_mount: &str, confirm: Option<Vec<String>>, active: &[PathBuf]) -> Result<PartialsReport, String> {
+  let dest = Path::new(dest_mount);
+  if !dest.is_dir() {
+    return Err(format!("{dest_mount} is not a folder"));
+  }
+
+  let mut files: Vec<PartialFile> = vec![];
+  let mut active_sessions: Vec<String> = vec![];
+  for dir in sessions::find_session_dirs(dest) {
+    if active.iter().any(|a| a == &dir) {
+      active_sessions.push(dir.to_string_lossy().to_string());
+      continue;
+    }
+    scan_session(&dir, &mut files);
+  }
+
+  let mut removed_files = 0u64;
+  let mut removed_bytes = 0u64;
+  if let Some(confirmed) = confirm {
+    let confirmed: HashSet<String> = confirmed.into_iter().collect();
+    files.retain(|f| {
+      if !confirmed.contains(&f.path) {
+        return true;
+      }
+      match fs::remove_file(&f.path) {
+        Ok(_) => {
+          removed_files += 1;
+          removed_bytes += f.bytes;
+          false
+        }
+        Err(e) => {
+          logging::warn(&format!("could not remove {}: {e}", f.path));
+          true
+        }
+      }
+    });
+    if removed_files > 0 {
+      logging::info(&format!("removed {removed_files} partial file(s) under {dest_mount}"));
+      audit::record(
+        "partials_removed",
+        serde_json::json!({
+          "dest": dest_mount,
+          "files": removed_files,
+          "bytes": removed_bytes,
+        }),
+      );
+    }
+  }
+
+  Ok(PartialsReport {
+    total_bytes: files.iter().map(|f| f.bytes).sum(),
+    files,
+    removed_files,
+    removed_bytes,
+    active_sessions,
+  })
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  env, fs,
+  path::{Component, Path, PathBuf},
+};
+
+/* ---------------------------------- Types ----------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedPath {
+  pub input: String,
+  // Absolute; canonical (symlinks resolved) when the path exists
+  pub path: String,
+  pub exists: bool,
+  // "file" | "folder" | "other"; None when it doesn't exist
+  pub kind: Option<String>,
+  // Windows network path (\\server\share\...)
+  pub unc: bool,
+}
+
+/* ------------------------------- Normalization ------------------------------ */
+/* One place to turn what a user typed, dropped or wrote into a plan file into a
+   path the engine can use: quotes and file:// stripped, ~ expanded, relative
+   paths resolved against `base`, "." and ".." folded, and on Windows the forms
+   C:/x, \\?\C:\x, //server/share and \\?\UNC\server\share reduced to the
+   ordinary C:\x and \\server\share. */
+
+fn home_dir() -> Option<PathBuf> {
+  env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).map(PathBuf::from)
+}
+
+// Minimal %XX decoding for file:// URLs
+fn percent_decode(s: &str) -> String {
+  let bytes = s.as_bytes();
+  let mut out = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'%' {
+      if let Some(Ok(b)) = s.get(i + 1..i + 3).map(|h| u8::from_str_radix(h, 16)) {
+        out.push(b);
+        i += 3;
+        continue;
+      }
+    }
+    out.push(bytes[i]);
+    i += 1;
+  }
+  String::from_utf8_lossy(&out).to_string()
+}
+
+fn strip_verbatim(s: &str) -> String {
+  if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+    format!(r"\\{rest}")
+  } else if let Some(rest) = s.strip_prefix(r"\\?\") {
+    rest.to_string()
+  } else {
+    s.to_string()
+  }
+}
+
+fn clean_input(input: &str) -> String {
+  let mut s = input.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+  if let Some(rest) = s.strip_prefix("file://") {
+    // file:///Users/x -> /Users/x, file:///C:/x -> C:/x, file://server/share -> //server/share
+    let rest = percent_decode(rest);
+    s = match rest.strip_prefix('/') {
+      Some(r) if cfg!(windows) && r.as_bytes().get(1) == Some(&b':') => r.to_string(),
+      Some(_) => rest,
+      None => format!("//{rest}"),
+    };
+  }
+  if cfg!(windows) {
+    s = strip_verbatim(&s.replace('/', "\\"));
+  }
+  s
+}
+
+// Folds "." and ".." without touching the filesystem (the path may not exist yet)
+fn fold(path: &Path) -> PathBuf {
+  let mut out = PathBuf::new();
+  for c in path.components() {
+    match c {
+      Component::CurDir => {}
+      Component::ParentDir => {
+        if !out.pop() {
+          out.push(c);
+        }
+      }
+      other => out.push(other),
+    }
+  }
+  out
+}
+
+/// Normalizes `input` to an absolute path. Relative input is resolved against
+/// `base`, and is an error without one.
+pub fn normalize_path(input: &str, base: Option<&str>) -> Result<NormalizedPath, String> {
+  let s = clean_input(input);
+  if s.is_empty() {
+    return Err("empty path".to_string());
+  }
+
+  let expanded = if s == "~" || s.starts_with("~/") || s.starts_with("~\\") {
+    let home = home_dir().ok_or_else(|| "cannot expand ~: home directory unknown".to_string())?;
+    home.join(s[1..].trim_start_matches(['/', '\\']))
+  } else {
+    PathBuf::from(&s)
+  };
+
+  let absolute = if expanded.is_absolute() {
+    expanded
+  } else {
+    let base = base
+      .filter(|b| !b.trim().is_empty())
+      .ok_or_else(|| format!("relative path needs a base: {input}"))?;
+    let base = normalize_path(base, None)?;
+    Path::new(&base.path).join(expanded)
+  };
+
+  let folded = fold(&absolute);
+  let meta = fs::metadata(&folded).ok();
+  let path = match meta {
+    Some(_) => fs::canonicalize(&folded)
+      .map(|p| strip_verbatim(&p.to_string_lossy()))
+      .unwrap_or_else(|_| folded.to_string_lossy().to_string()),
+    None => folded.to_string_lossy().to_string(),
+  };
+  let kind = meta.map(|m| {
+    if m.is_dir() {
+      "folder"
+    } else if m.is_file() {
+      "file"
+    } else {
+      "other"
+    }
+    .to_string()
+  });
+
+  Ok(NormalizedPath {
+    input: input.to_string(),
+    unc: cfg!(windows) && path.starts_with(r"\\"),
+    exists: kind.is_some(),
+    kind,
+    path,
+  })
+}
@@ -0,0 +1,123 @@
+use serde::Serialize;
+use std::{
+  env, fs,
+  io::ErrorKind,
+  path::{Path, PathBuf},
+};
+
+use crate::netfs;
+
+/* ---------------------------------- Types ----------------------------------- */
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingAccess {
+  // full_disk_access | removable_volumes | network_volumes | desktop | documents | downloads | files_and_folders
+  pub kind: String,
+  // The path the probe was denied on
+  pub path: String,
+  // Opens the matching pane in System Settings
+  pub settings_url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PermissionReport {
+  // False off macOS, where there is no TCC to check
+  pub checked: bool,
+  pub full_disk_access: Option<bool>,
+  pub missing: Vec<MissingAccess>,
+}
+
+/* ---------------------------------- Checks ---------------------------------- */
+/* macOS privacy controls (TCC) deny reads with EPERM rather than prompting when
+   access was refused, which otherwise shows up as a pile of per-file
+   "Operation not permitted" errors mid-transfer. Each check probes a path the
+   permission guards and reports which one is missing. */
+
+const FDA_URL: &str = "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles";
+const FILES_URL: &str = "x-apple.systempreferences:com.apple.preference.security?Privacy_FilesAndFolders";
+
+// Some(false) when TCC refused the read; None when the probe says nothing
+fn readable(path: &Path) -> Option<bool> {
+  let res = if path.is_dir() {
+    fs::read_dir(path).map(|_| ())
+  } else {
+    fs::File::open(path).map(|_| ())
+  };
+  match res {
+    Ok(_) => Some(true),
+    Err(e) if e.kind() == ErrorKind::PermissionDenied => Some(false),
+    Err(_) => None,
+  }
+}
+
+fn home() -> Option<PathBuf> {
+  env::var_os("HOME").map(PathBuf::from)
+}
+
+// Which privacy category guards `path`
+fn kind_for(path: &Path) -> &'static str {
+  let p = path.to_string_lossy();
+  if let Some(h) = home() {
+    for (dir, kind) in [("Desktop", "desktop"), ("Documents", "documents"), ("Downloads", "downloads")] {
+      if path.starts_with(h.join(dir)) {
+        return kind;
+      }
+    }
+  }
+  if p.starts_with("/Volumes/") {
+    if netfs::is_network_path(&p) {
+      "network_volumes"
+    } else {
+      "removable_volumes"
+    }
+  } else {
+    "files_and_folders"
+  }
+}
+
+/// Probes Full Disk Access and the Files-and-Folders categories covering `paths`
+/// (sources and destination of the upcoming transfer).
+pub fn check_permissions(paths: &[String]) -> PermissionReport {
+  if !cfg!(target_os = "macos") {
+    return PermissionReport {
+      checked: false,
+      full_disk_access: None,
+      missing: vec![],
+    };
+  }
+
+  let mut missing: Vec<MissingAccess> = vec![];
+
+  // The TCC database itself is only readable with Full Disk Access
+  let tcc = home().map(|h| h.join("Library/Application Support/com.apple.TCC/TCC.db"));
+  let full_disk_access = tcc.as_deref().and_then(readable);
+  if full_disk_access == Some(false) {
+    missing.push(MissingAccess {
+      kind: "full_disk_access".to_string(),
+      path: tcc.map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+      settings_url: FDA_URL.to_string(),
+    });
+  }
+
+  for p in paths {
+    let path = Path::new(p);
+    if readable(path) != Some(false) {
+      continue;
+    }
+    let kind = kind_for(path);
+    if missing.iter().any(|m| m.kind == kind) {
+      continue;
+    }
+    missing.push(MissingAccess {
+      kind: kind.to_string(),
+      path: p.clone(),
+      settings_url: FILES_URL.to_string(),
+    });
+  }
+
+  PermissionReport {
+    checked: true,
+    full_disk_access,
+    missing,
+  }
+}
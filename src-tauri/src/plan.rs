@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::{paths, transfer::TransferOptions, PickedItem};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+pub const PLAN_VERSION: u32 = 1;
+
+/// A saved selection plus settings: everything start_transfer needs, minus the
+/// machine-specific bits, so it can be reviewed, shared, and run later elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferPlan {
+  #[serde(default = "plan_version")]
+  pub version: u32,
+  #[serde(default)]
+  pub name: Option<String>,
+  #[serde(default)]
+  pub created_at: Option<String>,
+  pub items: Vec<PickedItem>,
+  // Optional: a plan may be executed against a different drive than it was made for
+  #[serde(default)]
+  pub dest_mount_point: Option<String>,
+  #[serde(default = "default_copy_mode")]
+  pub copy_mode: String,
+  #[serde(default = "default_conflict_policy")]
+  pub conflict_policy: String,
+  #[serde(default = "default_verify_mode")]
+  pub verify_mode: String,
+  #[serde(default)]
+  pub options: TransferOptions,
+}
+
+fn plan_version() -> u32 {
+  PLAN_VERSION
+}
+
+fn default_copy_mode() -> String {
+  "copy".to_string()
+}
+
+fn default_conflict_policy() -> String {
+  "rename".to_string()
+}
+
+fn default_verify_mode() -> String {
+  crate::checksum::DEFAULT_VERIFY_MODE.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanImport {
+  pub plan: TransferPlan,
+  // Items whose source path doesn't exist on this machine
+  pub missing_items: Vec<String>,
+}
+
+/* ------------------------------- Export / import ----------------------------- */
+
+pub fn export_plan(path: &str, mut plan: TransferPlan) -> Result<(), String> {
+  plan.version = PLAN_VERSION;
+  if plan.created_at.is_none() {
+    plan.created_at = Some(chrono::Local::now().to_rfc3339());
+  }
+  let json = serde_json::to_string_pretty(&plan).map_err(|e| format!("plan json error: {e}"))?;
+  fs::write(path, json).map_err(|e| format!("plan write error: {e}"))
+}
+
+pub fn import_plan(path: &str) -> Result<PlanImport, String> {
+  let s = fs::read_to_string(path).map_err(|e| format!("plan read error: {e}"))?;
+  let mut plan: TransferPlan = serde_json::from_str(&s).map_err(|e| format!("plan parse error: {e}"))?;
+
+  if plan.version > PLAN_VERSION {
+    return Err(format!(
+      "plan version {} is newer than this app supports ({PLAN_VERSION})",
+      plan.version
+    ));
+  }
+
+  // Hand-written plans may use ~ or paths relative to the plan file
+  let base = Path::new(path).parent().map(|p| p.to_string_lossy().to_string());
+  let mut missing_items = vec![];
+  for it in plan.items.iter_mut() {
+    match paths::normalize_path(&it.path, base.as_deref()) {
+      Ok(n) => {
+        it.path = n.path;
+        if !n.exists {
+          missing_items.push(it.path.clone());
+        }
+      }
+      Err(_) => missing_items.push(it.path.clone()),
+    }
+  }
+
+  Ok(PlanImport { plan, missing_items })
+}
@@ -0,0 +1,183 @@
+use std::{
+  fs::{self, FileTimes, OpenOptions},
+  path::Path,
+};
+
+/* ----------------------------- Preserve metadata ---------------------------- */
+/* With preserve_metadata, each copy gets its source's extended attributes
+   (Finder tags and colour labels, Linux user.* attributes), then its access and
+   modification times, then its permission bits, in that order so a read-only
+   mode doesn't stop the other two. Alternate data streams on Windows aren't
+   copied: std has no API for them. A failure here doesn't fail the file; the
+   manifest row carries it in metadata_error. */
+
+/// Copies what it can of `src`'s metadata onto `dst`; Err lists what didn't take.
+pub fn apply(src: &Path, dst: &Path) -> Result<(), String> {
+  let meta = fs::metadata(src).map_err(|e| format!("metadata error: {e}"))?;
+  let mut failed: Vec<String> = vec![];
+
+  if let Err(e) = copy_xattrs(src, dst) {
+    failed.push(e);
+  }
+  if let Err(e) = set_times(dst, &meta) {
+    failed.push(e);
+  }
+  if let Err(e) = set_mode(dst, &meta) {
+    failed.push(e);
+  }
+
+  if failed.is_empty() {
+    Ok(())
+  } else {
+    Err(failed.join("; "))
+  }
+}
+
+fn set_times(dst: &Path, meta: &fs::Metadata) -> Result<(), String> {
+  let mut times = FileTimes::new();
+  if let Ok(t) = meta.accessed() {
+    times = times.set_accessed(t);
+  }
+  if let Ok(t) = meta.modified() {
+    times = times.set_modified(t);
+  }
+  let f = OpenOptions::new()
+    .write(true)
+    .open(dst)
+    .map_err(|e| format!("set times open error: {e}"))?;
+  f.set_times(times).map_err(|e| format!("set times error: {e}"))
+}
+
+#[cfg(unix)]
+fn set_mode(dst: &Path, meta: &fs::Metadata) -> Result<(), String> {
+  use std::os::unix::fs::PermissionsExt;
+  let mode = meta.permissions().mode() & 0o7777;
+  fs::set_permissions(dst, fs::Permissions::from_mode(mode)).map_err(|e| format!("set permissions error: {e}"))
+}
+
+// Windows has only the read-only attribute
+#[cfg(not(unix))]
+fn set_mode(dst: &Path, meta: &fs::Metadata) -> Result<(), String> {
+  if !meta.permissions().readonly() {
+    return Ok(());
+  }
+  let mut perms = fs::metadata(dst).map_err(|e| format!("metadata error: {e}"))?.permissions();
+  perms.set_readonly(true);
+  fs::set_permissions(dst, perms).map_err(|e| format!("set permissions error: {e}"))
+}
+
+/* ------------------------------ Extended attrs ------------------------------ */
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod xattr {
+  use std::{ffi::CString, io, os::unix::ffi::OsStrExt, path::Path};
+
+  pub fn c_path(p: &Path) -> io::Result<CString> {
+    CString::new(p.as_os_str().as_bytes()).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))
+  }
+
+  fn check(n: isize) -> io::Result<usize> {
+    if n < 0 {
+      Err(io::Error::last_os_error())
+    } else {
+      Ok(n as usize)
+    }
+  }
+
+  #[cfg(target_os = "linux")]
+  unsafe fn raw_list(p: &CString, buf: *mut libc::c_char, len: usize) -> isize {
+    libc::llistxattr(p.as_ptr(), buf, len)
+  }
+  #[cfg(target_os = "macos")]
+  unsafe fn raw_list(p: &CString, buf: *mut libc::c_char, len: usize) -> isize {
+    libc::listxattr(p.as_ptr(), buf, len, libc::XATTR_NOFOLLOW)
+  }
+
+  #[cfg(target_os = "linux")]
+  unsafe fn raw_get(p: &CString, name: &CString, buf: *mut libc::c_void, len: usize) -> isize {
+    libc::lgetxattr(p.as_ptr(), name.as_ptr(), buf, len)
+  }
+  #[cfg(target_os = "macos")]
+  unsafe fn raw_get(p: &CString, name: &CString, buf: *mut libc::c_void, len: usize) -> isize {
+    libc::getxattr(p.as_ptr(), name.as_ptr(), buf, len, 0, libc::XATTR_NOFOLLOW)
+  }
+
+  #[cfg(target_os = "linux")]
+  unsafe fn raw_set(p: &CString, name: &CString, val: &[u8]) -> i32 {
+    libc::lsetxattr(p.as_ptr(), name.as_ptr(), val.as_ptr() as *const libc::c_void, val.len(), 0)
+  }
+  #[cfg(target_os = "macos")]
+  unsafe fn raw_set(p: &CString, name: &CString, val: &[u8]) -> i32 {
+    libc::setxattr(
+      p.as_ptr(),
+      name.as_ptr(),
+      val.as_ptr() as *const libc::c_void,
+      val.len(),
+      0,
+      libc::XATTR_NOFOLLOW,
+    )
+  }
+
+  /// Attribute names on `p` (NUL-separated list from the kernel).
+  pub fn list(p: &CString) -> io::Result<Vec<CString>> {
+    let len = check(unsafe { raw_list(p, std::ptr::null_mut(), 0) })?;
+    let mut buf = vec![0u8; len];
+    let len = check(unsafe { raw_list(p, buf.as_mut_ptr() as *mut libc::c_char, buf.len()) })?;
+    buf.truncate(len);
+    Ok(
+      buf
+        .split(|&b| b == 0)
+        .filter(|n| !n.is_empty())
+        .filter_map(|n| CString::new(n).ok())
+        .collect(),
+    )
+  }
+
+  pub fn get(p: &CString, name: &CString) -> io::Result<Vec<u8>> {
+    let len = check(unsafe { raw_get(p, name, std::ptr::null_mut(), 0) })?;
+    let mut buf = vec![0u8; len];
+    let len = check(unsafe { raw_get(p, name, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) })?;
+    buf.truncate(len);
+    Ok(buf)
+  }
+
+  pub fn set(p: &CString, name: &CString, val: &[u8]) -> io::Result<()> {
+    check(unsafe { raw_set(p, name, val) } as isize).map(|_| ())
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn copy_xattrs(src: &Path, dst: &Path) -> Result<(), String> {
+  let (s, d) = match (xattr::c_path(src), xattr::c_path(dst)) {
+    (Ok(s), Ok(d)) => (s, d),
+    _ => return Err("xattr error: path contains a NUL byte".to_string()),
+  };
+  let names = match xattr::list(&s) {
+    Ok(n) => n,
+    // Source filesystem has no xattrs (FAT, most network shares): nothing to copy
+    Err(e) if e.kind() == std::io::ErrorKind::Unsupported => return Ok(()),
+    Err(e) => return Err(format!("xattr list error: {e}")),
+  };
+
+  let mut failed: Vec<String> = vec![];
+  for name in names {
+    let n = name.to_string_lossy().to_string();
+    // security.*, system.* and trusted.* need privileges or are set by the kernel
+    if cfg!(target_os = "linux") && !n.starts_with("user.") {
+      continue;
+    }
+    if let Err(e) = xattr::get(&s, &name).and_then(|v| xattr::set(&d, &name, &v)) {
+      failed.push(format!("{n}: {e}"));
+    }
+  }
+  if failed.is_empty() {
+    Ok(())
+  } else {
+    Err(format!("xattr error: {}", failed.join(", ")))
+  }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn copy_xattrs(_src: &Path, _dst: &Path) -> Result<(), String> {
+  Ok(())
+}
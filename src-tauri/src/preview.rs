@@ -0,0 +1,354 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+use crate::{audio_tags, exif, transfer, video_meta};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemPreview {
+  pub path: String,
+  pub name: String,
+  pub kind: String, // "file" | "folder"
+  pub size_bytes: u64,
+  // Files inside, for folders
+  pub file_count: Option<u64>,
+  pub modified: Option<String>,
+  pub category: String,
+  pub ext: String,
+  // EXIF capture time for stills, container creation time for clips (RFC 3339)
+  pub captured_at: Option<String>,
+  // EXIF make + model
+  pub camera: Option<String>,
+  // data: URL, small enough to drop straight into an <img>
+  pub thumbnail: Option<String>,
+  pub thumbnail_source: Option<String>, // "exif" | "system" | "file"
+}
+
+/// Everything the inspector panel shows for one path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInfo {
+  pub path: String,
+  pub name: String,
+  pub is_dir: bool,
+  pub size_bytes: u64,
+  // RFC 3339; created isn't available on every filesystem
+  pub created: Option<String>,
+  pub modified: Option<String>,
+  pub accessed: Option<String>,
+  pub readonly: bool,
+  // Unix permission bits as octal, e.g. "644"
+  pub permissions: Option<String>,
+  pub category: String,
+  pub ext: String,
+  // From the extension, e.g. "image/jpeg"
+  pub mime: String,
+  // From the file's first bytes, e.g. "jpeg", "mp4", "flac"; None if unrecognised
+  pub detected_type: Option<String>,
+  pub exif: Option<ExifSummary>,
+  pub audio: Option<AudioSummary>,
+  // Container creation time for clips
+  pub media_created: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExifSummary {
+  pub make: Option<String>,
+  pub model: Option<String>,
+  pub lens: Option<String>,
+  pub iso: Option<u32>,
+  pub taken_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioSummary {
+  pub artist: Option<String>,
+  pub album_artist: Option<String>,
+  pub album: Option<String>,
+  pub title: Option<String>,
+  pub track: Option<String>,
+}
+
+// Images the webview can show as-is when nothing smaller is available
+const WEB_IMAGE_EXTS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
+const MAX_INLINE_IMAGE_BYTES: u64 = 2 * 1024 * 1024;
+// A folder's preview is the first image among this many files
+const FOLDER_PREVIEW_SCAN: usize = 200;
+
+/* ---------------------------------- Preview --------------------------------- */
+
+pub fn item_preview(path: &str) -> Result<ItemPreview, String> {
+  let p = PathBuf::from(path);
+  let meta = fs::metadata(&p).map_err(|e| format!("metadata error: {e}"))?;
+  let name = p
+    .file_name()
+    .map(|s| s.to_string_lossy().to_string())
+    .unwrap_or_else(|| path.to_string());
+  let modified = meta
+    .modified()
+    .ok()
+    .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339());
+
+  if meta.is_dir() {
+    let mut size_bytes = 0u64;
+    let mut file_count = 0u64;
+    let mut cover: Option<PathBuf> = None;
+    for e in WalkDir::new(&p).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+      if !e.file_type().is_file() {
+        continue;
+      }
+      size_bytes += e.metadata().map(|m| m.len()).unwrap_or(0);
+      file_count += 1;
+      if cover.is_none() && (file_count as usize) <= FOLDER_PREVIEW_SCAN {
+        let (cat, _) = transfer::category_for(e.path());
+        if cat == "Images" {
+          cover = Some(e.path().to_path_buf());
+        }
+      }
+    }
+    let (thumbnail, thumbnail_source) = match cover.as_deref().and_then(thumbnail_for) {
+      Some((t, s)) => (Some(t), Some(s)),
+      None => (None, None),
+    };
+    return Ok(ItemPreview {
+      path: path.to_string(),
+      name,
+      kind: "folder".to_string(),
+      size_bytes,
+      file_count: Some(file_count),
+      modified,
+      category: "Folder".to_string(),
+      ext: String::new(),
+      captured_at: None,
+      camera: None,
+      thumbnail,
+      thumbnail_source,
+    });
+  }
+
+  let (category, ext) = transfer::category_for(&p);
+  let exif = if category == "Images" { exif::read(&p) } else { None };
+  let captured_at = match category.as_str() {
+    "Videos" => video_meta::creation_time(&p).map(|t| t.with_timezone(&chrono::Local).to_rfc3339()),
+    _ => exif.as_ref().and_then(|e| e.taken_at).map(|t| t.to_rfc3339()),
+  };
+  let camera = exif.as_ref().and_then(|e| match (&e.make, &e.model) {
+    // Most models already start with the make ("Canon EOS R5")
+    (Some(make), Some(model)) if !model.to_lowercase().starts_with(&make.to_lowercase()) => {
+      Some(format!("{make} {model}"))
+    }
+    (_, Some(model)) => Some(model.clone()),
+    (Some(make), None) => Some(make.clone()),
+    (None, None) => None,
+  });
+
+  let (thumbnail, thumbnail_source) = match thumbnail_for(&p) {
+    Some((t, s)) => (Some(t), Some(s)),
+    None => (None, None),
+  };
+
+  Ok(ItemPreview {
+    path: path.to_string(),
+    name,
+    kind: "file".to_string(),
+    size_bytes: meta.len(),
+    file_count: None,
+    modified,
+    category,
+    ext,
+    captured_at,
+    camera,
+    thumbnail,
+    thumbnail_source,
+  })
+}
+
+/* --------------------------------- File info -------------------------------- */
+
+fn rfc3339(t: std::io::Result<std::time::SystemTime>) -> Option<String> {
+  t.ok().map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339())
+}
+
+pub fn file_info(path: &str) -> Result<FileInfo, String> {
+  let p = PathBuf::from(path);
+  let meta = fs::metadata(&p).map_err(|e| format!("metadata error: {e}"))?;
+  let name = p
+    .file_name()
+    .map(|s| s.to_string_lossy().to_string())
+    .unwrap_or_else(|| path.to_string());
+
+  #[cfg(unix)]
+  let permissions = {
+    use std::os::unix::fs::MetadataExt;
+    Some(format!("{:o}", meta.mode() & 0o7777))
+  };
+  #[cfg(not(unix))]
+  let permissions = None;
+
+  let (category, ext, mime) = if meta.is_dir() {
+    ("Folder".to_string(), String::new(), "inode/directory".to_string())
+  } else {
+    let (cat, ext) = transfer::category_for(&p);
+    let mime = mime_guess::from_path(&p).first_or_octet_stream().essence_str().to_string();
+    (cat, ext, mime)
+  };
+
+  let is_file = meta.is_file();
+  let exif = is_file.then(|| exif::read(&p)).flatten().map(|e| ExifSummary {
+    make: e.make,
+    model: e.model,
+    lens: e.lens,
+    iso: e.iso,
+    taken_at: e.taken_at.map(|t| t.to_rfc3339()),
+  });
+  let audio = (is_file && category == "Audio")
+    .then(|| audio_tags::read(&p))
+    .flatten()
+    .map(|t| AudioSummary {
+      artist: t.artist,
+      album_artist: t.album_artist,
+      album: t.album,
+      title: t.title,
+      track: t.track,
+    });
+  let media_created = is_file
+    .then(|| video_meta::creation_time(&p))
+    .flatten()
+    .map(|t| t.with_timezone(&chrono::Local).to_rfc3339());
+
+  Ok(FileInfo {
+    path: path.to_string(),
+    name,
+    is_dir: meta.is_dir(),
+    size_bytes: if meta.is_dir() { 0 } else { meta.len() },
+    created: rfc3339(meta.created()),
+    modified: rfc3339(meta.modified()),
+    accessed: rfc3339(meta.accessed()),
+    readonly: meta.permissions().readonly(),
+    permissions,
+    category,
+    ext,
+    mime,
+    detected_type: if is_file { sniff_type(&p) } else { None },
+    exif,
+    audio,
+    media_created,
+  })
+}
+
+// Magic numbers for the formats we handle; the extension can lie (or be missing)
+fn sniff_type(path: &Path) -> Option<String> {
+  use std::io::Read;
+
+  let mut head = [0u8; 16];
+  let n = fs::File::open(path).ok()?.read(&mut head).ok()?;
+  let b = &head[..n];
+
+  let t = if b.starts_with(&[0xFF, 0xD8, 0xFF]) {
+    "jpeg"
+  } else if b.starts_with(b"\x89PNG") {
+    "png"
+  } else if b.starts_with(b"GIF8") {
+    "gif"
+  } else if b.starts_with(b"RIFF") && b.get(8..12) == Some(b"WEBP") {
+    "webp"
+  } else if b.starts_with(b"RIFF") && b.get(8..12) == Some(b"WAVE") {
+    "wav"
+  } else if b.starts_with(b"RIFF") && b.get(8..12) == Some(b"AVI ") {
+    "avi"
+  } else if b.starts_with(b"II*\0") || b.starts_with(b"MM\0*") {
+    "tiff"
+  } else if b.starts_with(b"FUJIFILMCCD-RAW") {
+    "raf"
+  } else if b.get(4..8) == Some(b"ftyp") {
+    match b.get(8..12) {
+      Some(b"qt  ") => "mov",
+      Some(b"heic") | Some(b"heix") | Some(b"mif1") => "heic",
+      Some(b"crx ") => "cr3",
+      Some(b"M4A ") => "m4a",
+      _ => "mp4",
+    }
+  } else if b.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+    "matroska"
+  } else if b.starts_with(b"ID3") || (b.len() >= 2 && b[0] == 0xFF && b[1] & 0xE0 == 0xE0) {
+    "mp3"
+  } else if b.starts_with(b"fLaC") {
+    "flac"
+  } else if b.starts_with(b"OggS") {
+    "ogg"
+  } else if b.starts_with(b"%PDF") {
+    "pdf"
+  } else if b.starts_with(b"PK\x03\x04") {
+    "zip"
+  } else if b.starts_with(b"7z\xBC\xAF") {
+    "7z"
+  } else if b.starts_with(&[0x1F, 0x8B]) {
+    "gzip"
+  } else {
+    return None;
+  };
+  Some(t.to_string())
+}
+
+// Cheapest first: the camera's embedded JPEG, then the OS thumbnailer, then the
+// file itself when it's a small web image.
+fn thumbnail_for(path: &Path) -> Option<(String, String)> {
+  if let Some(jpeg) = exif::thumbnail(path) {
+    return Some((data_url("image/jpeg", &jpeg), "exif".to_string()));
+  }
+  if let Some(png) = system_thumbnail(path) {
+    return Some((data_url("image/png", &png), "system".to_string()));
+  }
+
+  let ext = path
+    .extension()
+    .and_then(|s| s.to_str())
+    .unwrap_or("")
+    .to_lowercase();
+  let small = fs::metadata(path).map(|m| m.len() <= MAX_INLINE_IMAGE_BYTES).unwrap_or(false);
+  if WEB_IMAGE_EXTS.contains(&ext.as_str()) && small {
+    let bytes = fs::read(path).ok()?;
+    let mime = mime_guess::from_ext(&ext).first_or_octet_stream();
+    return Some((data_url(mime.essence_str(), &bytes), "file".to_string()));
+  }
+  None
+}
+
+fn data_url(mime: &str, bytes: &[u8]) -> String {
+  format!("data:{mime};base64,{}", STANDARD.encode(bytes))
+}
+
+// Quick Look renders stills, raws and video frames alike
+#[cfg(target_os = "macos")]
+fn system_thumbnail(path: &Path) -> Option<Vec<u8>> {
+  use std::process::Command;
+  const THUMB_SIZE: u32 = 256;
+
+  let out_dir = std::env::temp_dir().join(format!("transferpilot-preview-{}", uuid::Uuid::new_v4()));
+  fs::create_dir_all(&out_dir).ok()?;
+  let ok = Command::new("qlmanage")
+    .arg("-t")
+    .arg("-s")
+    .arg(THUMB_SIZE.to_string())
+    .arg("-o")
+    .arg(&out_dir)
+    .arg(path)
+    .output()
+    .map(|o| o.status.success())
+    .unwrap_or(false);
+
+  let name = format!("{}.png", path.file_name()?.to_string_lossy());
+  let png = if ok { fs::read(out_dir.join(name)).ok() } else { None };
+  let _ = fs::remove_dir_all(&out_dir);
+  png
+}
+
+#[cfg(not(target_os = "macos"))]
+fn system_thumbnail(_path: &Path) -> Option<Vec<u8>> {
+  None
+}
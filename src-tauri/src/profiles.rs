@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  collections::HashSet,
+  fs,
+  path::PathBuf,
+  sync::{Mutex, OnceLock},
+  thread,
+  time::Duration,
+};
+use tauri::{AppHandle, Emitter};
+
+use crate::{daemon, device, logging, transfer::TransferOptions};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+/// A saved preset. With `card_uuid` set it becomes an auto-import profile: when
+/// that exact card is mounted, `profiles://card-matched` tells the UI to apply it
+/// (or, in background mode with auto-import on, the backend starts the copy).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+  pub id: String,
+  pub name: String,
+  #[serde(default)]
+  pub card_uuid: Option<String>,
+  #[serde(default)]
+  pub dest_mount_point: Option<String>,
+  #[serde(default = "default_copy_mode")]
+  pub copy_mode: String,
+  #[serde(default = "default_conflict_policy")]
+  pub conflict_policy: String,
+  #[serde(default = "default_verify_mode")]
+  pub verify_mode: String,
+  // Engine options, including the per-profile debug flag, dest_subdir and rename_prefix
+  #[serde(default)]
+  pub options: TransferOptions,
+}
+
+fn default_copy_mode() -> String {
+  "copy".to_string()
+}
+
+fn default_conflict_policy() -> String {
+  "rename".to_string()
+}
+
+fn default_verify_mode() -> String {
+  crate::checksum::DEFAULT_VERIFY_MODE.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardMatched {
+  pub profile: Profile,
+  pub mount_point: String,
+  // Set when daemon auto-import already started the transfer
+  #[serde(default)]
+  pub job_id: Option<String>,
+}
+
+/* ----------------------------------- Store ---------------------------------- */
+
+static PROFILES_FILE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+fn slot() -> &'static Mutex<Option<PathBuf>> {
+  PROFILES_FILE.get_or_init(|| Mutex::new(None))
+}
+
+pub fn set_profiles_file(path: PathBuf) {
+  if let Some(parent) = path.parent() {
+    let _ = fs::create_dir_all(parent);
+  }
+  if let Ok(mut g) = slot().lock() {
+    *g = Some(path);
+  }
+}
+
+fn profiles_path() -> Result<PathBuf, String> {
+  slot()
+    .lock()
+    .ok()
+    .and_then(|g| g.clone())
+    .ok_or_else(|| "profile store not initialised".to_string())
+}
+
+pub fn list_profiles() -> Result<Vec<Profile>, String> {
+  let path = profiles_path()?;
+  if !path.exists() {
+    return Ok(vec![]);
+  }
+  let s = fs::read_to_string(&path).map_err(|e| format!("profiles read error: {e}"))?;
+  serde_json::from_str(&s).map_err(|e| format!("profiles parse error: {e}"))
+}
+
+fn write_profiles(profiles: &[Profile]) -> Result<(), String> {
+  let json = serde_json::to_string_pretty(profiles).map_err(|e| format!("profiles json error: {e}"))?;
+  fs::write(profiles_path()?, json).map_err(|e| format!("profiles write error: {e}"))
+}
+
+/// Inserts or replaces by id (a blank id gets a fresh one). A card can only be
+/// bound to one profile, so binding it here unbinds it elsewhere.
+pub fn save_profile(mut profile: Profile) -> Result<Profile, String> {
+  if profile.id.trim().is_empty() {
+    profile.id = uuid::Uuid::new_v4().to_string();
+  }
+  let mut all = list_profiles()?;
+  if let Some(uuid) = profile.card_uuid.as_deref() {
+    for p in all.iter_mut().filter(|p| p.id != profile.id) {
+      if p.card_uuid.as_deref() == Some(uuid) {
+        p.card_uuid = None;
+      }
+    }
+  }
+  match all.iter_mut().find(|p| p.id == profile.id) {
+    Some(existing) => *existing = profile.clone(),
+    None => all.push(profile.clone()),
+  }
+  write_profiles(&all)?;
+  Ok(profile)
+}
+
+pub fn delete_profile(id: &str) -> Result<(), String> {
+  let mut all = list_profiles()?;
+  all.retain(|p| p.id != id);
+  write_profiles(&all)
+}
+
+pub fn profile_for_card(uuid: &str) -> Result<Option<Profile>, String> {
+  Ok(
+    list_profiles()?
+      .into_iter()
+      .find(|p| p.card_uuid.as_deref() == Some(uuid)),
+  )
+}
+
+/* ---------------------------------- Watcher --------------------------------- */
+
+/// Polls for newly mounted volumes and emits `profiles://card-matched` when one
+/// carries a UUID bound to a profile. Only new mounts are probed for a UUID.
+pub fn spawn_card_watcher(app: AppHandle) {
+  thread::spawn(move || {
+    let mut known: HashSet<String> = device::mounted_points().into_iter().collect();
+    loop {
+      thread::sleep(Duration::from_secs(3));
+
+      let current: HashSet<String> = device::mounted_points().into_iter().collect();
+      for mount in current.difference(&known) {
+        let Some(uuid) = device::volume_uuid(mount) else {
+          continue;
+        };
+        match profile_for_card(&uuid) {
+          Ok(Some(profile)) => {
+            logging::info(&format!("card {uuid} at {mount} matches profile {}", profile.name));
+            let job_id = daemon::auto_import(&app, &profile, mount);
+            let _ = app.emit(
+              "profiles://card-matched",
+              CardMatched {
+                profile,
+                mount_point: mount.clone(),
+                job_id,
+              },
+            );
+          }
+          Ok(None) => {}
+          Err(e) => logging::debug(&format!("profile lookup failed: {e}")),
+        }
+      }
+      known = current;
+    }
+  });
+}
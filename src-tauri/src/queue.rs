@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::Mutex};
+
+use crate::{paths, transfer::QueueItem};
+
+/* ---------------------------------- Types ----------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedPath {
+  pub path: String,
+  // "empty" | "not_found" | "duplicate" | "wrong_kind"
+  pub reason: String,
+}
+
+// A selection that overlaps a queued folder and was folded into it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedPath {
+  pub path: String,
+  pub into: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueAdd {
+  pub added: Vec<QueueItem>,
+  pub rejected: Vec<RejectedPath>,
+  pub merged: Vec<MergedPath>,
+  // Previously queued items now covered by an added folder; drop them from the UI
+  pub removed_ids: Vec<String>,
+}
+
+/* ----------------------------------- Queue ---------------------------------- */
+/* The selection lives here as well as in the UI so the pickers and drag-and-drop
+   can refuse paths that are already queued. Paths go through
+   paths::normalize_path, so "/a/b", "/a/b/", "~/b" and a symlink to it are all
+   the same item.
+   Nested selections collapse into the outer folder: a file dropped together with
+   its folder would otherwise be copied twice. */
+
+#[derive(Default)]
+pub struct Queue(Mutex<Vec<(PathBuf, QueueItem)>>);
+
+fn reject(out: &mut QueueAdd, path: &str, reason: &str) {
+  out.rejected.push(RejectedPath {
+    path: path.to_string(),
+    reason: reason.to_string(),
+  });
+}
+
+impl Queue {
+  /// Validates and queues `inputs`. `kind` forces "file" or "folder" (the pickers);
+  /// None takes it from the filesystem (drag and drop).
+  pub fn add(&self, inputs: Vec<String>, kind: Option<&str>) -> Result<QueueAdd, String> {
+    let mut g = self.0.lock().map_err(|_| "queue poisoned".to_string())?;
+    let mut out = QueueAdd::default();
+
+    for raw in inputs {
+      if raw.trim().is_empty() {
+        reject(&mut out, &raw, "empty");
+        continue;
+      }
+      let n = match paths::normalize_path(&raw, None) {
+        Ok(n) if n.exists => n,
+        _ => {
+          reject(&mut out, &raw, "not_found");
+          continue;
+        }
+      };
+      let (p, real) = (n.path.clone(), PathBuf::from(&n.path));
+      let actual = if n.kind.as_deref() == Some("folder") { "folder" } else { "file" };
+      if kind.is_some_and(|k| k != actual) {
+        reject(&mut out, &p, "wrong_kind");
+        continue;
+      }
+      if g.iter().any(|(r, _)| *r == real) {
+        reject(&mut out, &p, "duplicate");
+        continue;
+      }
+      if let Some((_, outer)) = g.iter().find(|(r, it)| it.kind == "folder" && real.starts_with(r)) {
+        out.merged.push(MergedPath {
+          path: p,
+          into: outer.path.clone(),
+        });
+        continue;
+      }
+      if actual == "folder" {
+        let (inner, keep): (Vec<_>, Vec<_>) = g.drain(..).partition(|(r, _)| r.starts_with(&real));
+        *g = keep;
+        for (_, it) in inner {
+          // Added earlier in this same call: just leave it out of `added`
+          match out.added.iter().position(|a| a.id == it.id) {
+            Some(i) => {
+              out.added.remove(i);
+            }
+            None => out.removed_ids.push(it.id.clone()),
+          }
+          out.merged.push(MergedPath {
+            path: it.path,
+            into: p.clone(),
+          });
+        }
+      }
+
+      let item = QueueItem {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind: actual.to_string(),
+        path: p,
+        size_bytes: None,
+        file_count: None,
+      };
+      g.push((real, item.clone()));
+      out.added.push(item);
+    }
+    Ok(out)
+  }
+
+  pub fn remove(&self, ids: &[String]) {
+    if let Ok(mut g) = self.0.lock() {
+      g.retain(|(_, it)| !ids.contains(&it.id));
+    }
+  }
+
+  pub fn clear(&self) {
+    if let Ok(mut g) = self.0.lock() {
+      g.clear();
+    }
+  }
+
+  pub fn list(&self) -> Vec<QueueItem> {
+    self
+      .0
+      .lock()
+      .map(|g| g.iter().map(|(_, it)| it.clone()).collect())
+      .unwrap_or_default()
+  }
+}
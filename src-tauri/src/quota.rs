@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+use crate::{audit, journal, logging, sessions};
+
+/* ----------------------------------- Types ---------------------------------- */
+/* The cap lives on the drive itself (Transfers/.transferpilot-quota.json), so it
+   follows the drive to whichever machine offloads onto it next. */
+
+pub const QUOTA_FILE: &str = ".transferpilot-quota.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quota {
+  // Ceiling for everything under Transfers/ on this drive
+  pub max_bytes: u64,
+  // When a new session would go over: "warn" | "fail" | "trim" (delete the oldest
+  // finished sessions until it fits)
+  #[serde(default = "default_action")]
+  pub action: String,
+}
+
+fn default_action() -> String {
+  "warn".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaCheck {
+  pub max_bytes: u64,
+  pub action: String,
+  pub used_bytes: u64,
+  pub incoming_bytes: u64,
+  pub exceeded: bool,
+  // Bytes that would have to be freed (0 when it fits)
+  pub over_by: u64,
+}
+
+/* ----------------------------------- Store ---------------------------------- */
+
+fn quota_path(dest_mount: &str) -> PathBuf {
+  Path::new(dest_mount).join("Transfers").join(QUOTA_FILE)
+}
+
+pub fn get_quota(dest_mount: &str) -> Result<Option<Quota>, String> {
+  let path = quota_path(dest_mount);
+  if !path.exists() {
+    return Ok(None);
+  }
+  let s = fs::read_to_string(&path).map_err(|e| format!("quota read error: {e}"))?;
+  serde_json::from_str(&s)
+    .map(Some)
+    .map_err(|e| format!("quota parse error: {e}"))
+}
+
+/// Sets (or with None, removes) the cap for the drive at `dest_mount`.
+pub fn set_quota(dest_mount: &str, quota: Option<Quota>) -> Result<(), String> {
+  let path = quota_path(dest_mount);
+  match quota {
+    Some(q) => {
+      if !matches!(q.action.as_str(), "warn" | "fail" | "trim") {
+        return Err(format!("unknown quota action: {}", q.action));
+      }
+      if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("mkdir error: {e}"))?;
+      }
+      let json = serde_json::to_string_pretty(&q).map_err(|e| format!("quota json error: {e}"))?;
+      fs::write(&path, json).map_err(|e| format!("quota write error: {e}"))
+    }
+    None => match fs::remove_file(&path) {
+      Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(format!("quota remove error: {e}")),
+      _ => Ok(()),
+    },
+  }
+}
+
+/* ---------------------------------- Usage ----------------------------------- */
+
+fn dir_bytes(dir: &Path) -> u64 {
+  WalkDir::new(dir)
+    .into_iter()
+    .filter_map(|e| e.ok())
+    .filter(|e| e.file_type().is_file())
+    .filter_map(|e| e.metadata().ok())
+    .map(|m| m.len())
+    .sum()
+}
+
+/// Bytes used by every session on the drive.
+pub fn transfers_usage(dest_mount: &str) -> u64 {
+  sessions::find_session_dirs(Path::new(dest_mount))
+    .iter()
+    .map(|d| dir_bytes(d))
+    .sum()
+}
+
+/// How a new session of `incoming_bytes` stands against the drive's cap; None when
+/// no cap is set (or it can't be read).
+pub fn check(dest_mount: &str, incoming_bytes: u64) -> Option<QuotaCheck> {
+  let quota = match get_quota(dest_mount) {
+    Ok(q) => q?,
+    Err(e) => {
+      logging::warn(&e);
+      return None;
+    }
+  };
+  let used_bytes = transfers_usage(dest_mount);
+  let needed = used_bytes.saturating_add(incoming_bytes);
+  Some(QuotaCheck {
+    max_bytes: quota.max_bytes,
+    action: quota.action,
+    used_bytes,
+    incoming_bytes,
+    exceeded: needed > quota.max_bytes,
+    over_by: needed.saturating_sub(quota.max_bytes),
+  })
+}
+
+/* --------------------------------- Enforce ---------------------------------- */
+
+/// Called before a new session is created. "fail" refuses the transfer, "trim"
+/// deletes the oldest finished sessions until the new one fits, "warn" only logs.
+pub fn enforce(dest_mount: &str, incoming_bytes: u64) -> Result<(), String> {
+  let Some(c) = check(dest_mount, incoming_bytes) else {
+    return Ok(());
+  };
+  if !c.exceeded {
+    return Ok(());
+  }
+  let msg = format!(
+    "Transfers quota on {dest_mount} exceeded by {} bytes ({} used + {} new > {} cap)",
+    c.over_by, c.used_bytes, c.incoming_bytes, c.max_bytes
+  );
+  match c.action.as_str() {
+    "fail" => Err(msg),
+    "trim" => {
+      let freed = trim(dest_mount, c.over_by);
+      if freed < c.over_by {
+        return Err(format!("{msg}; trimming old sessions freed only {freed} bytes"));
+      }
+      Ok(())
+    }
+    _ => {
+      logging::warn(&msg);
+      Ok(())
+    }
+  }
+}
+
+// Only sessions that finished (manifest.json, no journal) are candidates, so a
+// run in progress, or one waiting to be resumed, is never touched.
+fn trim(dest_mount: &str, need: u64) -> u64 {
+  let mut freed = 0u64;
+  for dir in sessions::find_session_dirs(Path::new(dest_mount)) {
+    if freed >= need {
+      break;
+    }
+    if !dir.join("manifest.json").exists() || dir.join(journal::JOURNAL_FILE).exists() {
+      continue;
+    }
+    let bytes = dir_bytes(&dir);
+    match fs::remove_dir_all(&dir) {
+      Ok(_) => {
+        freed += bytes;
+        logging::info(&format!("quota trim: removed {} ({bytes} bytes)", dir.display()));
+        audit::record(
+          "quota_trim",
+          serde_json::json!({
+            "session_dir": dir.to_string_lossy(),
+            "bytes": bytes,
+          }),
+        );
+      }
+      Err(e) => logging::warn(&format!("quota trim: could not remove {}: {e}", dir.display())),
+    }
+  }
+  freed
+}
@@ -0,0 +1,508 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+use crate::{
+  audit,
+  checksum::{self, Algo},
+  logging,
+  transfer::{self, ManifestItem},
+  watchdog::Watchdog,
+  TransferSummary,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportOptions {
+  // Heading on the report, e.g. "Smith wedding — card A"; defaults to the session folder name
+  pub label: Option<String>,
+  // Defaults to the logged-in user
+  pub operator: Option<String>,
+  // Also render delivery_report.pdf (needs wkhtmltopdf or a Chromium-based browser)
+  pub pdf: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryReport {
+  pub html_path: String,
+  // None when PDF wasn't requested or no converter was found (see pdf_error)
+  pub pdf_path: Option<String>,
+  pub pdf_error: Option<String>,
+}
+
+pub const REPORT_HTML_FILE: &str = "delivery_report.html";
+pub const REPORT_PDF_FILE: &str = "delivery_report.pdf";
+pub const HASHDEEP_FILE: &str = "hashdeep_audit.txt";
+pub const MANIFEST_CSV_FILE: &str = "manifest.csv";
+
+const LOGO_PNG: &[u8] = include_bytes!("../icons/128x128.png");
+
+/* --------------------------------- Formatting -------------------------------- */
+
+fn esc(s: &str) -> String {
+  s.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+pub(crate) fn human_bytes(n: u64) -> String {
+  const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+  let mut v = n as f64;
+  let mut u = 0;
+  while v >= 1000.0 && u < UNITS.len() - 1 {
+    v /= 1000.0;
+    u += 1;
+  }
+  if u == 0 {
+    format!("{n} B")
+  } else {
+    format!("{v:.2} {}", UNITS[u])
+  }
+}
+
+pub(crate) fn human_duration(ms: u64) -> String {
+  let secs = ms / 1000;
+  let (h, m, s) = (secs / 3600, (secs / 60) % 60, secs % 60);
+  if h > 0 {
+    format!("{h}h {m:02}m {s:02}s")
+  } else if m > 0 {
+    format!("{m}m {s:02}s")
+  } else {
+    format!("{}.{:01}s", s, (ms % 1000) / 100)
+  }
+}
+
+/// Session summary.json, or one rebuilt from the manifest when the run never wrote it.
+pub(crate) fn load_summary(session_dir: &Path, rows: &[ManifestItem]) -> TransferSummary {
+  let from_file = fs::read_to_string(session_dir.join("summary.json"))
+    .ok()
+    .and_then(|s| serde_json::from_str::<TransferSummary>(&s).ok());
+  from_file.unwrap_or_else(|| {
+    let count = |s: &str| rows.iter().filter(|r| r.status == s).count() as u64;
+    TransferSummary {
+      job_id: String::new(),
+      started_at: String::new(),
+      finished_at: String::new(),
+      started_at_utc: String::new(),
+      finished_at_utc: String::new(),
+      duration_ms: 0,
+      total_files: rows.len() as u64,
+      total_bytes: rows.iter().map(|r| r.bytes).sum(),
+      copied_files: count("copied"),
+      moved_files: count("moved"),
+      skipped_files: count("skipped"),
+      error_files: count("error"),
+      unverified_files: count("unverified"),
+      output_session_dir: session_dir.to_string_lossy().to_string(),
+      dry_run: false,
+      samples: vec![],
+    }
+  })
+}
+
+/// Row's destination relative to the session dir, as the recipient sees it on the drive.
+pub(crate) fn rel_dest(session_dir: &Path, row: &ManifestItem) -> String {
+  let dest = Path::new(&row.dest);
+  dest
+    .strip_prefix(session_dir)
+    .unwrap_or(dest)
+    .to_string_lossy()
+    .to_string()
+}
+
+/* ----------------------------------- HTML ----------------------------------- */
+
+fn render_html(session_dir: &Path, rows: &[ManifestItem], summary: &TransferSummary, opts: &ReportOptions) -> String {
+  let label = opts.label.clone().filter(|l| !l.trim().is_empty()).unwrap_or_else(|| {
+    session_dir
+      .file_name()
+      .map(|s| s.to_string_lossy().to_string())
+      .unwrap_or_default()
+  });
+  let operator = opts.operator.clone().filter(|o| !o.trim().is_empty()).unwrap_or_else(audit::current_user);
+  let algo = rows
+    .iter()
+    .find_map(|r| r.checksum_algo.clone())
+    .unwrap_or_else(|| "—".to_string());
+  let logo = STANDARD.encode(LOGO_PNG);
+
+  let mut table = String::new();
+  for (i, r) in rows.iter().enumerate() {
+    let status_class = match r.status.as_str() {
+      "copied" | "moved" => "ok",
+      "skipped" => "skip",
+      _ => "bad",
+    };
+    let status = match r.error.as_deref() {
+      Some(e) => format!("{} — {}", r.status, e),
+      None => r.status.clone(),
+    };
+    table.push_str(&format!(
+      "<tr><td class=\"n\">{}</td><td>{}</td><td class=\"n\">{}</td><td class=\"hash\">{}</td><td class=\"{status_class}\">{}</td></tr>\n",
+      i + 1,
+      esc(&rel_dest(session_dir, r)),
+      esc(&human_bytes(r.bytes)),
+      esc(r.checksum.as_deref().unwrap_or("")),
+      esc(&status),
+    ));
+  }
+
+  let fact = |k: &str, v: &str| format!("<div class=\"fact\"><span>{}</span><strong>{}</strong></div>", esc(k), esc(v));
+  let facts = [
+    fact("Files", &summary.total_files.to_string()),
+    fact("Total size", &human_bytes(summary.total_bytes)),
+    fact("Copied", &(summary.copied_files + summary.moved_files).to_string()),
+    fact("Skipped", &summary.skipped_files.to_string()),
+    fact("Errors", &summary.error_files.to_string()),
+    fact("Duration", &human_duration(summary.duration_ms)),
+    fact("Checksum", &algo),
+    fact("Operator", &operator),
+  ]
+  .join("\n");
+
+  format!(
+    r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font: 13px/1.45 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #1d2330; margin: 32px; }}
+  header {{ display: flex; align-items: center; gap: 16px; border-bottom: 2px solid #1d2330; padding-bottom: 12px; }}
+  header img {{ width: 48px; height: 48px; }}
+  h1 {{ font-size: 20px; margin: 0; }}
+  .sub {{ color: #667085; }}
+  .facts {{ display: grid; grid-template-columns: repeat(4, 1fr); gap: 8px; margin: 20px 0; }}
+  .fact {{ background: #f4f5f7; border-radius: 6px; padding: 8px 10px; display: flex; flex-direction: column; }}
+  .fact span {{ color: #667085; font-size: 11px; text-transform: uppercase; letter-spacing: .04em; }}
+  table {{ width: 100%; border-collapse: collapse; }}
+  th, td {{ text-align: left; padding: 4px 6px; border-bottom: 1px solid #e4e7ec; vertical-align: top; }}
+  th {{ font-size: 11px; text-transform: uppercase; color: #667085; }}
+  td.n {{ text-align: right; white-space: nowrap; }}
+  td.hash {{ font-family: Menlo, Consolas, monospace; font-size: 11px; word-break: break-all; }}
+  .ok {{ color: #067647; }} .skip {{ color: #667085; }} .bad {{ color: #b42318; }}
+  footer {{ margin-top: 24px; color: #667085; font-size: 11px; }}
+</style>
+</head>
+<body>
+<header>
+  <img src="data:image/png;base64,{logo}" alt="">
+  <div>
+    <h1>{title}</h1>
+    <div class="sub">Delivery report · {started} → {finished}</div>
+  </div>
+</header>
+<section class="facts">
+{facts}
+</section>
+<table>
+<thead><tr><th>#</th><th>File</th><th>Size</th><th>Checksum</th><th>Status</th></tr></thead>
+<tbody>
+{table}</tbody>
+</table>
+<footer>Generated {generated} by TransferPilot {version} · {session}</footer>
+</body>
+</html>
+"#,
+    title = esc(&label),
+    started = esc(&summary.started_at),
+    finished = esc(&summary.finished_at),
+    generated = esc(&chrono::Local::now().to_rfc3339()),
+    version = env!("CARGO_PKG_VERSION"),
+    session = esc(&session_dir.to_string_lossy()),
+  )
+}
+
+/* ------------------------------------ PDF ----------------------------------- */
+
+fn chromium_candidates() -> Vec<PathBuf> {
+  let mut v: Vec<PathBuf> = vec![];
+  if cfg!(target_os = "macos") {
+    v.push("/Applications/Google Chrome.app/Contents/MacOS/Google Chrome".into());
+    v.push("/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge".into());
+    v.push("/Applications/Chromium.app/Contents/MacOS/Chromium".into());
+  } else if cfg!(windows) {
+    v.push(r"C:\Program Files (x86)\Microsoft\Edge\Application\msedge.exe".into());
+    v.push(r"C:\Program Files\Google\Chrome\Application\chrome.exe".into());
+  } else {
+    for name in ["chromium", "chromium-browser", "google-chrome", "microsoft-edge"] {
+      v.push(name.into());
+    }
+  }
+  v
+}
+
+// We don't ship a PDF renderer; print the HTML with whatever converter the machine has.
+fn html_to_pdf(html: &Path, pdf: &Path) -> Result<(), String> {
+  let ok = |st: std::io::Result<std::process::ExitStatus>| matches!(st, Ok(s) if s.success()) && pdf.is_file();
+
+  if ok(Command::new("wkhtmltopdf").arg("--quiet").arg(html).arg(pdf).status()) {
+    return Ok(());
+  }
+  let url = format!("file://{}", html.to_string_lossy());
+  for browser in chromium_candidates() {
+    let st = Command::new(&browser)
+      .args(["--headless", "--disable-gpu", "--no-pdf-header-footer"])
+      .arg(format!("--print-to-pdf={}", pdf.to_string_lossy()))
+      .arg(&url)
+      .status();
+    if ok(st) {
+      return Ok(());
+    }
+  }
+  Err("no PDF converter found (install wkhtmltopdf or Chrome)".to_string())
+}
+
+/* --------------------------------- Generate --------------------------------- */
+
+/// Writes delivery_report.html (and optionally .pdf) into the session folder.
+pub fn generate(session_dir: &str, opts: &ReportOptions) -> Result<DeliveryReport, String> {
+  let dir = PathBuf::from(session_dir);
+  let rows = transfer::load_manifest_rows(&dir)?;
+  let summary = load_summary(&dir, &rows);
+
+  let html_path = dir.join(REPORT_HTML_FILE);
+  fs::write(&html_path, render_html(&dir, &rows, &summary, opts)).map_err(|e| format!("report write error: {e}"))?;
+
+  let (pdf_path, pdf_error) = if opts.pdf {
+    let pdf = dir.join(REPORT_PDF_FILE);
+    match html_to_pdf(&html_path, &pdf) {
+      Ok(_) => (Some(pdf.to_string_lossy().to_string()), None),
+      Err(e) => {
+        logging::warn(&format!("delivery report PDF: {e}"));
+        (None, Some(e))
+      }
+    }
+  } else {
+    (None, None)
+  };
+
+  Ok(DeliveryReport {
+    html_path: html_path.to_string_lossy().to_string(),
+    pdf_path,
+    pdf_error,
+  })
+}
+
+/* ---------------------------------- Hashdeep -------------------------------- */
+/* An audit file in hashdeep's known-hashes layout, so a delivery can be checked
+   with standard forensic tooling and without this app:
+     cd <session dir> && hashdeep -a -vv -l -r -k hashdeep_audit.txt .
+   Paths are relative to the session dir ("./..."), as `hashdeep -l` writes them.
+   Only copied files are listed, so the session's own bookkeeping files
+   (manifest.json, summary.json, ...) are reported as unknown; nothing else should be.
+   hashdeep only speaks md5/sha1/sha256/tiger/whirlpool, so rows verified with
+   another algorithm are hashed again from the destination. */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashdeepExport {
+  pub path: String,
+  pub algo: String,
+  pub files: u64,
+  // Rows whose recorded checksum couldn't be used and were hashed from the drive
+  pub rehashed: u64,
+}
+
+/// Writes hashdeep_audit.txt into the session dir. `algo` is "md5" or "sha256";
+/// by default the one the session was verified with, else sha256.
+pub fn export_hashdeep(session_dir: &str, algo: Option<String>) -> Result<HashdeepExport, String> {
+  let dir = PathBuf::from(session_dir);
+  let rows = transfer::load_manifest_rows(&dir)?;
+  let recorded = rows.iter().find_map(|r| r.checksum_algo.clone());
+  let algo = match algo.or_else(|| recorded.filter(|a| a == "md5" || a == "sha256")) {
+    Some(a) if a == "md5" => Algo::Md5,
+    Some(a) if a == "sha256" => Algo::Sha256,
+    None => Algo::Sha256,
+    Some(other) => return Err(format!("hashdeep can't use {other}; choose md5 or sha256")),
+  };
+
+  let mut out = format!(
+    "%%%% HASHDEEP-1.0\n%%%% size,{},filename\n## Invoked from: {}\n## $ exported by TransferPilot from manifest.json\n##\n",
+    algo.name(),
+    dir.display()
+  );
+  let watchdog = Watchdog::disabled();
+  let (mut files, mut rehashed) = (0u64, 0u64);
+  for r in rows.iter().filter(|r| r.status == "copied" || r.status == "moved") {
+    let digest = match r.checksum.as_deref().filter(|_| r.checksum_algo.as_deref() == Some(algo.name())) {
+      Some(d) => d.to_string(),
+      None => {
+        rehashed += 1;
+        checksum::hash_file(Path::new(&r.dest), algo, false, &watchdog)?
+      }
+    };
+    out.push_str(&format!("{},{digest},./{}\n", r.bytes, rel_dest(&dir, r)));
+    files += 1;
+  }
+  if files == 0 {
+    return Err("this session has no copied files".to_string());
+  }
+
+  let path = dir.join(HASHDEEP_FILE);
+  fs::write(&path, out).map_err(|e| format!("hashdeep write error: {e}"))?;
+  logging::info(&format!("hashdeep audit file for {session_dir}: {files} file(s), {rehashed} rehashed"));
+  Ok(HashdeepExport {
+    path: path.to_string_lossy().to_string(),
+    algo: algo.name().to_string(),
+    files,
+    rehashed,
+  })
+}
+
+/* ------------------------------------ CSV ----------------------------------- */
+/* manifest.csv, for recipients who open things in Excel. Written with a UTF-8
+   byte order mark and CRLF line ends, which is what Excel needs to read accents
+   and line breaks right. A cell starting with = + - or @ would run as a formula,
+   so those get a leading apostrophe. */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestExport {
+  pub path: String,
+  pub format: String,
+  pub rows: u64,
+}
+
+const CSV_HEADER: &[&str] = &[
+  "source",
+  "dest",
+  "bytes",
+  "status",
+  "checksum",
+  "checksum_algo",
+  "source_mtime",
+  "retries",
+  "copy_started_at",
+  "copy_finished_at",
+  "error",
+];
+
+fn csv_cell(s: &str) -> String {
+  let s = if s.starts_with(['=', '+', '-', '@']) { format!("'{s}") } else { s.to_string() };
+  if s.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s
+  }
+}
+
+fn manifest_csv(rows: &[ManifestItem]) -> String {
+  let mut out = String::from("\u{feff}");
+  out.push_str(&CSV_HEADER.join(","));
+  out.push_str("\r\n");
+  for r in rows {
+    let cells = [
+      r.source.clone(),
+      r.dest.clone(),
+      r.bytes.to_string(),
+      r.status.clone(),
+      r.checksum.clone().unwrap_or_default(),
+      r.checksum_algo.clone().unwrap_or_default(),
+      r.source_meta.mtime.clone().unwrap_or_default(),
+      r.retries.to_string(),
+      r.copy_started_at.clone().unwrap_or_default(),
+      r.copy_finished_at.clone().unwrap_or_default(),
+      r.error.clone().unwrap_or_default(),
+    ];
+    out.push_str(&cells.iter().map(|c| csv_cell(c)).collect::<Vec<_>>().join(","));
+    out.push_str("\r\n");
+  }
+  out
+}
+
+/// Writes manifest.csv beside manifest.json.
+pub(crate) fn write_manifest_csv(session_dir: &Path, rows: &[ManifestItem]) -> Result<PathBuf, String> {
+  let path = session_dir.join(MANIFEST_CSV_FILE);
+  fs::write(&path, manifest_csv(rows)).map_err(|e| format!("manifest csv write error: {e}"))?;
+  Ok(path)
+}
+
+/// Writes the session's manifest in `format` ("csv") into the session folder.
+pub fn export_manifest(session_dir: &str, format: &str) -> Result<ManifestExport, String> {
+  let dir = PathBuf::from(session_dir);
+  let rows = transfer::load_manifest_rows(&dir)?;
+  let path = match format {
+    "csv" => write_manifest_csv(&dir, &rows)?,
+    other => return Err(format!("unknown manifest format: {other}")),
+  };
+  logging::info(&format!("manifest {format} for {session_dir}: {} row(s)", rows.len()));
+  Ok(ManifestExport {
+    path: path.to_string_lossy().to_string(),
+    format: format.to_string(),
+    rows: rows.len() as u64,
+  })
+}
+
+/* --------------------------------- Clipboard -------------------------------- */
+
+/// Paste-ready session details: "text" or "markdown" summary, or "checksums"
+/// (`<digest>  <path>` lines, as sha256sum/md5sum print them).
+pub fn clipboard_text(session_dir: &str, format: &str) -> Result<String, String> {
+  let dir = PathBuf::from(session_dir);
+  let rows = transfer::load_manifest_rows(&dir)?;
+  let summary = load_summary(&dir, &rows);
+  let name = dir
+    .file_name()
+    .map(|s| s.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let algo = rows.iter().find_map(|r| r.checksum_algo.clone());
+
+  let lines: Vec<(String, String)> = vec![
+    ("Files".to_string(), summary.total_files.to_string()),
+    ("Total size".to_string(), human_bytes(summary.total_bytes)),
+    ("Copied".to_string(), (summary.copied_files + summary.moved_files).to_string()),
+    ("Skipped".to_string(), summary.skipped_files.to_string()),
+    ("Errors".to_string(), summary.error_files.to_string()),
+    ("Duration".to_string(), human_duration(summary.duration_ms)),
+    ("Verified with".to_string(), algo.clone().unwrap_or_else(|| "size/none".to_string())),
+    ("Location".to_string(), summary.output_session_dir.clone()),
+  ];
+  let failed: Vec<&ManifestItem> = rows.iter().filter(|r| r.status == "error").collect();
+
+  match format {
+    "text" => {
+      let mut out = format!("Transfer {name} ({} → {})\n", summary.started_at, summary.finished_at);
+      for (k, v) in &lines {
+        out.push_str(&format!("{k}: {v}\n"));
+      }
+      if !failed.is_empty() {
+        out.push_str("\nFailed:\n");
+        for r in &failed {
+          out.push_str(&format!("  {} — {}\n", rel_dest(&dir, r), r.error.as_deref().unwrap_or("error")));
+        }
+      }
+      Ok(out)
+    }
+    "markdown" => {
+      let mut out = format!("**Transfer {name}** ({} → {})\n\n", summary.started_at, summary.finished_at);
+      out.push_str("| | |\n|---|---|\n");
+      for (k, v) in &lines {
+        out.push_str(&format!("| {k} | `{v}` |\n"));
+      }
+      if !failed.is_empty() {
+        out.push_str("\n**Failed**\n\n");
+        for r in &failed {
+          out.push_str(&format!("- `{}` — {}\n", rel_dest(&dir, r), r.error.as_deref().unwrap_or("error")));
+        }
+      }
+      Ok(out)
+    }
+    "checksums" => {
+      let mut out = String::new();
+      for r in rows.iter().filter(|r| r.checksum.is_some()) {
+        out.push_str(&format!("{}  {}\n", r.checksum.as_deref().unwrap_or(""), rel_dest(&dir, r)));
+      }
+      if out.is_empty() {
+        return Err("this session has no recorded checksums".to_string());
+      }
+      Ok(out)
+    }
+    other => Err(format!("unknown summary format: {other}")),
+  }
+}
@@ -0,0 +1,179 @@
+use serde::{Deserialize, Serialize};
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+  time::{Duration, Instant, UNIX_EPOCH},
+};
+
+use crate::logging;
+
+/* ------------------------------- Session state ------------------------------ */
+/* session.state.json in the session dir says where a run got to: the files it
+   was going to copy, the ones already done, and how far the in-flight file got.
+   The journal already records finished files; what it can't record is a half
+   copied 40 GB clip. Every CHECKPOINT_BYTES the copy syncs the partial file and
+   saves its offset here, so resume_session can continue it instead of starting
+   that file over. The file goes away once a run gets through its whole queue. */
+
+pub const STATE_FILE: &str = "session.state.json";
+
+const STATE_VERSION: u32 = 1;
+
+// Partial copies are synced and their offset saved this often
+pub const CHECKPOINT_BYTES: u64 = 64 * 1024 * 1024;
+
+// Finished files are saved at most this often (the journal has them anyway)
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialFile {
+  pub source: String,
+  pub dest: String,
+  // Bytes of dest known to be on disk
+  pub offset: u64,
+  // The source as it was when copying started; a changed source is copied afresh
+  pub source_bytes: u64,
+  pub source_modified: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+  pub version: u32,
+  pub updated_at: String,
+  // Source paths this run set out to copy, in order
+  pub queue: Vec<String>,
+  // Source paths finished (copied, moved or skipped)
+  pub completed: Vec<String>,
+  pub partial: Option<PartialFile>,
+}
+
+fn modified_secs(meta: &fs::Metadata) -> Option<u64> {
+  meta
+    .modified()
+    .ok()
+    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+    .map(|d| d.as_secs())
+}
+
+impl PartialFile {
+  /// Offset to continue `src` -> `dst` from, if this checkpoint is for that pair,
+  /// the source hasn't changed since, and dest still holds at least that much.
+  pub fn resume_offset(&self, src: &Path, dst: &Path) -> Option<u64> {
+    if self.offset == 0 || self.source != src.to_string_lossy() || self.dest != dst.to_string_lossy() {
+      return None;
+    }
+    let meta = fs::metadata(src).ok()?;
+    if meta.len() != self.source_bytes || modified_secs(&meta) != self.source_modified {
+      return None;
+    }
+    let written = fs::metadata(dst).ok()?.len();
+    (written >= self.offset && self.offset <= self.source_bytes).then_some(self.offset)
+  }
+}
+
+pub fn load(session_dir: &Path) -> Option<SessionState> {
+  let s = fs::read_to_string(session_dir.join(STATE_FILE)).ok()?;
+  serde_json::from_str(&s).ok()
+}
+
+/* ---------------------------------- Writer ---------------------------------- */
+
+pub struct StateFile {
+  path: PathBuf,
+  state: Mutex<SessionState>,
+  last_save: Mutex<Instant>,
+}
+
+impl StateFile {
+  pub fn create(session_dir: &Path, queue: Vec<String>, completed: Vec<String>) -> Self {
+    let f = StateFile {
+      path: session_dir.join(STATE_FILE),
+      state: Mutex::new(SessionState {
+        version: STATE_VERSION,
+        updated_at: String::new(),
+        queue,
+        completed,
+        // Kept until its file is picked up again, or the run moves past it
+        partial: load(session_dir).and_then(|s| s.partial),
+      }),
+      last_save: Mutex::new(Instant::now()),
+    };
+    f.save();
+    f
+  }
+
+  /// Follows the session to a new path (destination remounted elsewhere).
+  pub fn move_to(&mut self, session_dir: &Path) {
+    self.path = session_dir.join(STATE_FILE);
+    self.save();
+  }
+
+  /// The checkpoint left by the interrupted run, if any.
+  pub fn partial(&self) -> Option<PartialFile> {
+    self.state.lock().ok().and_then(|g| g.partial.clone())
+  }
+
+  // Written to a temp file and renamed, so a crash mid-save keeps the old state
+  fn save(&self) {
+    let json = match self.state.lock() {
+      Ok(mut g) => {
+        g.updated_at = chrono::Local::now().to_rfc3339();
+        serde_json::to_string_pretty(&*g)
+      }
+      Err(_) => return,
+    };
+    let tmp = self.path.with_extension("json.tmp");
+    let res = json
+      .map_err(|e| e.to_string())
+      .and_then(|json| fs::write(&tmp, json).map_err(|e| e.to_string()))
+      .and_then(|_| fs::rename(&tmp, &self.path).map_err(|e| e.to_string()));
+    if let Err(e) = res {
+      logging::warn(&format!("session state write error: {e}"));
+    }
+    if let Ok(mut t) = self.last_save.lock() {
+      *t = Instant::now();
+    }
+  }
+
+  /// Records that the first `offset` bytes of `dst` are on disk (the caller has synced them).
+  pub fn checkpoint(&self, src: &Path, dst: &Path, offset: u64) {
+    let meta = fs::metadata(src).ok();
+    if let Ok(mut g) = self.state.lock() {
+      g.partial = Some(PartialFile {
+        source: src.to_string_lossy().to_string(),
+        dest: dst.to_string_lossy().to_string(),
+        offset,
+        source_bytes: meta.as_ref().map(|m| m.len()).unwrap_or(0),
+        source_modified: meta.as_ref().and_then(modified_secs),
+      });
+    }
+    self.save();
+  }
+
+  /// Marks `source` finished (whatever the outcome) and drops its checkpoint.
+  pub fn complete(&self, source: &str) {
+    let Ok(mut g) = self.state.lock() else {
+      return;
+    };
+    if g.partial.as_ref().is_some_and(|p| p.source == source) {
+      g.partial = None;
+    }
+    g.completed.push(source.to_string());
+    drop(g);
+    let due = self.last_save.lock().map(|t| t.elapsed() >= SAVE_INTERVAL).unwrap_or(true);
+    if due {
+      self.save();
+    }
+  }
+
+  /// Saves the final state, or removes the file when the whole queue was handled.
+  pub fn finish(self, all_handled: bool) {
+    if all_handled {
+      let _ = fs::remove_file(&self.path);
+    } else {
+      self.save();
+    }
+  }
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::{completion, journal, report, transfer, TransferSummary};
+
+/* --------------------------------- Discovery -------------------------------- */
+
+// Transfers/ may sit under a dest_subdir (e.g. <dest>/Weddings/2025/Transfers)
+const TRANSFERS_SEARCH_DEPTH: usize = 4;
+
+/// Every Transfers/<day>/<run> session dir on a destination, oldest first. A dir
+/// counts as a session once it has a manifest, a journal or a saved selection.
+pub fn find_session_dirs(dest: &Path) -> Vec<PathBuf> {
+  let mut out: Vec<PathBuf> = vec![];
+  let transfers_roots = WalkDir::new(dest)
+    .max_depth(TRANSFERS_SEARCH_DEPTH)
+    .into_iter()
+    .filter_entry(|e| e.file_type().is_dir())
+    .filter_map(|e| e.ok())
+    .filter(|e| e.file_name() == "Transfers");
+
+  for root in transfers_roots {
+    for e in WalkDir::new(root.path())
+      .min_depth(2)
+      .max_depth(2)
+      .sort_by_file_name()
+      .into_iter()
+      .filter_map(|e| e.ok())
+    {
+      let dir = e.path();
+      if e.file_type().is_dir()
+        && ["manifest.json", journal::JOURNAL_FILE, transfer::SELECTION_FILE]
+          .iter()
+          .any(|f| dir.join(f).exists())
+      {
+        out.push(dir.to_path_buf());
+      }
+    }
+  }
+  out
+}
+
+/* ---------------------------------- Listing --------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+  pub session_dir: String,
+  // Transfers/<day>/<run>
+  pub day: String,
+  pub run: String,
+  // From summary.json, else counted from the manifest (or journal); no samples
+  pub summary: TransferSummary,
+  // Finished: manifest.json written and no journal left to resume from
+  pub complete: bool,
+  // A running job is writing into it
+  pub active: bool,
+  // The manifest couldn't be read; summary is then empty
+  pub error: Option<String>,
+}
+
+fn name_of(p: Option<&Path>) -> String {
+  p.and_then(|p| p.file_name())
+    .map(|n| n.to_string_lossy().to_string())
+    .unwrap_or_default()
+}
+
+/// Every session on a destination with its summary, newest first.
+pub fn list_sessions(mount_point: &str, active: &[PathBuf]) -> Result<Vec<SessionInfo>, String> {
+  let dest = Path::new(mount_point);
+  if !dest.is_dir() {
+    return Err(format!("{mount_point} is not a folder"));
+  }
+  let mut out: Vec<SessionInfo> = find_session_dirs(dest)
+    .into_iter()
+    .map(|dir| {
+      let (rows, error) = match transfer::load_manifest_rows(&dir) {
+        Ok(rows) => (rows, None),
+        Err(e) => (vec![], Some(e)),
+      };
+      let mut summary = report::load_summary(&dir, &rows);
+      summary.samples.clear();
+      SessionInfo {
+        session_dir: dir.to_string_lossy().to_string(),
+        day: name_of(dir.parent()),
+        run: name_of(Some(&dir)),
+        summary,
+        complete: dir.join("manifest.json").exists() && !dir.join(journal::JOURNAL_FILE).exists(),
+        active: active.iter().any(|a| a == &dir),
+        error,
+      }
+    })
+    .collect();
+  out.reverse();
+  Ok(out)
+}
+
+/// Shows a session folder in Finder / Explorer / the file manager.
+pub fn open_session_dir(path: &str) -> Result<(), String> {
+  let dir = Path::new(path);
+  if !dir.is_dir() {
+    return Err(format!("{path} is not a folder"));
+  }
+  completion::reveal(dir)
+}
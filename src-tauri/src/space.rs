@@ -0,0 +1,173 @@
+use std::{collections::HashMap, process::Command, sync::Mutex};
+use tauri::{AppHandle, Manager};
+
+use crate::{device, logging, transfer::TransferOptions};
+
+/* ---------------------------------- Query ----------------------------------- */
+
+/// (total, available) bytes on the volume holding `mount_point`, from `df -k`.
+pub fn space_for_mount(mount_point: &str) -> Result<(u64, u64), String> {
+  let out = Command::new("df")
+    .arg("-k")
+    .arg(mount_point)
+    .output()
+    .map_err(|e| format!("failed to run df: {e}"))?;
+
+  let s = String::from_utf8_lossy(&out.stdout);
+  let mut lines = s.lines();
+  lines.next(); // header
+  if let Some(line) = lines.next() {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() >= 4 {
+      let total_kb = parts[1].parse::<u64>().unwrap_or(0);
+      let avail_kb = parts[3].parse::<u64>().unwrap_or(0);
+      return Ok((total_kb * 1024, avail_kb * 1024));
+    }
+  }
+  Ok((0, 0))
+}
+
+/* ---------------------------------- Reserve --------------------------------- */
+
+/// Space to leave free on a volume of `total_bytes`: the larger of the fixed and
+/// percentage reserves. Filesystems need headroom for metadata and journals, so
+/// a copy that would fill the drive exactly still fails.
+pub fn reserve_bytes(options: &TransferOptions, total_bytes: u64) -> u64 {
+  let pct = (total_bytes as f64 * options.reserve_percent.clamp(0.0, 100.0) / 100.0) as u64;
+  options.reserve_bytes.max(pct)
+}
+
+/* -------------------------------- Reservations ------------------------------ */
+/* Bytes running jobs still intend to write, per destination volume. Two jobs
+   aimed at the same drive can each fit on their own and overflow it together;
+   preflight and every SpaceMonitor subtract what the other jobs have claimed. */
+
+#[derive(Default)]
+pub struct Reservations(Mutex<HashMap<String, HashMap<String, u64>>>);
+
+/// Reservation key for `dest`: jobs writing to different folders of one drive
+/// share its free space.
+pub fn volume_key(dest: &str) -> String {
+  device::mount_point_for(dest).unwrap_or_else(|| dest.to_string())
+}
+
+impl Reservations {
+  pub fn reserve(&self, volume: &str, job_id: &str, bytes: u64) {
+    if let Ok(mut g) = self.0.lock() {
+      let held = g.entry(volume.to_string()).or_default().entry(job_id.to_string()).or_insert(0);
+      *held = held.saturating_add(bytes);
+    }
+  }
+
+  pub fn release(&self, volume: &str, job_id: &str, bytes: u64) {
+    if let Ok(mut g) = self.0.lock() {
+      if let Some(jobs) = g.get_mut(volume) {
+        if let Some(held) = jobs.get_mut(job_id) {
+          *held = held.saturating_sub(bytes);
+          if *held == 0 {
+            jobs.remove(job_id);
+          }
+        }
+        if jobs.is_empty() {
+          g.remove(volume);
+        }
+      }
+    }
+  }
+
+  /// Reserved on `volume` by every job except `except_job` (all jobs for None).
+  pub fn held(&self, volume: &str, except_job: Option<&str>) -> u64 {
+    let Ok(g) = self.0.lock() else {
+      return 0;
+    };
+    g.get(volume)
+      .map(|jobs| {
+        jobs
+          .iter()
+          .filter(|(id, _)| Some(id.as_str()) != except_job)
+          .map(|(_, b)| *b)
+          .sum()
+      })
+      .unwrap_or(0)
+  }
+}
+
+/* ---------------------------------- Monitor --------------------------------- */
+
+/// Tracks free space on the destination while copying. Works from an estimate
+/// (starting free space minus what we've written) and only re-runs `df` when the
+/// estimate says a file may not fit. Holds a reservation for the bytes the job has
+/// yet to write, released as they land and when the monitor is dropped.
+pub struct SpaceMonitor {
+  app: AppHandle,
+  job_id: String,
+  mount_point: String,
+  volume: String,
+  reserve: u64,
+  avail_estimate: u64,
+  // Still reserved by this monitor
+  outstanding: u64,
+}
+
+impl SpaceMonitor {
+  pub fn new(app: &AppHandle, job_id: &str, mount_point: &str, options: &TransferOptions, planned_bytes: u64) -> Self {
+    let (total, avail) = space_for_mount(mount_point).unwrap_or((0, u64::MAX));
+    let reserve = reserve_bytes(options, total);
+    logging::debug(&format!("space monitor on {mount_point}: {avail} bytes free, {reserve} reserved"));
+    let volume = volume_key(mount_point);
+    app.state::<Reservations>().reserve(&volume, job_id, planned_bytes);
+    SpaceMonitor {
+      app: app.clone(),
+      job_id: job_id.to_string(),
+      mount_point: mount_point.to_string(),
+      volume,
+      reserve,
+      avail_estimate: avail,
+      outstanding: planned_bytes,
+    }
+  }
+
+  fn others(&self) -> u64 {
+    self.app.state::<Reservations>().held(&self.volume, Some(&self.job_id))
+  }
+
+  /// Ok if `bytes` more can be written without eating into the reserve or into
+  /// space other running jobs have claimed on the same drive.
+  pub fn check(&mut self, bytes: u64) -> Result<(), String> {
+    let needed = bytes.saturating_add(self.reserve).saturating_add(self.others());
+    if self.avail_estimate >= needed {
+      return Ok(());
+    }
+    // Something else may have freed space (or our estimate was pessimistic)
+    if let Ok((_, avail)) = space_for_mount(&self.mount_point) {
+      self.avail_estimate = avail;
+    }
+    if self.avail_estimate >= needed {
+      Ok(())
+    } else {
+      Err(format!(
+        "not enough free space on {}: {bytes} bytes needed, {} free with {} kept in reserve and {} claimed by other jobs",
+        self.mount_point,
+        self.avail_estimate,
+        self.reserve,
+        self.others()
+      ))
+    }
+  }
+
+  pub fn consumed(&mut self, bytes: u64) {
+    self.avail_estimate = self.avail_estimate.saturating_sub(bytes);
+    let n = bytes.min(self.outstanding);
+    self.outstanding -= n;
+    self.app.state::<Reservations>().release(&self.volume, &self.job_id, n);
+  }
+}
+
+impl Drop for SpaceMonitor {
+  fn drop(&mut self) {
+    self
+      .app
+      .state::<Reservations>()
+      .release(&self.volume, &self.job_id, self.outstanding);
+  }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/* ----------------------------------- Speed ---------------------------------- */
+/* Speed and time remaining for the progress payload. Each progress emit feeds
+   bytes_done in; the rate is an exponentially weighted moving average whose
+   weight depends on the time since the last sample, so it settles the same way
+   whatever progress_interval_ms is. A run of small files or a burst into the
+   drive's cache moves it a little instead of swinging the ETA around. */
+
+// Time constant: a sample this old still counts for about a third
+const TAU: Duration = Duration::from_secs(5);
+
+// Too short to measure anything (two emits back to back)
+const MIN_SAMPLE: Duration = Duration::from_millis(50);
+
+/* --------------------------------- Samples ---------------------------------- */
+/* Alongside the average, raw throughput about once a second for a speed-over-time
+   chart, where a drive that stalls now and then shows up as dips to zero. An hour
+   of them at most: past that, neighbours are merged and the step doubles. A
+   sample covers the time since the one before it, so a stall that held up
+   progress emits entirely comes out as one long slow sample. */
+
+const SAMPLE_STEP: Duration = Duration::from_secs(1);
+const MAX_SAMPLES: usize = 3600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThroughputSample {
+  // End of the sample, since the job started
+  pub t_ms: u64,
+  pub bytes_per_sec: u64,
+}
+
+#[derive(Debug)]
+pub struct SpeedMeter {
+  started: Instant,
+  // Previous sample; None after a pause
+  last: Option<(Instant, u64)>,
+  // bytes_total the rate was measured against
+  total: u64,
+  rate: f64,
+  // Start of the sample being collected
+  window: Option<(Instant, u64)>,
+  step: Duration,
+  samples: Vec<ThroughputSample>,
+}
+
+impl Default for SpeedMeter {
+  fn default() -> Self {
+    SpeedMeter {
+      started: Instant::now(),
+      last: None,
+      total: 0,
+      rate: 0.0,
+      window: None,
+      step: SAMPLE_STEP,
+      samples: vec![],
+    }
+  }
+}
+
+impl SpeedMeter {
+  /// Feeds one progress sample in; returns (bytes_per_sec, eta_seconds, elapsed_ms).
+  pub fn sample(&mut self, phase: &str, bytes_done: u64, bytes_total: u64) -> (u64, Option<u64>, u64) {
+    let now = Instant::now();
+    let elapsed_ms = now.duration_since(self.started).as_millis() as u64;
+
+    // A pause isn't slowness: measure again from where it resumes
+    if phase != "copying" && phase != "verifying" {
+      self.last = None;
+      self.window = None;
+      return (self.rate as u64, None, elapsed_ms);
+    }
+    // The batch verify pass counts its own bytes from zero, at a different speed.
+    // (Verifying each file inline stays in the copy's count, and its time belongs there.)
+    if bytes_total != self.total {
+      self.total = bytes_total;
+      self.rate = 0.0;
+      self.last = Some((now, bytes_done));
+      self.window = Some((now, bytes_done));
+      return (0, None, elapsed_ms);
+    }
+
+    match self.last {
+      // A retry rewinds bytes_done to the start of the file
+      Some((_, prev)) if bytes_done < prev => self.last = Some((now, bytes_done)),
+      Some((at, prev)) => {
+        let dt = now.duration_since(at);
+        if dt >= MIN_SAMPLE {
+          let instant = (bytes_done - prev) as f64 / dt.as_secs_f64();
+          // The first real sample seeds the average instead of dragging it up from 0
+          self.rate = if self.rate <= 0.0 {
+            instant
+          } else {
+            let alpha = 1.0 - (-dt.as_secs_f64() / TAU.as_secs_f64()).exp();
+            self.rate + alpha * (instant - self.rate)
+          };
+          self.last = Some((now, bytes_done));
+        }
+      }
+      None => self.last = Some((now, bytes_done)),
+    }
+    self.record(now, bytes_done);
+
+    let remaining = bytes_total.saturating_sub(bytes_done);
+    let eta = if remaining == 0 {
+      Some(0)
+    } else if self.rate >= 1.0 {
+      Some((remaining as f64 / self.rate).ceil() as u64)
+    } else {
+      None
+    };
+    (self.rate as u64, eta, elapsed_ms)
+  }
+
+  fn record(&mut self, now: Instant, bytes_done: u64) {
+    match self.window {
+      Some((at, from)) if bytes_done >= from => {
+        let dt = now.duration_since(at);
+        if dt < self.step {
+          return;
+        }
+        self.samples.push(ThroughputSample {
+          t_ms: now.duration_since(self.started).as_millis() as u64,
+          bytes_per_sec: ((bytes_done - from) as f64 / dt.as_secs_f64()) as u64,
+        });
+        self.window = Some((now, bytes_done));
+        if self.samples.len() >= MAX_SAMPLES {
+          self.samples = self
+            .samples
+            .chunks(2)
+            .map(|pair| ThroughputSample {
+              t_ms: pair[pair.len() - 1].t_ms,
+              bytes_per_sec: pair.iter().map(|p| p.bytes_per_sec).sum::<u64>() / pair.len() as u64,
+            })
+            .collect();
+          self.step *= 2;
+        }
+      }
+      // First sample, or a retry rewound bytes_done
+      _ => self.window = Some((now, bytes_done)),
+    }
+  }
+
+  pub fn samples(&self) -> Vec<ThroughputSample> {
+    self.samples.clone()
+  }
+}
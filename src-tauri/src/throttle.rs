@@ -0,0 +1,81 @@
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex, OnceLock,
+  },
+  thread,
+  time::{Duration, Instant},
+};
+
+/* -------------------------------- Rate limiter ------------------------------- */
+
+/// Token bucket shared by everything reading from (or writing to) one device.
+/// `consume` blocks the caller just long enough to keep the combined rate at or
+/// under the cap; a rate of 0 means unlimited.
+pub struct RateLimiter {
+  bytes_per_sec: AtomicU64,
+  // (available tokens, last refill); tokens go negative to carry a debt
+  bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+  pub fn new(bytes_per_sec: u64) -> Self {
+    RateLimiter {
+      bytes_per_sec: AtomicU64::new(bytes_per_sec),
+      bucket: Mutex::new((bytes_per_sec as f64, Instant::now())),
+    }
+  }
+
+  pub fn set_rate(&self, bytes_per_sec: u64) {
+    self.bytes_per_sec.store(bytes_per_sec, Ordering::SeqCst);
+  }
+
+  pub fn consume(&self, n: u64) {
+    let rate = self.bytes_per_sec.load(Ordering::SeqCst);
+    if rate == 0 {
+      return;
+    }
+
+    let wait = {
+      let Ok(mut b) = self.bucket.lock() else {
+        return;
+      };
+      let now = Instant::now();
+      let refill = now.duration_since(b.1).as_secs_f64() * rate as f64;
+      // At most one second of burst
+      b.0 = (b.0 + refill).min(rate as f64) - n as f64;
+      b.1 = now;
+      if b.0 < 0.0 {
+        Duration::from_secs_f64(-b.0 / rate as f64)
+      } else {
+        Duration::ZERO
+      }
+    };
+
+    if !wait.is_zero() {
+      thread::sleep(wait);
+    }
+  }
+}
+
+/* --------------------------------- Registry --------------------------------- */
+
+static DEVICE_LIMITERS: OnceLock<Mutex<HashMap<String, Arc<RateLimiter>>>> = OnceLock::new();
+
+/// The limiter for a device (keyed by mount point), shared across all running
+/// jobs so several cards offloading at once can't starve each other. The most
+/// recent caller's rate wins.
+pub fn device_limiter(device: &str, bytes_per_sec: u64) -> Arc<RateLimiter> {
+  let map = DEVICE_LIMITERS.get_or_init(|| Mutex::new(HashMap::new()));
+  let mut g = match map.lock() {
+    Ok(g) => g,
+    Err(p) => p.into_inner(),
+  };
+  let limiter = g
+    .entry(device.to_string())
+    .or_insert_with(|| Arc::new(RateLimiter::new(bytes_per_sec)))
+    .clone();
+  limiter.set_rate(bytes_per_sec);
+  limiter
+}
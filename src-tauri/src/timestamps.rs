@@ -0,0 +1,56 @@
+use std::{fs, time::SystemTime};
+
+use crate::{netfs, transfer::TransferOptions};
+
+/* -------------------------------- Resolution -------------------------------- */
+/* FAT keeps mtimes to 2 seconds and HFS+ to 1, so a file copied onto (or off) one
+   of those never has quite the same mtime as its source. Comparisons allow for the
+   coarser side of the pair. */
+
+/// mtime granularity of a filesystem type, in milliseconds.
+pub fn resolution_ms(fs_type: &str) -> u64 {
+  match fs_type {
+    "msdos" | "vfat" | "fat" | "fat32" | "exfat" | "fuseblk.exfat" => 2000,
+    "hfs" | "hfsplus" | "smbfs" | "cifs" => 1000,
+    _ => 1,
+  }
+}
+
+/// Tolerance for comparing mtimes between `sources` and `dest`: the configured
+/// value, or else the coarsest resolution among the filesystems involved.
+pub fn tolerance_ms<'a>(options: &TransferOptions, sources: impl Iterator<Item = &'a str>, dest: &str) -> u64 {
+  if let Some(ms) = options.mtime_tolerance_ms {
+    return ms;
+  }
+  std::iter::once(dest)
+    .chain(sources)
+    .filter_map(netfs::fs_type_for)
+    .map(|t| resolution_ms(&t))
+    .max()
+    .unwrap_or(1)
+}
+
+/* -------------------------------- Comparison -------------------------------- */
+
+fn mtime(meta: &fs::Metadata) -> Option<SystemTime> {
+  meta.modified().ok()
+}
+
+/// True when `src` was modified more than `tol_ms` after `dst`.
+pub fn is_newer(src: &fs::Metadata, dst: &fs::Metadata, tol_ms: u64) -> bool {
+  match (mtime(src), mtime(dst)) {
+    (Some(s), Some(d)) => s.duration_since(d).map(|diff| diff.as_millis() > tol_ms as u128).unwrap_or(false),
+    // Can't tell; treat as changed so nothing is silently left stale
+    _ => true,
+  }
+}
+
+/// Conflict decision for an existing destination under "newer" / "sync":
+/// true = replace it, false = leave it and skip the file.
+pub fn should_replace(policy: &str, src: &fs::Metadata, dst: &fs::Metadata, tol_ms: u64) -> bool {
+  match policy {
+    "newer" => is_newer(src, dst, tol_ms),
+    // sync: same size and not newer means it's already there
+    _ => src.len() != dst.len() || is_newer(src, dst, tol_ms),
+  }
+}
@@ -1,20 +1,44 @@
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use std::{
-  collections::HashMap,
+  collections::{HashMap, HashSet, VecDeque},
   fs,
-  io::{Read, Write},
+  io::{Read, Seek, SeekFrom, Write},
   path::{Path, PathBuf},
   sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc, Mutex,
   },
+  thread,
   time::{Duration, Instant},
 };
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Manager};
 use walkdir::WalkDir;
 
-use crate::{PickedItem, Preflight, TransferSummary};
+use crate::{
+  adaptive::AdaptiveLimit,
+  audit,
+  bursts,
+  completion,
+  camera_hashes::{self, CameraHashes},
+  checksum::{self, Algo},
+  cloud,
+  device,
+  durability::{self, Policy},
+  elevate, erase, errors,
+  events::{self, FileErrorEvent, ManifestRowEvent, OffloadItemEvent, PlanStaleEvent, TransferProgress},
+  filters::{Excluded, Filters},
+  fscompat, history, history_db,
+  jobs::{JobControl, PreflightControl},
+  journal::{self, Journal},
+  logging, naming, netfs, plan, preserve, quota,
+  session_state::{self, StateFile},
+  space::{self, SpaceMonitor},
+  speed::ThroughputSample,
+  throttle::{self, RateLimiter},
+  timestamps,
+  watchdog::{self, Watchdog},
+  PickedItem, Preflight, TransferSummary,
+};
 
 /* ----------------------------------- Types ---------------------------------- */
 
@@ -27,29 +51,249 @@ pub struct QueueItem {
   pub file_count: Option<u64>,
 }
 
+// Per-file retries for errors that may not happen twice (see errors::is_retryable)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+  // Tries per file, the first one included (1 = never retry)
+  pub max_attempts: u32,
+  // Wait before the first retry; doubles for each one after
+  pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    RetryPolicy {
+      max_attempts: 1,
+      backoff_ms: 1000,
+    }
+  }
+}
+
+impl RetryPolicy {
+  // Wait before retry `attempt` (1-based), capped at a minute
+  fn delay(&self, attempt: u32) -> Duration {
+    let ms = self.backoff_ms.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    Duration::from_millis(ms.min(60_000))
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransferOptions {
+  // Verbose engine logging for this transfer only (set from the profile's debug flag)
+  pub debug: bool,
+  // Seconds without any bytes moving before `transfer://stalled` fires (0 = off)
+  pub stall_timeout_secs: u64,
+  // Processing order: "path" (sorted by source path) | "scan" (as walked) | "locality" (grouped by directory, then on-disk order)
+  pub ordering: String,
+  // "inline" (verify each file right after copying) | "batch" (copy all, then verify in one pass)
+  pub verify_strategy: String,
+  // Concurrent hashing threads for the batch verify pass (0 = auto by CPU count and drive type)
+  pub verify_workers: usize,
+  // Read back from the media rather than the OS page cache when verifying
+  pub verify_bypass_cache: bool,
+  // Files copied at once (1 = one after another); only small files are spread over workers
+  pub concurrency: u8,
+  // Sessions go under <dest>/<dest_subdir>/Transfers/ (e.g. "Weddings/2025")
+  pub dest_subdir: Option<String>,
+  // Prepended to every copied file name (e.g. "A_" for card A)
+  pub rename_prefix: Option<String>,
+  // Value of the {project} token
+  pub project: Option<String>,
+  // Directory inside the session for each file, e.g. "{project}/{reel}" (see naming.rs);
+  // None keeps the Files/ + Folders/ layout
+  pub layout_template: Option<String>,
+  // New file name without extension, e.g. "{reel}_{clip}_{stem}"
+  pub rename_template: Option<String>,
+  // File audio under Music/<Artist>/<Album>/ by its ID3/Vorbis tags
+  pub organize_audio: bool,
+  // Stills shot in rapid bursts: "off" | "folder" (own subfolder) | "prefix" (shared name prefix)
+  pub burst_grouping: String,
+  // Read cap per source device in bytes/sec, shared by every job on that device (0 = no cap)
+  pub device_bytes_per_sec: u64,
+  // After a run that reaches "done": "none" | "reveal" (open the session folder) | "hook" | "eject"
+  pub on_complete: String,
+  // Shell command for on_complete = "hook"; gets TRANSFERPILOT_SESSION_DIR etc. in its env
+  pub on_complete_hook: Option<String>,
+  // Eject the destination after a "done" run, but only if no file failed
+  pub auto_eject: bool,
+  // When copies are fsynced: "per_file" | "per_batch" | "end_of_session" | "none" (see durability.rs)
+  pub durability: String,
+  // Free space to leave on the destination: the larger of these two (see space.rs)
+  pub reserve_bytes: u64,
+  pub reserve_percent: f64,
+  // On SMB/NFS/WebDAV destinations: bigger buffers, batched fsyncs, retry network blips (see netfs.rs)
+  pub network_tuning: bool,
+  // Slack when comparing mtimes for the "newer"/"sync" conflict policies; None picks
+  // it from the filesystems involved (2000 on FAT/exFAT, see timestamps.rs)
+  pub mtime_tolerance_ms: Option<u64>,
+  // Session folder names: "local" (HHMMSS) | "utc" (HHMMSSZ) | "offset" (HHMMSS+hhmm)
+  pub session_naming: String,
+  // Every folder pick copies its contents without the top-level folder name
+  pub contents_only: bool,
+  // Drop all structure: every file goes straight into Files/, clashing names de-duplicated
+  pub flatten: bool,
+  // With flatten, de-duplicate clashes with a short hash of the source path instead of " (n)"
+  pub flatten_hash: bool,
+  // Per-extension destinations, first match wins (e.g. .wav -> Audio/Location); see naming.rs
+  pub routes: Vec<naming::RouteRule>,
+  // Descend into symlinked directories inside folder picks (loops are detected and skipped)
+  pub follow_dir_symlinks: bool,
+  // Minimum gap between `transfer://progress` events while copying
+  pub progress_interval_ms: u64,
+  // Also emit after every MiB copied (reads are capped at 1 MiB to allow it)
+  pub progress_precise: bool,
+  // Tune the number of parallel copies (concurrency > 1 is the starting point) and, with
+  // verify_workers = 0, verify threads to observed throughput
+  pub adaptive_workers: bool,
+  // Secure move: overwrite each source this many times before deleting it (0 = plain
+  // delete). Needs a hash verify_mode; see erase.rs for the SSD caveat
+  pub secure_erase_passes: u32,
+  // Archival: make verified destination files read-only once the job finishes
+  pub lock_readonly: bool,
+  // With lock_readonly, also lock the session folder itself (manifest, summary and
+  // every subfolder) so nothing can be added or removed. Retries into it then fail
+  pub lock_session_dir: bool,
+  // Online-only cloud files (OneDrive/iCloud/Dropbox): "hydrate" | "skip" | "fail"; see cloud.rs
+  pub cloud_placeholders: String,
+  // Give each copy its source's times, permission bits and xattrs (see preserve.rs)
+  pub preserve_metadata: bool,
+  // Globs picking which files are scanned, e.g. "**/*.raw" / "node_modules/**" (see filters.rs)
+  pub include_globs: Vec<String>,
+  pub exclude_globs: Vec<String>,
+  // Leave out dotfiles, .DS_Store, Thumbs.db, desktop.ini, __MACOSX and the like (see filters.rs)
+  pub skip_hidden: bool,
+  // start_transfer only plans: writes the manifest a run would produce, copies nothing
+  pub dry_run: bool,
+  // Cap on the job's total throughput in bytes/sec (0 = none); set_transfer_limit changes it mid-run
+  pub max_bytes_per_sec: u64,
+  // Read/write errors on a file are retried this way before the row becomes an error
+  pub retry_policy: RetryPolicy,
+  // Also write manifest.csv (for opening in Excel) beside manifest.json
+  pub manifest_csv: bool,
+}
+
+impl Default for TransferOptions {
+  fn default() -> Self {
+    TransferOptions {
+      debug: false,
+      stall_timeout_secs: 30,
+      ordering: "path".to_string(),
+      verify_strategy: "inline".to_string(),
+      verify_workers: 0,
+      concurrency: 1,
+      verify_bypass_cache: false,
+      dest_subdir: None,
+      rename_prefix: None,
+      project: None,
+      layout_template: None,
+      rename_template: None,
+      organize_audio: false,
+      burst_grouping: "off".to_string(),
+      device_bytes_per_sec: 0,
+      on_complete: "none".to_string(),
+      on_complete_hook: None,
+      auto_eject: false,
+      durability: "per_file".to_string(),
+      reserve_bytes: 512 * 1024 * 1024,
+      reserve_percent: 0.0,
+      network_tuning: true,
+      mtime_tolerance_ms: None,
+      session_naming: "local".to_string(),
+      contents_only: false,
+      flatten: false,
+      flatten_hash: false,
+      routes: vec![],
+      follow_dir_symlinks: false,
+      progress_interval_ms: 120,
+      progress_precise: false,
+      adaptive_workers: true,
+      secure_erase_passes: 0,
+      lock_readonly: false,
+      lock_session_dir: false,
+      cloud_placeholders: "hydrate".to_string(),
+      preserve_metadata: false,
+      include_globs: vec![],
+      exclude_globs: vec![],
+      skip_hidden: false,
+      dry_run: false,
+      max_bytes_per_sec: 0,
+      retry_policy: RetryPolicy::default(),
+      manifest_csv: false,
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 struct FileEntry {
   src: PathBuf,
   // If it came from a folder pick, this is Some(<folder_basename>/<relative_path_inside_folder>)
   // If it came from a loose file pick, this is None
   folder_rel: Option<PathBuf>,
+  // Exact destination, bypassing the layout (retrying a row recorded in a manifest)
+  dest: Option<PathBuf>,
+  // Burst this still belongs to, when burst grouping is on (see bursts.rs)
+  burst: Option<String>,
+}
+
+/// Where a run writes: a fresh timestamped session on a drive, or back into an
+/// existing session (retry/resume), merging into its manifest.
+pub(crate) enum SessionTarget {
+  New(String),
+  Existing {
+    session_dir: PathBuf,
+    rows: Vec<ManifestItem>,
+    // Dests holding this session's own unfinished copies: replaced whatever
+    // conflict_policy says, while the rows' other dests are never reused
+    replace: HashSet<PathBuf>,
+  },
 }
 
 /* --------------------------------- Progress -------------------------------- */
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TransferProgress {
-  pub phase: String, // "scanning" | "copying" | "verifying" | "done" | "cancelled" | "error"
-  pub current_file: u64, // 1-based
-  pub total_files: u64,
-  pub current_path: String,
-  pub bytes_done: u64,
-  pub bytes_total: u64,
-  pub percent: f64, // 0..=100
+// Fills in speed, ETA and elapsed time from the job's meter, then sends on both
+// the shared topic and the job's own
+fn emit_progress(app: &AppHandle, job: &JobControl, p: &TransferProgress) {
+  let mut p = p.clone();
+  (p.bytes_per_sec, p.eta_seconds, p.elapsed_ms) = job.speed_sample(&p.phase, p.bytes_done, p.bytes_total);
+  events::emit_for_job(app, events::PROGRESS, &job.id, &p);
+  if let Some(topic) = events::job_topic(events::PROGRESS, &job.id) {
+    events::emit_for_job(app, &topic, &job.id, &p);
+  }
 }
 
-fn emit_progress(app: &AppHandle, p: &TransferProgress) {
-  let _ = app.emit("transfer://progress", p.clone());
+// How often copy loops emit `transfer://progress`: every `interval`, and in
+// precise mode also after every MiB copied (for external displays and logging).
+#[derive(Debug, Clone, Copy)]
+struct EmitRate {
+  interval: Duration,
+  // 0 = time-based only
+  every_bytes: u64,
+}
+
+const PRECISE_EMIT_BYTES: u64 = 1024 * 1024;
+
+impl EmitRate {
+  fn from_options(options: &TransferOptions) -> Self {
+    EmitRate {
+      interval: Duration::from_millis(options.progress_interval_ms.max(1)),
+      every_bytes: if options.progress_precise { PRECISE_EMIT_BYTES } else { 0 },
+    }
+  }
+
+  fn due(&self, last_emit: Instant, bytes_since: u64) -> bool {
+    last_emit.elapsed() >= self.interval || (self.every_bytes > 0 && bytes_since >= self.every_bytes)
+  }
+
+  // A read can't be reported in smaller steps than it happens in
+  fn cap_buf(&self, buf_bytes: usize) -> usize {
+    if self.every_bytes > 0 {
+      buf_bytes.min(self.every_bytes as usize)
+    } else {
+      buf_bytes
+    }
+  }
 }
 
 fn pct(bytes_done: u64, bytes_total: u64) -> f64 {
@@ -60,50 +304,43 @@ fn pct(bytes_done: u64, bytes_total: u64) -> f64 {
   }
 }
 
-/* ---------------------------------- Storage -------------------------------- */
-
-pub fn avail_bytes_for_mount(mount_point: &str) -> Result<u64, String> {
-  use std::process::Command;
-
-  let out = Command::new("df")
-    .arg("-k")
-    .arg(mount_point)
-    .output()
-    .map_err(|e| format!("failed to run df: {e}"))?;
-
-  let s = String::from_utf8_lossy(&out.stdout);
-  let mut lines = s.lines();
-  lines.next(); // header
-  if let Some(line) = lines.next() {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 4 {
-      let avail_kb = parts[3].parse::<u64>().unwrap_or(0);
-      return Ok(avail_kb * 1024);
-    }
-  }
-  Ok(0)
-}
-
 /* ----------------------------- Local time helpers ---------------------------- */
 /* Uses chrono because it's reliable cross-OS and doesn't require time crate local offset features. */
 
-fn day_stamp_local() -> String {
-  // e.g. 2025-12-13
-  chrono::Local::now().format("%Y-%m-%d").to_string()
-}
-
-fn time_stamp_local() -> String {
-  // e.g. 185354 (HHMMSS)
-  chrono::Local::now().format("%H%M%S").to_string()
+// (day dir, run dir) names for a new session. "local": 2025-12-13 / 185354;
+// "utc": 2025-12-13 / 175354Z (sorts correctly across time zones and DST);
+// "offset": local time with its UTC offset, 2025-12-13 / 185354+0100.
+fn session_stamps(naming: &str) -> (String, String) {
+  match naming {
+    "utc" => {
+      let now = chrono::Utc::now();
+      (now.format("%Y-%m-%d").to_string(), now.format("%H%M%SZ").to_string())
+    }
+    "offset" => {
+      let now = chrono::Local::now();
+      (now.format("%Y-%m-%d").to_string(), now.format("%H%M%S%z").to_string())
+    }
+    _ => {
+      let now = chrono::Local::now();
+      (now.format("%Y-%m-%d").to_string(), now.format("%H%M%S").to_string())
+    }
+  }
 }
 
 fn now_local_rfc3339() -> String {
   chrono::Local::now().to_rfc3339()
 }
 
+// Same instant as a local RFC 3339 stamp, in UTC ("" if it doesn't parse)
+fn utc_rfc3339(local: &str) -> String {
+  chrono::DateTime::parse_from_rfc3339(local)
+    .map(|t| t.with_timezone(&chrono::Utc).to_rfc3339())
+    .unwrap_or_default()
+}
+
 /* --------------------------------- Categorize -------------------------------- */
 
-fn category_for(path: &Path) -> (String, String) {
+pub(crate) fn category_for(path: &Path) -> (String, String) {
   let ext = path
     .extension()
     .and_then(|s| s.to_str())
@@ -149,18 +386,55 @@ fn category_for(path: &Path) -> (String, String) {
 
 /* ---------------------------------- Scanning -------------------------------- */
 
-fn scan_entries(items: &[PickedItem]) -> Result<Vec<FileEntry>, String> {
+// Applies the per-transfer contents_only option to every folder pick.
+fn with_contents_only(mut items: Vec<PickedItem>, options: &TransferOptions) -> Vec<PickedItem> {
+  if options.contents_only {
+    for it in items.iter_mut().filter(|it| it.kind == "folder") {
+      it.contents_only = true;
+    }
+  }
+  items
+}
+
+fn scan_entries(items: &[PickedItem], options: &TransferOptions) -> Result<Vec<FileEntry>, String> {
+  scan_entries_until(items, options, &|| false).map(|(entries, _, _)| entries)
+}
+
+// Like scan_entries, but gives up as soon as `stop` says so. The bool is true when
+// the walk was cut short and the entries are only what was found up to that point.
+// Also returns what the filters (globs, skip_hidden) left out.
+fn scan_entries_until(
+  items: &[PickedItem],
+  options: &TransferOptions,
+  stop: &dyn Fn() -> bool,
+) -> Result<(Vec<FileEntry>, bool, Excluded), String> {
   let mut out: Vec<FileEntry> = vec![];
+  // Following links, two links (or a link and the real dir) can reach the same file
+  let mut seen_real: HashSet<PathBuf> = HashSet::new();
+  let filters = Filters::new(options);
+  let mut excluded = Excluded::default();
+  // Counted from inside filter_entry, which can't borrow `excluded`
+  let pruned = std::cell::Cell::new(0u64);
 
   for it in items {
+    if stop() {
+      excluded.folders = pruned.get();
+      return Ok((out, true, excluded));
+    }
     let p = PathBuf::from(&it.path);
 
     if it.kind == "file" {
-      if p.is_file() {
+      let name = PathBuf::from(p.file_name().unwrap_or_default());
+      if p.is_file() && filters.keeps_file(&name) {
         out.push(FileEntry {
           src: p,
           folder_rel: None,
+          dest: None,
+          burst: None,
         });
+      } else if p.is_file() {
+        excluded.files += 1;
+        excluded.bytes += fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
       }
       continue;
     }
@@ -173,51 +447,263 @@ fn scan_entries(items: &[PickedItem]) -> Result<Vec<FileEntry>, String> {
         .unwrap_or("Folder")
         .to_string();
 
-      for e in WalkDir::new(&p).into_iter().filter_map(|e| e.ok()) {
+      // sort_by_file_name: readdir order varies between runs and filesystems.
+      // With follow_links walkdir reports a link back to an ancestor as an error
+      // instead of descending, so a loop ends the branch rather than the scan.
+      for e in WalkDir::new(&p)
+        .sort_by_file_name()
+        .follow_links(options.follow_dir_symlinks)
+        .into_iter()
+        // Excluded folders aren't descended into
+        .filter_entry(|e| {
+          if !e.file_type().is_dir() || e.depth() == 0 {
+            return true;
+          }
+          let rel = Path::new(&folder_base).join(e.path().strip_prefix(&p).unwrap_or(e.path()));
+          let walk = !filters.hides(e.path()) && filters.walks_folder(&rel);
+          if !walk {
+            pruned.set(pruned.get() + 1);
+          }
+          walk
+        })
+        .filter_map(|e| match e {
+          Ok(e) => Some(e),
+          Err(err) => {
+            if err.loop_ancestor().is_some() {
+              logging::warn(&format!("symlink loop skipped: {err}"));
+            }
+            None
+          }
+        })
+      {
+        if stop() {
+          excluded.folders = pruned.get();
+          return Ok((out, true, excluded));
+        }
+        if options.follow_dir_symlinks && e.file_type().is_file() {
+          let real = fs::canonicalize(e.path()).unwrap_or_else(|_| e.path().to_path_buf());
+          if !seen_real.insert(real) {
+            continue;
+          }
+        }
         if e.file_type().is_file() {
           let full = e.path().to_path_buf();
           let rel_inside = full.strip_prefix(&p).unwrap_or(&full);
-          let rel = PathBuf::from(&folder_base).join(rel_inside);
+          if filters.hides(&full) || !filters.keeps_file(&Path::new(&folder_base).join(rel_inside)) {
+            excluded.files += 1;
+            excluded.bytes += e.metadata().map(|m| m.len()).unwrap_or(0);
+            continue;
+          }
+          let rel = if it.contents_only {
+            rel_inside.to_path_buf()
+          } else {
+            PathBuf::from(&folder_base).join(rel_inside)
+          };
 
           out.push(FileEntry {
             src: full,
             folder_rel: Some(rel),
+            dest: None,
+            burst: None,
           });
         }
       }
     }
   }
 
-  Ok(out)
+  excluded.folders = pruned.get();
+  Ok((out, false, excluded))
+}
+
+/* ---------------------------------- Ordering -------------------------------- */
+
+#[cfg(unix)]
+fn disk_order_key(path: &Path) -> u64 {
+  use std::os::unix::fs::MetadataExt;
+  fs::metadata(path).map(|m| m.ino()).unwrap_or(u64::MAX)
+}
+
+#[cfg(not(unix))]
+fn disk_order_key(_path: &Path) -> u64 {
+  0
+}
+
+// Spinning disks seek badly when we hop between directories. "locality" keeps each
+// directory's files together and, within a directory, follows inode order, which on
+// HFS+/APFS/ext4 roughly tracks where the data sits on the platter.
+fn order_entries(entries: &mut [FileEntry], ordering: &str) {
+  match ordering {
+    "locality" => {
+      entries.sort_by_cached_key(|e| {
+        let dir = e.src.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        (dir, disk_order_key(&e.src), e.src.clone())
+      });
+    }
+    "scan" => {}
+    // Stable sort by path: two runs over the same selection copy in the same order
+    _ => entries.sort_by(|a, b| a.src.cmp(&b.src)),
+  }
+}
+
+// How many of the biggest files preflight lists
+const PREFLIGHT_LARGEST: usize = 10;
+// How many cloud placeholder paths preflight lists (all are counted)
+const PREFLIGHT_PLACEHOLDERS: usize = 20;
+// How many destination filesystem warnings preflight lists
+const PREFLIGHT_WARNINGS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFile {
+  pub path: String,
+  pub bytes: u64,
+}
+
+/// Creates, writes, syncs and deletes a small file in `dest` to prove it can be
+/// written to. Read-only mounts (or NTFS on macOS without a driver) otherwise pass
+/// the space check and then fail every copy.
+pub fn probe_writable(dest: &str) -> Result<(), String> {
+  let probe = Path::new(dest).join(format!(".transferpilot-write-test-{}", uuid::Uuid::new_v4().simple()));
+  let res = fs::File::create(&probe)
+    .and_then(|mut f| f.write_all(b"transferpilot").and_then(|_| f.sync_all()))
+    .map_err(|e| format!("destination not writable: {e}"));
+  let removed = fs::remove_file(&probe);
+  res?;
+  removed.map_err(|e| format!("destination write test could not be removed: {e}"))
 }
 
-pub fn preflight_scan(items: Vec<PickedItem>, dest_mount_point: String) -> Result<Preflight, String> {
-  let entries = scan_entries(&items)?;
+/// Cancelling `ctl` ends the scan early; the result then covers only what was
+/// walked so far (`cancelled: true`). Either way the result is also left in
+/// `ctl.result` for cancel_preflight to hand back.
+pub fn preflight_scan(
+  items: Vec<PickedItem>,
+  dest_mount_point: String,
+  options: TransferOptions,
+  reserved_bytes: u64,
+  ctl: &PreflightControl,
+) -> Result<Preflight, String> {
+  let (mut entries, mut cancelled, excluded) = scan_entries_until(&items, &options, &|| ctl.is_cancelled())?;
 
   let mut total_bytes: u64 = 0;
   let mut by_category: HashMap<String, u64> = HashMap::new();
   let mut by_extension: HashMap<String, u64> = HashMap::new();
 
+  let mut sizes: Vec<(u64, usize)> = Vec::with_capacity(entries.len());
+  let mut placeholder_files: u64 = 0;
+  let mut placeholder_paths: Vec<String> = vec![];
+  let mut warnings: Vec<fscompat::PreflightWarning> = vec![];
+  let mut unlisted_warnings: u64 = 0;
+
+  // Where the files would land, as run_transfer lays them out
+  let limits = fscompat::Limits::for_mount(&dest_mount_point);
+  let session_dir = planned_session_dir(&dest_mount_point, &options);
+
+  let mut counted = 0;
   for ent in &entries {
+    if ctl.is_cancelled() {
+      cancelled = true;
+      break;
+    }
     let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
     total_bytes = total_bytes.saturating_add(meta.len());
+    sizes.push((meta.len(), counted));
+    if cloud::placeholder_kind(&ent.src, &meta).is_some() {
+      placeholder_files += 1;
+      if placeholder_paths.len() < PREFLIGHT_PLACEHOLDERS {
+        placeholder_paths.push(ent.src.to_string_lossy().to_string());
+      }
+    }
+    let dst = ent.dest.clone().unwrap_or_else(|| session_dir.join(layout_rel(ent, &options)));
+    for w in limits.check(&ent.src, &dst, meta.len()) {
+      if warnings.len() < PREFLIGHT_WARNINGS {
+        warnings.push(w);
+      } else {
+        unlisted_warnings += 1;
+      }
+    }
+    counted += 1;
 
     let (cat, ext) = category_for(&ent.src);
     *by_category.entry(cat).or_insert(0) += 1;
     *by_extension.entry(format!(".{ext}")).or_insert(0) += 1;
   }
+  entries.truncate(counted);
+
+  sizes.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+  let largest_files = sizes
+    .iter()
+    .take(PREFLIGHT_LARGEST)
+    .map(|&(bytes, i)| LargeFile {
+      path: entries[i].src.to_string_lossy().to_string(),
+      bytes,
+    })
+    .collect();
 
-  let dest_avail = crate::transfer::avail_bytes_for_mount(&dest_mount_point).unwrap_or(0);
+  let (dest_total, dest_avail) = space::space_for_mount(&dest_mount_point).unwrap_or((0, 0));
+  let reserve_bytes = space::reserve_bytes(&options, dest_total);
+  let camera = camera_hashes::detect(entries.iter().map(|e| e.src.as_path()));
+  let quota = quota::check(&dest_mount_point, total_bytes);
+  let quota_blocks = quota.as_ref().is_some_and(|q| q.exceeded && q.action == "fail");
+  let dest_network = netfs::is_network_path(&dest_mount_point);
+  let write_error = probe_writable(&dest_mount_point).err();
+  let mut notes: Vec<String> = vec![];
+  if dest_network {
+    notes.push("Destination is a network share; throughput depends on the network, not the drives.".to_string());
+  }
+  let flash_source = entries.first().is_some_and(|e| device::is_rotational(&e.src.to_string_lossy()) != Some(true));
+  if placeholder_files > 0 {
+    notes.push(format!(
+      "{placeholder_files} file(s) are online-only cloud placeholders; with cloud_placeholders = \"{}\" they will {}.",
+      options.cloud_placeholders,
+      match options.cloud_placeholders.as_str() {
+        "skip" => "be skipped",
+        "fail" => "stop the transfer from starting",
+        _ => "be downloaded as they're copied, which can be slow",
+      }
+    ));
+  }
+  if unlisted_warnings > 0 {
+    notes.push(format!(
+      "{unlisted_warnings} more file(s) won't fit the destination's filesystem limits than are listed in warnings."
+    ));
+  }
+  if options.secure_erase_passes > 0 && flash_source {
+    notes.push(
+      "Secure move overwrites sources before deleting them, but SSDs and flash cards remap writes, so copies of the data can survive. Use a device-level erase if that matters."
+        .to_string(),
+    );
+  }
 
-  Ok(Preflight {
+  let preflight = Preflight {
     total_files: entries.len() as u64,
     total_folders: items.iter().filter(|x| x.kind == "folder").count() as u64,
     total_bytes,
     dest_avail_bytes: dest_avail,
-    will_fit: dest_avail >= total_bytes,
+    reserve_bytes,
+    dest_reserved_bytes: reserved_bytes,
+    will_fit: dest_avail >= total_bytes.saturating_add(reserve_bytes).saturating_add(reserved_bytes) && !quota_blocks,
     by_category,
     by_extension,
-  })
+    camera_checksum_files: camera.files.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+    camera_hashed_files: camera.len() as u64,
+    quota,
+    largest_files,
+    placeholder_files,
+    placeholder_paths,
+    writable: write_error.is_none(),
+    write_error,
+    dest_network,
+    notes,
+    warnings,
+    excluded,
+    cancelled,
+  };
+  if cancelled {
+    logging::info(&format!("preflight {} cancelled after {} file(s)", ctl.id, preflight.total_files));
+  }
+  if let Ok(mut g) = ctl.result.lock() {
+    *g = Some(preflight.clone());
+  }
+  Ok(preflight)
 }
 
 /* -------------------------------- File helpers ------------------------------- */
@@ -227,7 +713,13 @@ fn ensure_dir(p: &Path) -> Result<(), String> {
 }
 
 fn unique_dest_path(dest: &Path) -> PathBuf {
-  if !dest.exists() {
+  unique_path_where(dest, &|p| p.exists())
+}
+
+// unique_dest_path with `taken` deciding what's in use (a dry run also counts
+// names it has already handed out)
+fn unique_path_where(dest: &Path, taken: &dyn Fn(&Path) -> bool) -> PathBuf {
+  if !taken(dest) {
     return dest.to_path_buf();
   }
   let stem = dest.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
@@ -240,50 +732,189 @@ fn unique_dest_path(dest: &Path) -> PathBuf {
       format!("{stem} ({i}).{ext}")
     };
     let candidate = parent.join(name);
-    if !candidate.exists() {
+    if !taken(&candidate) {
       return candidate;
     }
   }
   dest.to_path_buf()
 }
 
+// Flatten mode: a name another source already took this run. "IMG_0001.JPG" from a
+// second card becomes "IMG_0001 (1).JPG", or with `hash` "IMG_0001_3fa9c2d1.JPG".
+fn flatten_unique_path(dest: &Path, src: &Path, hash: bool) -> PathBuf {
+  if !hash {
+    return unique_dest_path(dest);
+  }
+  let stem = dest.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+  let ext = dest.extension().and_then(|s| s.to_str()).unwrap_or("");
+  let tag = format!("{:016x}", xxhash_rust::xxh3::xxh3_64(src.to_string_lossy().as_bytes()));
+  let name = if ext.is_empty() {
+    format!("{stem}_{}", &tag[..8])
+  } else {
+    format!("{stem}_{}.{ext}", &tag[..8])
+  };
+  unique_dest_path(&dest.with_file_name(name))
+}
+
+fn rebase_path(p: &Path, old_root: &str, new_root: &str) -> PathBuf {
+  match p.strip_prefix(old_root) {
+    Ok(rel) => PathBuf::from(new_root).join(rel),
+    Err(_) => p.to_path_buf(),
+  }
+}
+
+// How often a paused copy checks for resume / cancel
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+
+/// Blocks a copy loop while the job is paused (until resumed, cancelled or the
+/// file is skipped). Both files stay open, so the copy carries on from the same
+/// byte; the watchdog is kept fed so a long pause isn't reported as a stall.
+#[allow(clippy::too_many_arguments)]
+fn hold_paused(
+  app: &AppHandle,
+  job: &JobControl,
+  watchdog: &Watchdog,
+  item_id: &str,
+  current_file: u64,
+  total_files: u64,
+  bytes_done: u64,
+  bytes_total: u64,
+) {
+  let progress = |phase: &str| TransferProgress {
+    phase: phase.to_string(),
+    current_file,
+    total_files,
+    current_path: item_id.to_string(),
+    bytes_done,
+    bytes_total,
+    percent: pct(bytes_done, bytes_total),
+    io: None,
+    bytes_per_sec: 0,
+    eta_seconds: None,
+    elapsed_ms: 0,
+  };
+  emit_progress(app, job, &progress("paused"));
+  while job.is_paused() && !job.is_cancelled() && !job.is_skipped(item_id) {
+    watchdog.tick();
+    thread::sleep(PAUSE_POLL);
+  }
+  emit_progress(app, job, &progress("copying"));
+}
+
+#[allow(clippy::too_many_arguments)]
 fn copy_file_streamed(
   src: &Path,
   dst: &Path,
-  cancel: &Arc<AtomicBool>,
+  job: &JobControl,
   bytes_done: &mut u64,
   bytes_total: u64,
   app: &AppHandle,
   current_file: u64,
   total_files: u64,
-) -> Result<(), String> {
+  watchdog: &Watchdog,
+  limiter: Option<&RateLimiter>,
+  sync: bool,
+  buf_bytes: usize,
+  rate: EmitRate,
+  resume_from: Option<u64>,
+  state: Option<&StateFile>,
+  hash: Option<Algo>,
+) -> Result<Option<String>, String> {
   if let Some(parent) = dst.parent() {
     ensure_dir(parent)?;
   }
 
   let mut in_f = fs::File::open(src).map_err(|e| format!("open src error: {e}"))?;
-  let mut out_f = fs::File::create(dst).map_err(|e| format!("create dst error: {e}"))?;
+  // Source digest taken from the bytes as they stream through, so verify only
+  // has to read the copy back
+  let mut hasher = hash.map(checksum::Hasher::new);
+  // Continue a partial copy from an interrupted run (offset already validated)
+  let mut written = resume_from.unwrap_or(0);
+  let mut out_f = if written > 0 {
+    // The part copied last time still has to go through the hash
+    if let Some(h) = hasher.as_mut() {
+      hash_prefix(&mut in_f, written, h)?;
+    }
+    let mut f = fs::OpenOptions::new()
+      .write(true)
+      .open(dst)
+      .map_err(|e| format!("open dst error: {e}"))?;
+    f.set_len(written).map_err(|e| format!("truncate dst error: {e}"))?;
+    f.seek(SeekFrom::Start(written)).map_err(|e| format!("seek dst error: {e}"))?;
+    in_f.seek(SeekFrom::Start(written)).map_err(|e| format!("seek src error: {e}"))?;
+    logging::info(&format!("{}: resuming at byte {written}", src.display()));
+    *bytes_done = bytes_done.saturating_add(written);
+    f
+  } else {
+    fs::File::create(dst).map_err(|e| format!("create dst error: {e}"))?
+  };
+  let mut since_checkpoint = 0u64;
 
-  let mut buf = vec![0u8; 1024 * 1024];
+  let mut buf = vec![0u8; buf_bytes];
   let mut last_emit = Instant::now();
+  let mut last_emit_bytes = *bytes_done;
+
+  let item_id = src.to_string_lossy();
 
   loop {
-    if cancel.load(Ordering::SeqCst) {
+    if job.is_cancelled() {
+      // Leave the partial file resumable from exactly here
+      if let Some(state) = state.filter(|_| out_f.sync_data().is_ok()) {
+        state.checkpoint(src, dst, written);
+      }
       return Err("cancelled".to_string());
     }
+    if job.is_skipped(&item_id) {
+      return Err("skipped".to_string());
+    }
+    if job.is_paused() {
+      hold_paused(app, job, watchdog, &item_id, current_file, total_files, *bytes_done, bytes_total);
+      continue;
+    }
+
+    match watchdog.take_action() {
+      watchdog::STALL_RETRY => return Err("retry".to_string()),
+      watchdog::STALL_ABORT => {
+        job.cancel.store(true, Ordering::SeqCst);
+        return Err("cancelled".to_string());
+      }
+      _ => {}
+    }
 
+    let t = Instant::now();
     let n = in_f.read(&mut buf).map_err(|e| format!("read error: {e}"))?;
     if n == 0 {
       break;
     }
+    job.record_read(n as u64, t.elapsed());
+    watchdog.tick();
+    if let Some(l) = limiter {
+      l.consume(n as u64);
+    }
+    job.limit.consume(n as u64);
+    if let Some(h) = hasher.as_mut() {
+      h.update(&buf[..n]);
+    }
 
+    let t = Instant::now();
     out_f.write_all(&buf[..n]).map_err(|e| format!("write error: {e}"))?;
+    job.record_write(n as u64, t.elapsed());
     *bytes_done = bytes_done.saturating_add(n as u64);
+    written += n as u64;
+    watchdog.tick();
+
+    since_checkpoint += n as u64;
+    if let Some(state) = state.filter(|_| since_checkpoint >= session_state::CHECKPOINT_BYTES) {
+      out_f.sync_data().map_err(|e| format!("sync error: {e}"))?;
+      state.checkpoint(src, dst, written);
+      since_checkpoint = 0;
+    }
 
-    // throttle emits to ~8/sec
-    if last_emit.elapsed() >= Duration::from_millis(120) {
+    // throttle emits (~8/sec by default)
+    if rate.due(last_emit, *bytes_done - last_emit_bytes) {
       emit_progress(
         app,
+        job,
         &TransferProgress {
           phase: "copying".to_string(),
           current_file,
@@ -292,386 +923,3824 @@ fn copy_file_streamed(
           bytes_done: *bytes_done,
           bytes_total,
           percent: pct(*bytes_done, bytes_total),
+          io: job.io_stats(),
+          bytes_per_sec: 0,
+          eta_seconds: None,
+          elapsed_ms: 0,
         },
       );
       last_emit = Instant::now();
+      last_emit_bytes = *bytes_done;
     }
   }
 
-  out_f.sync_all().ok();
-  Ok(())
+  if sync {
+    out_f.sync_all().map_err(|e| format!("sync error: {e}"))?;
+  }
+  Ok(hasher.map(|h| h.finalize()))
 }
 
-fn sha256_file(path: &Path) -> Result<String, String> {
-  let mut f = fs::File::open(path).map_err(|e| format!("open error: {e}"))?;
-  let mut hasher = Sha256::new();
-  let mut buf = [0u8; 1024 * 1024];
-  loop {
-    let n = f.read(&mut buf).map_err(|e| format!("read error: {e}"))?;
-    if n == 0 {
-      break;
-    }
-    hasher.update(&buf[..n]);
+// Feeds the first `len` bytes of `f` to `hasher`, leaving `f` at offset `len`
+fn hash_prefix(f: &mut fs::File, len: u64, hasher: &mut checksum::Hasher) -> Result<(), String> {
+  let mut buf = vec![0u8; 1024 * 1024];
+  let mut left = len;
+  while left > 0 {
+    let want = left.min(buf.len() as u64) as usize;
+    f.read_exact(&mut buf[..want]).map_err(|e| format!("read error: {e}"))?;
+    hasher.update(&buf[..want]);
+    left -= want as u64;
   }
-  Ok(hex::encode(hasher.finalize()))
+  Ok(())
 }
 
-/* --------------------------------- Manifest --------------------------------- */
+/* ------------------------------ Parallel copy ------------------------------- */
+/* Tens of thousands of small files are dominated by per-file overhead (open,
+   create, metadata), not bandwidth, so with concurrency > 1 run_transfer hands
+   them to copy_parallel in batches. Big files still go one at a time through
+   copy_file_streamed, which is where checkpoints, stall retries and drive
+   reattach live. */
+
+// Files up to this size are batched
+const PARALLEL_MAX_BYTES: u64 = 8 * 1024 * 1024;
+// A batch is copied once it holds this many files or bytes
+const PARALLEL_BATCH_FILES: usize = 256;
+const PARALLEL_BATCH_BYTES: u64 = 256 * 1024 * 1024;
 
-#[derive(Debug, Serialize)]
-struct ManifestItem {
-  source: String,
-  dest: String,
-  category: String,
+// A file run_transfer has placed (dest, conflicts, space) and is ready to copy
+struct Planned {
+  ent: FileEntry,
+  dst: PathBuf,
+  cat: String,
   ext: String,
   bytes: u64,
-  status: String, // copied|moved|skipped|error|cancelled
-  error: Option<String>,
+  src_meta: SourceMeta,
+  current_file: u64,
+  limiter: Option<Arc<RateLimiter>>,
 }
 
-/* --------------------------------- Transfer --------------------------------- */
+// Manifest copy_started_at / copy_finished_at for one file
+#[derive(Debug, Clone, Default)]
+struct CopyTimes {
+  started: Option<String>,
+  finished: Option<String>,
+}
 
-pub async fn start_transfer(
-  app: tauri::AppHandle,
-  items: Vec<PickedItem>,
-  dest_mount_point: String,
-  copy_mode: String,
-  conflict_policy: String,
-  verify_mode: String,
-  cancel: Arc<AtomicBool>,
-) -> Result<TransferSummary, String> {
-  let started_at = now_local_rfc3339();
-  let start = Instant::now();
+impl CopyTimes {
+  fn start() -> Self {
+    CopyTimes {
+      started: Some(now_local_rfc3339()),
+      finished: None,
+    }
+  }
 
-  emit_progress(
-    &app,
-    &TransferProgress {
-      phase: "scanning".to_string(),
-      current_file: 0,
-      total_files: 0,
-      current_path: "".to_string(),
-      bytes_done: 0,
-      bytes_total: 0,
-      percent: 0.0,
-    },
-  );
+  fn finish(&mut self) {
+    self.finished = Some(now_local_rfc3339());
+  }
+}
 
-  let entries = scan_entries(&items)?;
+// Whole-file copy for batched files: small enough to read in one go, so cancel
+// and skip are checked per file rather than per chunk
+fn copy_small_file(
+  p: &Planned,
+  job: &JobControl,
+  watchdog: &Watchdog,
+  sync: bool,
+  hash: Option<Algo>,
+) -> Result<Option<String>, String> {
+  let item_id = p.ent.src.to_string_lossy();
+  // Fed like hold_paused does, so a long pause isn't reported as a stall
+  while job.is_paused() && !job.is_cancelled() && !job.is_skipped(&item_id) {
+    watchdog.tick();
+    thread::sleep(PAUSE_POLL);
+  }
+  // After any pause: the user may have cancelled or skipped it meanwhile
+  if job.is_cancelled() {
+    return Err("cancelled".to_string());
+  }
+  if job.is_skipped(&item_id) {
+    return Err("skipped".to_string());
+  }
+  if let Some(parent) = p.dst.parent() {
+    ensure_dir(parent)?;
+  }
 
-  // precompute total_bytes
-  let mut total_bytes: u64 = 0;
-  for ent in &entries {
-    let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
-    total_bytes = total_bytes.saturating_add(meta.len());
+  let t = Instant::now();
+  let data = fs::read(&p.ent.src).map_err(|e| format!("read error: {e}"))?;
+  job.record_read(data.len() as u64, t.elapsed());
+  if let Some(l) = p.limiter.as_deref() {
+    l.consume(data.len() as u64);
   }
+  job.limit.consume(data.len() as u64);
 
-  // Folder layout: Transfers/YYYY-MM-DD/HHMMSS/
-  let day = day_stamp_local();
-  let run = time_stamp_local();
+  let t = Instant::now();
+  let mut out_f = fs::File::create(&p.dst).map_err(|e| format!("create dst error: {e}"))?;
+  out_f.write_all(&data).map_err(|e| format!("write error: {e}"))?;
+  if sync {
+    out_f.sync_all().map_err(|e| format!("sync error: {e}"))?;
+  }
+  job.record_write(data.len() as u64, t.elapsed());
+  Ok(hash.map(|algo| {
+    let mut h = checksum::Hasher::new(algo);
+    h.update(&data);
+    h.finalize()
+  }))
+}
 
-  let transfers_root = PathBuf::from(&dest_mount_point).join("Transfers");
-  let day_dir = transfers_root.join(&day);
-  let session_dir = day_dir.join(&run);
+/// Copies `batch` on `workers` threads and returns each file's result, in batch
+/// order. With `limit` set, up to its max threads exist but only as many as keep
+/// the batch's aggregate throughput rising take files (see adaptive.rs); the limit
+/// outlives the batch, so the next one starts where this one settled. Progress
+/// counts on from `bytes_base`.
+#[allow(clippy::too_many_arguments)]
+fn copy_parallel(
+  app: &AppHandle,
+  batch: &[Planned],
+  job: &JobControl,
+  watchdog: &Watchdog,
+  workers: usize,
+  limit: Option<&AdaptiveLimit>,
+  sync: bool,
+  hash: Option<Algo>,
+  bytes_base: u64,
+  bytes_total: u64,
+  total_files: u64,
+  rate: EmitRate,
+) -> Vec<(Result<Option<String>, String>, CopyTimes)> {
+  let next = AtomicUsize::new(0);
+  let bytes_done = AtomicU64::new(bytes_base);
+  let files_done = AtomicUsize::new(0);
+  let last_emit = Mutex::new((Instant::now(), bytes_base));
+  let results: Mutex<Vec<(Result<Option<String>, String>, CopyTimes)>> =
+    Mutex::new(vec![(Err("not copied".to_string()), CopyTimes::default()); batch.len()]);
+  let spawned = limit.map_or(workers, |l| l.max()).min(batch.len());
+  // Workers log at the job's level, not the global one
+  let debug = logging::debug_active();
 
-  ensure_dir(&session_dir)?;
+  thread::scope(|s| {
+    if let Some(l) = limit {
+      s.spawn(|| {
+        let _debug = logging::DebugGuard::new(debug);
+        l.run(&bytes_done, &|| files_done.load(Ordering::SeqCst) >= batch.len())
+      });
+    }
 
-  // Write Transfers/README.txt once
-  let readme_path = transfers_root.join("README.txt");
-  if !readme_path.exists() {
-    let contents = "\
-TransferPilot output
+    for w in 0..spawned {
+      let next = &next;
+      let bytes_done = &bytes_done;
+      let files_done = &files_done;
+      let last_emit = &last_emit;
+      let results = &results;
+      s.spawn(move || {
+        let _debug = logging::DebugGuard::new(debug);
+        loop {
+          // Parked by the adaptive limit (worker 0 never is)
+          if limit.is_some_and(|l| !l.allows(w)) {
+            if next.load(Ordering::SeqCst) >= batch.len() {
+              break;
+            }
+            thread::sleep(Duration::from_millis(50));
+            continue;
+          }
+          let idx = next.fetch_add(1, Ordering::SeqCst);
+          let Some(p) = batch.get(idx) else {
+            break;
+          };
 
-Folder layout:
-  Transfers/<YYYY-MM-DD>/<HHMMSS>/
-    - Files/      (loose files you added directly)
-    - Folders/    (folder picks; preserves the folder tree)
-    - manifest.json
+          watchdog.set_current(&p.ent.src.to_string_lossy());
+          let mut times = CopyTimes::start();
+          let r = copy_small_file(p, job, watchdog, sync, hash);
+          times.finish();
+          watchdog.tick();
 
-Pointers:
-  Transfers/_latest.txt -> most recent run folder
-  Transfers/<YYYY-MM-DD>/_latest.txt -> most recent run for that day
-";
-    let _ = fs::write(&readme_path, contents);
+          let b = bytes_done.fetch_add(p.bytes, Ordering::SeqCst) + p.bytes;
+          if let Ok(mut last) = last_emit.lock() {
+            if rate.due(last.0, b.saturating_sub(last.1)) {
+              emit_progress(
+                app,
+                job,
+                &TransferProgress {
+                  phase: "copying".to_string(),
+                  current_file: p.current_file,
+                  total_files,
+                  current_path: p.ent.src.to_string_lossy().to_string(),
+                  bytes_done: b,
+                  bytes_total,
+                  percent: pct(b, bytes_total),
+                  io: job.io_stats(),
+                  bytes_per_sec: 0,
+                  eta_seconds: None,
+                  elapsed_ms: 0,
+                },
+              );
+              *last = (Instant::now(), b);
+            }
+          }
+
+          if let Ok(mut g) = results.lock() {
+            g[idx] = (r, times);
+          }
+          files_done.fetch_add(1, Ordering::SeqCst);
+        }
+      });
+    }
+  });
+
+  results.into_inner().unwrap_or_else(|e| e.into_inner())
+}
+
+/* ---------------------------------- Verify ---------------------------------- */
+
+// A copied file awaiting the batch verify pass
+struct PendingVerify {
+  manifest_idx: usize,
+  src: PathBuf,
+  dst: PathBuf,
+  bytes: u64,
+  // Source digest taken while copying
+  src_checksum: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Verified {
+  // Some(msg) when the copy doesn't match the source
+  mismatch: Option<String>,
+  // Source digest, when verify_mode is a hash
+  checksum: Option<String>,
+}
+
+/// `src_checksum`, when the copy already hashed the source, saves reading it again.
+fn verify_copy(
+  src: &Path,
+  dst: &Path,
+  verify_mode: &str,
+  bypass_cache: bool,
+  watchdog: &Watchdog,
+  src_checksum: Option<String>,
+) -> Result<Verified, String> {
+  if verify_mode == "size" {
+    let a = fs::metadata(src).map_err(|e| format!("metadata error: {e}"))?;
+    let b = fs::metadata(dst).map_err(|e| format!("dst metadata error: {e}"))?;
+    if a.len() != b.len() {
+      return Ok(Verified {
+        mismatch: Some("verify failed: size mismatch".to_string()),
+        checksum: None,
+      });
+    }
+  } else if let Some(algo) = Algo::from_verify_mode(verify_mode) {
+    let a = match src_checksum {
+      Some(a) => a,
+      None => checksum::hash_file(src, algo, bypass_cache, watchdog)?,
+    };
+    let b = checksum::hash_file(dst, algo, bypass_cache, watchdog)?;
+    let mismatch = (a != b).then(|| format!("verify failed: {} mismatch", algo.name()));
+    return Ok(Verified {
+      mismatch,
+      checksum: Some(a),
+    });
   }
+  Ok(Verified::default())
+}
 
-  // Latest pointers
-  fs::write(
-    transfers_root.join("_latest.txt"),
-    session_dir.to_string_lossy().to_string(),
-  )
-  .map_err(|e| format!("latest write error: {e}"))?;
-  fs::write(
-    day_dir.join("_latest.txt"),
-    session_dir.to_string_lossy().to_string(),
-  )
-  .map_err(|e| format!("day latest write error: {e}"))?;
+// Hashing is CPU-bound on SSDs but seek-bound on spinning disks, where extra
+// readers only make things slower.
+fn verify_worker_count(requested: usize, roots: &[String], files: usize) -> usize {
+  let n = if requested > 0 {
+    requested
+  } else if roots.iter().any(|r| device::is_rotational(r) == Some(true)) {
+    1
+  } else {
+    max_adaptive_workers()
+  };
+  n.clamp(1, files.max(1))
+}
 
-  let total_files = entries.len() as u64;
+type VerifyResult = Result<Verified, String>;
+
+// Upper bound for adaptive verify and copy workers
+fn max_adaptive_workers() -> usize {
+  thread::available_parallelism().map(|n| n.get()).unwrap_or(2).min(8)
+}
+
+/// Verifies `pending` on `workers` threads. With `adaptive` set, up to
+/// max_adaptive_workers threads exist but only as many as keep throughput rising
+/// take work (see adaptive.rs). Slot i is None if the job was cancelled before
+/// file i was picked up.
+#[allow(clippy::too_many_arguments)]
+fn verify_parallel(
+  app: &AppHandle,
+  pending: &[PendingVerify],
+  verify_mode: &str,
+  bypass_cache: bool,
+  watchdog: &Watchdog,
+  job: &JobControl,
+  workers: usize,
+  adaptive: bool,
+) -> Vec<Option<VerifyResult>> {
+  let total_bytes: u64 = pending.iter().map(|p| p.bytes).sum();
+  let total_files = pending.len() as u64;
+
+  let next = AtomicUsize::new(0);
+  let bytes_done = AtomicU64::new(0);
+  let files_done = AtomicU64::new(0);
+  let results: Mutex<Vec<Option<VerifyResult>>> = Mutex::new(vec![None; pending.len()]);
 
-  // initial “copying” emit so bar appears instantly
   emit_progress(
-    &app,
+    app,
+    job,
     &TransferProgress {
-      phase: "copying".to_string(),
+      phase: "verifying".to_string(),
       current_file: 0,
       total_files,
       current_path: "".to_string(),
       bytes_done: 0,
       bytes_total: total_bytes,
       percent: 0.0,
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
     },
   );
 
-  let mut manifest: Vec<ManifestItem> = vec![];
+  let limit = adaptive.then(|| AdaptiveLimit::new(workers, max_adaptive_workers().max(workers)));
+  let spawned = limit.as_ref().map_or(workers, |l| l.max());
+  // Workers log at the job's level, not the global one
+  let debug = logging::debug_active();
 
-  let mut copied_files = 0u64;
-  let mut moved_files = 0u64;
+  thread::scope(|s| {
+    if let Some(l) = limit.as_ref() {
+      s.spawn(|| {
+        l.run(&bytes_done, &|| {
+          job.is_cancelled() || files_done.load(Ordering::SeqCst) >= total_files
+        })
+      });
+    }
+
+    for w in 0..spawned {
+      let limit = limit.as_ref();
+      let next = &next;
+      let bytes_done = &bytes_done;
+      let files_done = &files_done;
+      let results = &results;
+      s.spawn(move || {
+        let _debug = logging::DebugGuard::new(debug);
+        loop {
+          if job.is_cancelled() || next.load(Ordering::SeqCst) >= pending.len() {
+            break;
+          }
+          // Parked by the adaptive limit
+          if limit.is_some_and(|l| !l.allows(w)) {
+            thread::sleep(Duration::from_millis(50));
+            continue;
+          }
+          let idx = next.fetch_add(1, Ordering::SeqCst);
+          let Some(pv) = pending.get(idx) else {
+            break;
+          };
+
+          watchdog.set_current(&pv.src.to_string_lossy());
+          let r = verify_copy(
+            &pv.src,
+            &pv.dst,
+            verify_mode,
+            bypass_cache,
+            watchdog,
+            pv.src_checksum.clone(),
+          );
+
+          let b = bytes_done.fetch_add(pv.bytes, Ordering::SeqCst) + pv.bytes;
+          let f = files_done.fetch_add(1, Ordering::SeqCst) + 1;
+          emit_progress(
+            app,
+            job,
+            &TransferProgress {
+              phase: "verifying".to_string(),
+              current_file: f,
+              total_files,
+              current_path: pv.src.to_string_lossy().to_string(),
+              bytes_done: b,
+              bytes_total: total_bytes,
+              percent: pct(b, total_bytes),
+              io: None,
+              bytes_per_sec: 0,
+              eta_seconds: None,
+              elapsed_ms: 0,
+            },
+          );
+
+          if let Ok(mut g) = results.lock() {
+            g[idx] = Some(r);
+          }
+        }
+      });
+    }
+  });
+
+  results.into_inner().unwrap_or_default()
+}
+
+fn remove_moved_source(
+  src: &Path,
+  dst: &Path,
+  bytes: u64,
+  verify_mode: &str,
+  erase_passes: u32,
+) -> Result<(), String> {
+  if erase_passes > 0 {
+    erase::secure_remove(src, erase_passes).map_err(|e| format!("move cleanup failed: {e}"))?;
+  } else {
+    fs::remove_file(src).map_err(|e| format!("move cleanup failed: {e}"))?;
+  }
+  audit::record(
+    "source_delete",
+    serde_json::json!({
+      "source": src.to_string_lossy(),
+      "dest": dst.to_string_lossy(),
+      "bytes": bytes,
+      "verify_mode": verify_mode,
+      "secure_erase_passes": erase_passes,
+    }),
+  );
+  Ok(())
+}
+
+// Undoes a copy the engine won't stand behind (partial, corrupt, or half of a
+// failed move): removes `dst` and records a "rollback" audit entry with why
+fn rollback_dest(src: &Path, dst: &Path, reason: &str) {
+  match fs::remove_file(dst) {
+    Ok(()) => {
+      logging::info(&format!("{}: removed ({reason})", dst.display()));
+      audit::record(
+        "rollback",
+        serde_json::json!({
+          "source": src.to_string_lossy(),
+          "dest": dst.to_string_lossy(),
+          "reason": reason,
+        }),
+      );
+    }
+    // Never created (e.g. the source failed to open)
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+    Err(e) => logging::warn(&format!("{}: rollback failed: {e}", dst.display())),
+  }
+}
+
+// A move whose source couldn't be removed is undone: the source is still whole,
+// so keeping the copy too would leave the file in two places. With secure erase
+// the source may already be overwritten, and the copy is all that's left.
+fn rollback_failed_move(src: &Path, dst: &Path, erase_passes: u32) {
+  if erase_passes == 0 && src.exists() {
+    rollback_dest(src, dst, "move failed: source could not be removed");
+  }
+}
+
+fn detect_camera_hashes(entries: &[FileEntry]) -> CameraHashes {
+  let camera = camera_hashes::detect(entries.iter().map(|e| e.src.as_path()));
+  if !camera.is_empty() {
+    logging::info(&format!(
+      "{} file(s) covered by {} camera checksum file(s)",
+      camera.len(),
+      camera.files.len()
+    ));
+  }
+  camera
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_camera_hash(
+  camera: &CameraHashes,
+  src: &Path,
+  dst: &Path,
+  bytes: u64,
+  source_checksum: Option<&str>,
+  verify_mode: &str,
+  options: &TransferOptions,
+  watchdog: &Watchdog,
+) -> Option<String> {
+  let hash = camera.get(src)?;
+  camera_hashes::check(
+    hash,
+    src,
+    dst,
+    bytes,
+    source_checksum,
+    verify_mode,
+    options.verify_bypass_cache,
+    watchdog,
+  )
+}
+
+/* ----------------------------------- Queue ---------------------------------- */
+
+// Pops the next file, first moving any files the user prioritized to the front
+// (earliest request ends up first).
+fn next_entry(queue: &mut VecDeque<FileEntry>, job: &JobControl) -> Option<FileEntry> {
+  for id in job.take_priorities().into_iter().rev() {
+    if let Some(pos) = queue.iter().position(|e| e.src.to_string_lossy() == id.as_str()) {
+      if let Some(ent) = queue.remove(pos) {
+        logging::debug(&format!("job {}: prioritized {id}", job.id));
+        queue.push_front(ent);
+      }
+    }
+  }
+  queue.pop_front()
+}
+
+/* --------------------------------- Manifest --------------------------------- */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ManifestItem {
+  pub(crate) source: String,
+  pub(crate) dest: String,
+  pub(crate) category: String,
+  pub(crate) ext: String,
+  pub(crate) bytes: u64,
+  pub(crate) status: String, // copied|moved|skipped|error|cancelled|unverified (dry runs: would_copy|would_move|would_overwrite|would_rename)
+  pub(crate) error: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) checksum: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) checksum_algo: Option<String>,
+  #[serde(flatten)]
+  pub(crate) source_meta: SourceMeta,
+  // See errors::classify; only set on error rows
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) error_class: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) suggestion: Option<String>,
+  // preserve_metadata couldn't restore everything; the copy itself is fine
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) metadata_error: Option<String>,
+  // Attempts after the first (retry_policy, network retries)
+  #[serde(default, skip_serializing_if = "is_zero")]
+  pub(crate) retries: u32,
+  // When this file's copy began and ended (local, RFC 3339); retries included
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) copy_started_at: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub(crate) copy_finished_at: Option<String>,
+}
+
+fn is_zero(n: &u32) -> bool {
+  *n == 0
+}
+
+/* manifest.json is {"manifest_version": 2, "header": {...}, "files": [rows]}: the
+   rows plus how the session was made, for audits that only have the drive.
+   Version 1 was the bare array of rows; load_manifest_rows still reads it. */
+pub(crate) const MANIFEST_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SessionHeader {
+  pub(crate) app_version: String,
+  // Machine the copy ran on
+  pub(crate) source_host: String,
+  pub(crate) created_at: String,
+  pub(crate) copy_mode: String,
+  pub(crate) conflict_policy: String,
+  pub(crate) verify_mode: String,
+  pub(crate) settings: TransferOptions,
+}
+
+impl SessionHeader {
+  fn new(copy_mode: &str, conflict_policy: &str, verify_mode: &str, options: &TransferOptions) -> Self {
+    SessionHeader {
+      app_version: env!("CARGO_PKG_VERSION").to_string(),
+      source_host: sysinfo::System::host_name().unwrap_or_default(),
+      created_at: now_local_rfc3339(),
+      copy_mode: copy_mode.to_string(),
+      conflict_policy: conflict_policy.to_string(),
+      verify_mode: verify_mode.to_string(),
+      settings: options.clone(),
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct ManifestOut<'a> {
+  manifest_version: u32,
+  header: Option<&'a SessionHeader>,
+  files: &'a [ManifestItem],
+}
+
+#[derive(Deserialize)]
+struct ManifestIn {
+  #[serde(default)]
+  header: Option<SessionHeader>,
+  files: Vec<ManifestItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyManifest {
+  V2(ManifestIn),
+  V1(Vec<ManifestItem>),
+}
+
+fn write_manifest(dir: &Path, header: Option<&SessionHeader>, rows: &[ManifestItem]) -> Result<(), String> {
+  let json = serde_json::to_string_pretty(&ManifestOut {
+    manifest_version: MANIFEST_VERSION,
+    header,
+    files: rows,
+  })
+  .map_err(|e| format!("manifest json error: {e}"))?;
+  fs::write(dir.join("manifest.json"), json).map_err(|e| format!("manifest write error: {e}"))
+}
+
+/// Header of a session's manifest.json; for a session that never finished (or
+/// predates v2) it's rebuilt from the selection the run saved.
+fn load_session_header(session_dir: &Path) -> Option<SessionHeader> {
+  if let Some(h) = read_manifest(&session_dir.join("manifest.json")).ok().and_then(|(h, _)| h) {
+    return Some(h);
+  }
+  let plan = plan::import_plan(&session_dir.join(SELECTION_FILE).to_string_lossy()).ok()?.plan;
+  let mut header = SessionHeader::new(&plan.copy_mode, &plan.conflict_policy, &plan.verify_mode, &plan.options);
+  if let Some(at) = plan.created_at {
+    header.created_at = at;
+  }
+  Some(header)
+}
+
+/// A manifest.json of either version; v1 files have no header.
+pub(crate) fn read_manifest(path: &Path) -> Result<(Option<SessionHeader>, Vec<ManifestItem>), String> {
+  let s = fs::read_to_string(path).map_err(|e| format!("manifest read error: {e}"))?;
+  match serde_json::from_str(&s).map_err(|e| format!("manifest parse error: {e}"))? {
+    AnyManifest::V2(m) => Ok((m.header, m.files)),
+    AnyManifest::V1(rows) => Ok((None, rows)),
+  }
+}
+
+impl ManifestItem {
+  /// Fills `error_class`/`suggestion` from `error` on error rows.
+  pub(crate) fn classify(&mut self) {
+    match self.error.as_deref().filter(|_| self.status == "error") {
+      Some(e) => {
+        let c = errors::classify(e);
+        self.error_class = Some(c.class.to_string());
+        self.suggestion = Some(c.suggestion.to_string());
+      }
+      None => {
+        self.error_class = None;
+        self.suggestion = None;
+      }
+    }
+  }
+
+  /// Marks the row failed with `e`.
+  pub(crate) fn fail(&mut self, e: String) {
+    self.status = "error".to_string();
+    self.error = Some(e);
+    self.classify();
+  }
+}
+
+// Source-side attributes, so the manifest alone is enough to audit (or rebuild)
+// the transfer without the original media.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct SourceMeta {
+  // Source modification time, RFC 3339
+  pub(crate) mtime: Option<String>,
+  // Allocated bytes on the source volume (blocks * 512 on unix)
+  pub(crate) size_on_disk: Option<u64>,
+  // Unix permission bits as octal, e.g. "644"
+  pub(crate) permissions: Option<String>,
+  pub(crate) readonly: bool,
+}
+
+impl SourceMeta {
+  fn from_metadata(meta: &fs::Metadata) -> Self {
+    let mtime = meta
+      .modified()
+      .ok()
+      .map(|t| chrono::DateTime::<chrono::Local>::from(t).to_rfc3339());
+
+    #[cfg(unix)]
+    let (size_on_disk, permissions) = {
+      use std::os::unix::fs::MetadataExt;
+      (Some(meta.blocks() * 512), Some(format!("{:o}", meta.mode() & 0o7777)))
+    };
+    #[cfg(not(unix))]
+    let (size_on_disk, permissions) = (None, None);
+
+    SourceMeta {
+      mtime,
+      size_on_disk,
+      permissions,
+      readonly: meta.permissions().readonly(),
+    }
+  }
+}
+
+fn emit_file_error(app: &AppHandle, job_id: &str, src: &str, dst: &str, error: &str, will_retry: bool, attempt: u32) {
+  events::emit(
+    app,
+    events::FILE_ERROR,
+    &FileErrorEvent {
+      job_id: job_id.to_string(),
+      source: src.to_string(),
+      dest: dst.to_string(),
+      error: error.to_string(),
+      error_class: errors::classify(error).class.to_string(),
+      will_retry,
+      attempt,
+    },
+  );
+}
+
+// Every row that ends up an error also goes out as a final file-error event
+fn emit_manifest_row(app: &AppHandle, job_id: &str, journal: &Journal, row_index: usize, item: &ManifestItem) {
+  if item.status == "error" {
+    let error = item.error.as_deref().unwrap_or("error");
+    emit_file_error(app, job_id, &item.source, &item.dest, error, false, item.retries + 1);
+  }
+  events::emit(
+    app,
+    events::MANIFEST_ROW,
+    &ManifestRowEvent {
+      job_id,
+      session_dir: journal.session_dir().to_string_lossy().to_string(),
+      row_index,
+      item,
+    },
+  );
+}
+
+fn push_row(
+  app: &AppHandle,
+  job_id: &str,
+  journal: &Journal,
+  manifest: &mut Vec<ManifestItem>,
+  mut item: ManifestItem,
+) {
+  item.classify();
+  journal.append(&item);
+  emit_manifest_row(app, job_id, journal, manifest.len(), &item);
+  manifest.push(item);
+}
+
+/// A session dir given as itself or as a file in it (its manifest.json).
+fn session_dir_of(p: PathBuf) -> PathBuf {
+  if p.is_dir() {
+    p
+  } else {
+    p.parent().map(|d| d.to_path_buf()).unwrap_or_default()
+  }
+}
+
+/// Rows of a session's manifest.json, or of its journal when the session never
+/// finished (last journal line per source wins).
+pub(crate) fn load_manifest_rows(session_dir: &Path) -> Result<Vec<ManifestItem>, String> {
+  let manifest_path = session_dir.join("manifest.json");
+  if manifest_path.exists() {
+    return read_manifest(&manifest_path).map(|(_, rows)| rows);
+  }
+
+  let journal_path = session_dir.join(journal::JOURNAL_FILE);
+  if journal_path.exists() {
+    let mut rows: Vec<ManifestItem> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for row in journal::read_rows::<ManifestItem>(&journal_path)? {
+      match index.get(&row.source) {
+        Some(&i) => rows[i] = row,
+        None => {
+          index.insert(row.source.clone(), rows.len());
+          rows.push(row);
+        }
+      }
+    }
+    return Ok(rows);
+  }
+
+  Err(format!("no manifest found in {}", session_dir.display()))
+}
+
+// Files that made it to the original destination, read back from there (falling
+// back to the original source), keeping their place in the session layout.
+fn entries_from_manifest(session_dir: &Path, rows: &[ManifestItem]) -> Vec<FileEntry> {
+  let mut missing = 0u64;
+  let entries: Vec<FileEntry> = rows
+    .iter()
+    .filter(|r| r.status == "copied" || r.status == "moved")
+    .filter_map(|r| {
+      let dest = PathBuf::from(&r.dest);
+      let src = if dest.is_file() {
+        dest.clone()
+      } else if Path::new(&r.source).is_file() {
+        PathBuf::from(&r.source)
+      } else {
+        missing += 1;
+        return None;
+      };
+      let folder_rel = dest
+        .strip_prefix(session_dir)
+        .ok()
+        .and_then(|rel| rel.strip_prefix("Folders").ok())
+        .map(|rel| rel.to_path_buf());
+      Some(FileEntry {
+        src,
+        folder_rel,
+        dest: None,
+        burst: None,
+      })
+    })
+    .collect();
+
+  if missing > 0 {
+    logging::warn(&format!("{missing} manifest row(s) have neither dest nor source on disk"));
+  }
+  entries
+}
+
+/* ---------------------------------- Layout ---------------------------------- */
+
+fn group_bursts(entries: &mut [FileEntry], options: &TransferOptions) {
+  if !matches!(options.burst_grouping.as_str(), "folder" | "prefix") {
+    return;
+  }
+  let bursts = bursts::detect(entries.iter().filter(|e| e.dest.is_none()).map(|e| e.src.as_path()));
+  for ent in entries.iter_mut() {
+    // Already grouped (e.g. replaying a session that used burst folders)
+    let parent_name = ent.src.parent().and_then(|p| p.file_name()).map(|s| s.to_os_string());
+    ent.burst = bursts
+      .get(&ent.src)
+      .filter(|label| parent_name.as_deref() != Some(std::ffi::OsStr::new(label.as_str())))
+      .cloned();
+  }
+  logging::debug(&format!(
+    "burst grouping: {} file(s) in bursts",
+    entries.iter().filter(|e| e.burst.is_some()).count()
+  ));
+}
+
+// Path of a file inside the session dir:
+// - Loose files: Files/<filename>
+// - Folder picks: Folders/<TopFolder>/<relative>
+// - With a layout template: <expanded template>/<filename>
+// - Audio, when organize_audio is on: Music/<Artist>/<Album>/<filename>
+// - Flatten: Files/<filename> for everything
+// - Matching a routing rule: <rule dir>/<filename> (checked before the audio and template layouts)
+// Burst frames then go into <dir>/Burst_<first frame>/ or get that label as a prefix.
+fn layout_rel(ent: &FileEntry, options: &TransferOptions) -> PathBuf {
+  let source = naming::NameSource::new(&ent.src, ent.folder_rel.as_deref(), options.project.as_deref());
+  let file_name = ent
+    .src
+    .file_name()
+    .and_then(|s| s.to_str())
+    .unwrap_or("file");
+
+  let rel = if options.flatten {
+    PathBuf::from("Files").join(file_name)
+  } else if let Some(dir) = naming::route_dir(&options.routes, &source) {
+    dir.join(file_name)
+  } else if options.organize_audio && category_for(&ent.src).0 == "Audio" {
+    naming::audio_dir(&source).join(file_name)
+  } else if let Some(t) = options.layout_template.as_deref().filter(|t| !t.trim().is_empty()) {
+    naming::layout_dir(t, &source).join(file_name)
+  } else if let Some(rel) = ent.folder_rel.clone() {
+    PathBuf::from("Folders").join(rel)
+  } else {
+    PathBuf::from("Files").join(file_name)
+  };
+
+  let rel = match options.rename_template.as_deref().filter(|t| !t.trim().is_empty()) {
+    Some(t) => rel.with_file_name(naming::file_name(t, &source)),
+    None => rel,
+  };
+
+  let rel = match (ent.burst.as_deref(), options.burst_grouping.as_str()) {
+    (Some(label), "folder") if !options.flatten => {
+      let name = rel.file_name().map(|s| s.to_os_string()).unwrap_or_default();
+      rel.with_file_name(label).join(name)
+    }
+    (Some(label), "prefix") => {
+      let name = rel.file_name().and_then(|s| s.to_str()).unwrap_or("file");
+      rel.with_file_name(format!("{label}_{name}"))
+    }
+    _ => rel,
+  };
+
+  match options.rename_prefix.as_deref().filter(|p| !p.is_empty()) {
+    Some(prefix) => {
+      let name = rel.file_name().and_then(|s| s.to_str()).unwrap_or("file");
+      rel.with_file_name(format!("{prefix}{name}"))
+    }
+    None => rel,
+  }
+}
+
+/* ---------------------------------- Session --------------------------------- */
+
+// The session dir a run started now would get, without creating it. The run
+// folder's name is a guess (the real one is stamped at start), but its length isn't.
+fn planned_session_dir(dest_mount_point: &str, options: &TransferOptions) -> PathBuf {
+  let (day, run) = session_stamps(&options.session_naming);
+  match options.dest_subdir.as_deref().filter(|d| !d.is_empty()) {
+    Some(sub) => PathBuf::from(dest_mount_point).join(sub),
+    None => PathBuf::from(dest_mount_point),
+  }
+  .join("Transfers")
+  .join(day)
+  .join(run)
+}
+
+fn create_session_dir(dest_base: &str, naming: &str) -> Result<PathBuf, String> {
+  // Folder layout: Transfers/YYYY-MM-DD/HHMMSS/ (see session_stamps for the UTC forms)
+  let (day, run) = session_stamps(naming);
+
+  let transfers_root = PathBuf::from(dest_base).join("Transfers");
+  let day_dir = transfers_root.join(&day);
+  ensure_dir(&day_dir)?;
+
+  // Several jobs (e.g. two cards at once, or two app instances) can start in the
+  // same second; each must get its own dir, so claim it with create_dir rather than
+  // create_dir_all. create_dir is atomic, so two racing claims can't both win.
+  let mut session_dir = day_dir.join(&run);
+  let mut n = 2;
+  loop {
+    match fs::create_dir(&session_dir) {
+      Ok(_) => break,
+      Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && n < 100 => {
+        session_dir = day_dir.join(format!("{run}-{n}"));
+        n += 1;
+      }
+      // Out of numbered names: a short random suffix can't collide in practice
+      Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && n < 110 => {
+        let tag = uuid::Uuid::new_v4().simple().to_string();
+        session_dir = day_dir.join(format!("{run}-{}", &tag[..8]));
+        n += 1;
+      }
+      Err(e) => return Err(format!("mkdir error: {e}")),
+    }
+  }
+  if n > 2 {
+    logging::info(&format!("session dir {run} was taken; using {}", session_dir.display()));
+  }
+
+  // Write Transfers/README.txt once
+  let readme_path = transfers_root.join("README.txt");
+  if !readme_path.exists() {
+    let contents = "\
+TransferPilot output
+
+Folder layout:
+  Transfers/<YYYY-MM-DD>/<HHMMSS>/
+    - Files/      (loose files you added directly)
+    - Folders/    (folder picks; preserves the folder tree)
+    - manifest.json
+    - summary.json
+
+Pointers:
+  Transfers/_latest.txt -> most recent run folder
+  Transfers/<YYYY-MM-DD>/_latest.txt -> most recent run for that day
+";
+    let _ = fs::write(&readme_path, contents);
+  }
+
+  // Latest pointers
+  fs::write(
+    transfers_root.join("_latest.txt"),
+    session_dir.to_string_lossy().to_string(),
+  )
+  .map_err(|e| format!("latest write error: {e}"))?;
+  fs::write(
+    day_dir.join("_latest.txt"),
+    session_dir.to_string_lossy().to_string(),
+  )
+  .map_err(|e| format!("day latest write error: {e}"))?;
+
+  Ok(session_dir)
+}
+
+// The run's original selection + settings, saved as a plan so an interrupted
+// session can be resumed in place (see resume_session).
+pub(crate) const SELECTION_FILE: &str = "selection.json";
+
+/// Bookkeeping files a session dir (or Transfers/) holds besides the copied files.
+pub(crate) const SESSION_FILES: &[&str] = &[
+  "manifest.json",
+  journal::JOURNAL_FILE,
+  "summary.json",
+  SELECTION_FILE,
+  session_state::STATE_FILE,
+  OFFLOAD_REPORT_FILE,
+  crate::report::REPORT_HTML_FILE,
+  crate::report::REPORT_PDF_FILE,
+  crate::report::HASHDEEP_FILE,
+  crate::report::MANIFEST_CSV_FILE,
+  "README.txt",
+  "_latest.txt",
+  quota::QUOTA_FILE,
+];
+
+fn write_selection(
+  session_dir: &Path,
+  items: &[PickedItem],
+  dest_mount_point: &str,
+  copy_mode: &str,
+  conflict_policy: &str,
+  verify_mode: &str,
+  options: &TransferOptions,
+) {
+  let selection = plan::TransferPlan {
+    version: plan::PLAN_VERSION,
+    name: None,
+    created_at: Some(now_local_rfc3339()),
+    items: items.to_vec(),
+    dest_mount_point: Some(dest_mount_point.to_string()),
+    copy_mode: copy_mode.to_string(),
+    conflict_policy: conflict_policy.to_string(),
+    verify_mode: verify_mode.to_string(),
+    options: options.clone(),
+  };
+  let res = serde_json::to_string_pretty(&selection)
+    .map_err(|e| e.to_string())
+    .and_then(|json| fs::write(session_dir.join(SELECTION_FILE), json).map_err(|e| e.to_string()));
+  if let Err(e) = res {
+    logging::warn(&format!("selection write error: {e}"));
+  }
+}
+
+/* ------------------------------- Revalidation ------------------------------- */
+
+/// Totals the UI showed from preflight_scan, passed back to start_transfer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreflightTotals {
+  pub total_files: u64,
+  pub total_bytes: u64,
+}
+
+// Files may have been added, changed or deleted since the preflight the user
+// looked at. Emits `transfer://plan-stale` when the totals moved, and refuses to
+// start if the new totals no longer fit on the destination.
+fn revalidate(
+  app: &AppHandle,
+  job: &JobControl,
+  entries: &[FileEntry],
+  expected: &PreflightTotals,
+  dest_mount_point: &str,
+  options: &TransferOptions,
+) -> Result<(), String> {
+  let actual_files = entries.len() as u64;
+  let actual_bytes: u64 = entries
+    .iter()
+    .filter_map(|e| fs::metadata(&e.src).ok())
+    .map(|m| m.len())
+    .sum();
+  if actual_files == expected.total_files && actual_bytes == expected.total_bytes {
+    return Ok(());
+  }
+
+  logging::warn(&format!(
+    "selection changed since preflight: {} file(s)/{} bytes then, {actual_files}/{actual_bytes} now",
+    expected.total_files, expected.total_bytes
+  ));
+  events::emit(
+    app,
+    events::PLAN_STALE,
+    &PlanStaleEvent {
+      job_id: job.id.clone(),
+      expected_files: expected.total_files,
+      expected_bytes: expected.total_bytes,
+      actual_files,
+      actual_bytes,
+    },
+  );
+
+  if actual_bytes > expected.total_bytes {
+    let (total, avail) = space::space_for_mount(dest_mount_point)?;
+    let others = app
+      .state::<space::Reservations>()
+      .held(&space::volume_key(dest_mount_point), Some(&job.id));
+    let needed = actual_bytes
+      .saturating_add(space::reserve_bytes(options, total))
+      .saturating_add(others);
+    if avail < needed {
+      return Err(format!(
+        "selection grew since preflight and no longer fits: {needed} bytes needed, {avail} free"
+      ));
+    }
+  }
+  Ok(())
+}
+
+/* ---------------------------------- Dry run --------------------------------- */
+/* With dry_run, start_transfer takes every decision a real run would (layout,
+   flatten clashes, conflict policy, cloud placeholders, free space, destination
+   filesystem limits) and writes the manifest it would have produced, plus a
+   summary, to <app data>/dry_runs/<stamp>/. Nothing on the destination is
+   created or touched. Row statuses say what would happen: "would_copy",
+   "would_move", "would_overwrite", "would_rename", "skipped" or "error";
+   checksum_algo is the hash each copy would be verified with. */
+
+#[allow(clippy::too_many_arguments)]
+fn dry_run(
+  app: &AppHandle,
+  job_id: &str,
+  items: &[PickedItem],
+  mut entries: Vec<FileEntry>,
+  dest_mount_point: &str,
+  copy_mode: &str,
+  conflict_policy: &str,
+  verify_mode: &str,
+  options: &TransferOptions,
+) -> Result<TransferSummary, String> {
+  let started_at = now_local_rfc3339();
+  let start = Instant::now();
+  if copy_mode == "move" && options.secure_erase_passes > 0 && Algo::from_verify_mode(verify_mode).is_none() {
+    return Err("secure move needs a hash verify_mode".to_string());
+  }
+  if !cloud::POLICIES.contains(&options.cloud_placeholders.as_str()) {
+    return Err(format!("unknown cloud_placeholders policy: {}", options.cloud_placeholders));
+  }
+  order_entries(&mut entries, &options.ordering);
+  group_bursts(&mut entries, options);
+
+  let session_dir = planned_session_dir(dest_mount_point, options);
+  let limits = fscompat::Limits::for_mount(dest_mount_point);
+  let (dest_total, dest_avail) = space::space_for_mount(dest_mount_point).unwrap_or((0, 0));
+  let usable = dest_avail.saturating_sub(space::reserve_bytes(options, dest_total));
+  let mtime_tol_ms = if matches!(conflict_policy, "newer" | "sync") {
+    timestamps::tolerance_ms(options, items.iter().map(|it| it.path.as_str()), dest_mount_point)
+  } else {
+    0
+  };
+  let checksum_algo = Algo::from_verify_mode(verify_mode).map(|_| verify_mode.to_string());
+  let new_status = if copy_mode == "move" { "would_move" } else { "would_copy" };
+
+  let mut planned: HashSet<PathBuf> = HashSet::new();
+  let mut needed: u64 = 0;
+  let mut rows: Vec<ManifestItem> = Vec::with_capacity(entries.len());
+  for ent in &entries {
+    let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
+    let bytes = meta.len();
+    let (category, ext) = category_for(&ent.src);
+    let mut dst = ent.dest.clone().unwrap_or_else(|| session_dir.join(layout_rel(ent, options)));
+    if options.flatten && ent.dest.is_none() && planned.contains(&dst) {
+      dst = unique_path_where(&dst, &|p| p.exists() || planned.contains(p));
+    }
+
+    let mut status = new_status;
+    let mut error: Option<String> = None;
+    let placeholder = cloud::placeholder_kind(&ent.src, &meta);
+    if placeholder == Some("icloud_stub") {
+      status = "error";
+      error = Some("iCloud placeholder: download the file in Finder first".to_string());
+    } else if placeholder.is_some() && options.cloud_placeholders != "hydrate" {
+      status = if options.cloud_placeholders == "skip" { "skipped" } else { "error" };
+      error = Some("online-only cloud placeholder".to_string());
+    } else if dst.exists() || planned.contains(&dst) {
+      let keep_existing = matches!(conflict_policy, "newer" | "sync")
+        && match (fs::metadata(&ent.src), fs::metadata(&dst)) {
+          (Ok(s), Ok(d)) => !timestamps::should_replace(conflict_policy, &s, &d, mtime_tol_ms),
+          _ => false,
+        };
+      match conflict_policy {
+        "overwrite" => status = "would_overwrite",
+        "newer" | "sync" if !keep_existing => status = "would_overwrite",
+        "skip" | "newer" | "sync" => status = "skipped",
+        _ => {
+          dst = unique_path_where(&dst, &|p| p.exists() || planned.contains(p));
+          status = "would_rename";
+        }
+      }
+    }
+    if status.starts_with("would_") {
+      if let Some(w) = limits.check(&ent.src, &dst, bytes).into_iter().next() {
+        status = "error";
+        error = Some(w.message);
+      } else if needed.saturating_add(bytes) > usable {
+        status = "error";
+        error = Some("would not fit: destination (less its reserve) is full by this point".to_string());
+      } else {
+        needed += bytes;
+      }
+    }
+    planned.insert(dst.clone());
+
+    let mut row = ManifestItem {
+      source: ent.src.to_string_lossy().to_string(),
+      dest: dst.to_string_lossy().to_string(),
+      category,
+      ext,
+      bytes,
+      status: status.to_string(),
+      error,
+      checksum: None,
+      checksum_algo: checksum_algo.clone().filter(|_| status.starts_with("would_")),
+      source_meta: SourceMeta::from_metadata(&meta),
+      error_class: None,
+      suggestion: None,
+      metadata_error: None,
+      retries: 0,
+      copy_started_at: None,
+      copy_finished_at: None,
+    };
+    row.classify();
+    rows.push(row);
+  }
+
+  let dir = app
+    .path()
+    .app_data_dir()
+    .map_err(|e| format!("app data dir error: {e}"))?
+    .join("dry_runs")
+    .join(chrono::Local::now().format("%Y-%m-%d_%H%M%S").to_string());
+  ensure_dir(&dir)?;
+  let header = SessionHeader::new(copy_mode, conflict_policy, verify_mode, options);
+  write_manifest(&dir, Some(&header), &rows)?;
+
+  let count = |s: &str| rows.iter().filter(|r| r.status == s).count() as u64;
+  let would_copy = rows.iter().filter(|r| r.status.starts_with("would_")).count() as u64;
+  let finished_at = now_local_rfc3339();
+  let summary = TransferSummary {
+    job_id: job_id.to_string(),
+    started_at_utc: utc_rfc3339(&started_at),
+    finished_at_utc: utc_rfc3339(&finished_at),
+    started_at,
+    finished_at,
+    duration_ms: start.elapsed().as_millis() as u64,
+    total_files: rows.len() as u64,
+    total_bytes: rows.iter().map(|r| r.bytes).sum(),
+    copied_files: if copy_mode == "move" { 0 } else { would_copy },
+    moved_files: if copy_mode == "move" { would_copy } else { 0 },
+    skipped_files: count("skipped"),
+    error_files: count("error"),
+    unverified_files: 0,
+    output_session_dir: dir.to_string_lossy().to_string(),
+    dry_run: true,
+    samples: vec![],
+  };
+  write_summary(&dir, &summary);
+  logging::info(&format!(
+    "dry run: {would_copy} to copy, {} skipped, {} error(s); manifest in {}",
+    summary.skipped_files,
+    summary.error_files,
+    dir.display()
+  ));
+  Ok(summary)
+}
+
+/* --------------------------------- Transfer --------------------------------- */
+
+/// `expected`, when given, is what preflight reported; the selection is rescanned
+/// and checked against it before anything is copied.
+#[allow(clippy::too_many_arguments)]
+pub async fn start_transfer(
+  app: tauri::AppHandle,
+  items: Vec<PickedItem>,
+  dest_mount_point: String,
+  copy_mode: String,
+  conflict_policy: String,
+  verify_mode: String,
+  options: TransferOptions,
+  expected: Option<PreflightTotals>,
+  job: Arc<JobControl>,
+) -> Result<TransferSummary, String> {
+  let items = with_contents_only(items, &options);
+  let entries = scan_entries(&items, &options)?;
+  if let Some(exp) = expected.as_ref() {
+    revalidate(&app, &job, &entries, exp, &dest_mount_point, &options)?;
+  }
+  if options.dry_run {
+    return dry_run(
+      &app,
+      &job.id,
+      &items,
+      entries,
+      &dest_mount_point,
+      &copy_mode,
+      &conflict_policy,
+      &verify_mode,
+      &options,
+    );
+  }
+
+  run_transfer(
+    app,
+    items,
+    Some(entries),
+    SessionTarget::New(dest_mount_point),
+    copy_mode,
+    conflict_policy,
+    verify_mode,
+    options,
+    job,
+  )
+  .await
+}
+
+/// Re-runs a previous session (its manifest.json, or journal if it never finished)
+/// or a saved plan file against `new_dest`. Manifest replays copy what actually
+/// landed on the first drive, preserving the Files/ and Folders/ layout.
+pub async fn start_from_manifest(
+  app: tauri::AppHandle,
+  path: String,
+  new_dest: String,
+  verify_mode: Option<String>,
+  options: Option<TransferOptions>,
+  job: Arc<JobControl>,
+) -> Result<TransferSummary, String> {
+  let p = PathBuf::from(&path);
+
+  // Plan file: a JSON object with "items" (a v2 manifest is an object too)
+  if p.is_file() {
+    let text = fs::read_to_string(&p).map_err(|e| format!("read error: {e}"))?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("parse error: {e}"))?;
+    if value.is_object() && value.get("manifest_version").is_none() {
+      let imported = plan::import_plan(&path)?;
+      let plan = imported.plan;
+      return run_transfer(
+        app,
+        plan.items,
+        None,
+        SessionTarget::New(new_dest),
+        plan.copy_mode,
+        plan.conflict_policy,
+        verify_mode.unwrap_or(plan.verify_mode),
+        options.unwrap_or(plan.options),
+        job,
+      )
+      .await;
+    }
+  }
+
+  let session_dir = session_dir_of(p);
+  let rows = load_manifest_rows(&session_dir)?;
+  let entries = entries_from_manifest(&session_dir, &rows);
+  let items: Vec<PickedItem> = entries
+    .iter()
+    .map(|e| PickedItem {
+      kind: "file".to_string(),
+      path: e.src.to_string_lossy().to_string(),
+      contents_only: false,
+    })
+    .collect();
+
+  run_transfer(
+    app,
+    items,
+    Some(entries),
+    SessionTarget::New(new_dest),
+    "copy".to_string(),
+    "rename".to_string(),
+    verify_mode.unwrap_or_else(|| checksum::DEFAULT_VERIFY_MODE.to_string()),
+    options.unwrap_or_default(),
+    job,
+  )
+  .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_transfer(
+  app: tauri::AppHandle,
+  items: Vec<PickedItem>,
+  prescanned: Option<Vec<FileEntry>>,
+  target: SessionTarget,
+  copy_mode: String,
+  conflict_policy: String,
+  verify_mode: String,
+  options: TransferOptions,
+  job: Arc<JobControl>,
+) -> Result<TransferSummary, String> {
+  let _debug = logging::DebugGuard::new(options.debug);
+  let items = with_contents_only(items, &options);
+  let cancel = job.cancel.clone();
+  job.limit.set_rate(options.max_bytes_per_sec);
+  events::job(&app, &job.id, "transfer", "started", None);
+  let started_at = now_local_rfc3339();
+  let start = Instant::now();
+
+  logging::info(&format!(
+    "transfer start: {} item(s) (mode={copy_mode}, conflict={conflict_policy}, verify={verify_mode})",
+    items.len()
+  ));
+  if copy_mode == "move" && options.secure_erase_passes > 0 && Algo::from_verify_mode(&verify_mode).is_none() {
+    return Err("secure move needs a hash verify_mode".to_string());
+  }
+
+  emit_progress(
+    &app,
+    &job,
+    &TransferProgress {
+      phase: "scanning".to_string(),
+      current_file: 0,
+      total_files: 0,
+      current_path: "".to_string(),
+      bytes_done: 0,
+      bytes_total: 0,
+      percent: 0.0,
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
+    },
+  );
+
+  let mut entries = match prescanned {
+    Some(e) => e,
+    None => scan_entries(&items, &options)?,
+  };
+  order_entries(&mut entries, &options.ordering);
+  group_bursts(&mut entries, &options);
+  logging::debug(&format!("scan found {} file(s), ordering={}", entries.len(), options.ordering));
+  let camera = detect_camera_hashes(&entries);
+
+  // precompute total_bytes
+  let mut total_bytes: u64 = 0;
+  let mut placeholders = 0u64;
+  for ent in &entries {
+    let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
+    total_bytes = total_bytes.saturating_add(meta.len());
+    if cloud::placeholder_kind(&ent.src, &meta).is_some() {
+      placeholders += 1;
+    }
+  }
+  if !cloud::POLICIES.contains(&options.cloud_placeholders.as_str()) {
+    return Err(format!("unknown cloud_placeholders policy: {}", options.cloud_placeholders));
+  }
+  if placeholders > 0 && options.cloud_placeholders == "fail" {
+    return Err(format!(
+      "{placeholders} selected file(s) are online-only cloud placeholders; download them first or change cloud_placeholders"
+    ));
+  }
+
+  let (mut session_dir, dest_mount_point, base_rows, replace) = match target {
+    SessionTarget::New(dest) => {
+      quota::enforce(&dest, total_bytes)?;
+      let base = match options.dest_subdir.as_deref().filter(|d| !d.is_empty()) {
+        Some(sub) => PathBuf::from(&dest).join(sub),
+        None => PathBuf::from(&dest),
+      };
+      let dir = create_session_dir(&base.to_string_lossy(), &options.session_naming)?;
+      write_selection(&dir, &items, &dest, &copy_mode, &conflict_policy, &verify_mode, &options);
+      (dir, dest, None, HashSet::new())
+    }
+    SessionTarget::Existing {
+      session_dir,
+      rows,
+      replace,
+    } => {
+      let dir_str = session_dir.to_string_lossy().to_string();
+      let mount = device::mount_point_for(&dir_str).unwrap_or(dir_str);
+      (session_dir, mount, Some(rows), replace)
+    }
+  };
+  logging::info(&format!("session dir: {}", session_dir.display()));
+  job.add_session_dir(&session_dir);
+
+  let total_files = entries.len() as u64;
+
+  audit::record(
+    "transfer_start",
+    serde_json::json!({
+      "session_dir": session_dir.to_string_lossy(),
+      "sources": items.iter().map(|it| it.path.clone()).collect::<Vec<_>>(),
+      "total_files": total_files,
+      "total_bytes": total_bytes,
+      "copy_mode": copy_mode,
+      "verify_mode": verify_mode,
+    }),
+  );
+
+  // initial “copying” emit so bar appears instantly
+  emit_progress(
+    &app,
+    &job,
+    &TransferProgress {
+      phase: "copying".to_string(),
+      current_file: 0,
+      total_files,
+      current_path: "".to_string(),
+      bytes_done: 0,
+      bytes_total: total_bytes,
+      percent: 0.0,
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
+    },
+  );
+
+  let mut manifest: Vec<ManifestItem> = vec![];
+  let mut journal = Journal::open(&session_dir)?;
+  let mut state = StateFile::create(
+    &session_dir,
+    entries.iter().map(|e| e.src.to_string_lossy().to_string()).collect(),
+    base_rows
+      .iter()
+      .flatten()
+      .filter(|r| matches!(r.status.as_str(), "copied" | "moved" | "skipped"))
+      .map(|r| r.source.clone())
+      .collect(),
+  );
+  // Source of the file being handled; finished once the loop moves past it
+  let mut in_flight: Option<String> = None;
+
+  let mut copied_files = 0u64;
+  let mut moved_files = 0u64;
   let mut skipped_files = 0u64;
   let mut error_files = 0u64;
+  let mut unverified_files = 0u64;
+
+  let mut bytes_done: u64 = 0;
+
+  // Remember which physical drive we're writing to so a replug can be recognised
+  let mut dest_root = dest_mount_point.clone();
+  let dest_is_mount = device::is_mounted(&dest_root);
+  let dest_uuid = if dest_is_mount { device::volume_uuid(&dest_root) } else { None };
+  let mut dest_space = SpaceMonitor::new(&app, &job.id, &dest_root, &options, total_bytes);
+
+  // Per-source-device read caps, keyed by each picked item's volume
+  let source_limiters: Vec<(PathBuf, Arc<RateLimiter>)> = if options.device_bytes_per_sec > 0 {
+    items
+      .iter()
+      .filter_map(|it| {
+        device::mount_point_for(&it.path)
+          .map(|m| (PathBuf::from(&it.path), throttle::device_limiter(&m, options.device_bytes_per_sec)))
+      })
+      .collect()
+  } else {
+    vec![]
+  };
+
+  let watchdog = Watchdog::spawn(
+    app.clone(),
+    &job.id,
+    Duration::from_secs(options.stall_timeout_secs),
+    job.stall_action.clone(),
+  );
+
+  let dest_network = options.network_tuning && netfs::is_network_path(&dest_root);
+  let mut durability = Policy::parse(&options.durability)?;
+  if dest_network {
+    logging::info(&format!("{dest_root} is a network mount; using network I/O tuning"));
+    if durability == Policy::PerFile {
+      durability = Policy::PerBatch;
+    }
+  }
+  let durability = durability.for_copy_mode(&copy_mode);
+  let emit_rate = EmitRate::from_options(&options);
+  let buf_bytes = emit_rate.cap_buf(netfs::buf_bytes(dest_network));
+
+  // Hash the source as it's copied so verifying only reads the copy back
+  let hash_while_copying = Algo::from_verify_mode(&verify_mode);
+
+  let mtime_tol_ms = if matches!(conflict_policy.as_str(), "newer" | "sync") {
+    let tol = timestamps::tolerance_ms(&options, items.iter().map(|it| it.path.as_str()), &dest_root);
+    logging::debug(&format!("mtime tolerance for {conflict_policy}: {tol} ms"));
+    tol
+  } else {
+    0
+  };
+  // Copied but not yet fsynced (deferred durability policies): (manifest index, dest, bytes)
+  let mut pending_sync: Vec<(usize, PathBuf, u64)> = vec![];
+
+  let mut stopped_early = false;
+  let mut pending_verify: Vec<PendingVerify> = vec![];
+  // Flatten: dests already handed out this run (or by the session being continued)
+  let mut flat_taken: HashSet<PathBuf> = base_rows
+    .iter()
+    .flatten()
+    .map(|r| PathBuf::from(&r.dest))
+    .filter(|d| !replace.contains(d))
+    .collect();
+
+  let mut queue: VecDeque<FileEntry> = entries.into();
+  let mut i: usize = 0;
+  let workers = options.concurrency.max(1) as usize;
+  let mut batch: Vec<Planned> = vec![];
+  // Batched copies start at `concurrency` workers and follow throughput from there
+  let copy_limit =
+    (options.adaptive_workers && workers > 1).then(|| AdaptiveLimit::new(workers, max_adaptive_workers().max(workers)));
+
+  loop {
+    let next = next_entry(&mut queue, &job);
+    if next.is_none() && batch.is_empty() {
+      break;
+    }
+
+    // Per file: decide where it goes (or why it doesn't), then copy and finish it.
+    // With concurrency > 1 small files are collected into a batch first and copied
+    // by copy_parallel; they're finished here afterwards, in queue order.
+    let mut inline: Option<Planned> = None;
+
+    // Soft cancel: the previous file is fully copied + verified, so nothing new is
+    // planned. Files already batched are still copied and finished below (on a hard
+    // cancel they get "cancelled" rows instead), then the loop ends.
+    let halt = match &next {
+      Some(ent) if job.should_stop() || cancel.load(Ordering::SeqCst) => {
+        if let Some(prev) = in_flight.take() {
+          state.complete(&prev);
+        }
+        if cancel.load(Ordering::SeqCst) {
+          emit_progress(
+            &app,
+            &job,
+            &TransferProgress {
+              phase: "cancelled".to_string(),
+              current_file: (i as u64) + 1,
+              total_files,
+              current_path: ent.src.to_string_lossy().to_string(),
+              bytes_done,
+              bytes_total: total_bytes,
+              percent: pct(bytes_done, total_bytes),
+              io: None,
+              bytes_per_sec: 0,
+              eta_seconds: None,
+              elapsed_ms: 0,
+            },
+          );
+        } else {
+          logging::info(&format!("job {}: stopped after {i} file(s)", job.id));
+          stopped_early = true;
+        }
+        if batch.is_empty() {
+          break;
+        }
+        true
+      }
+      _ => false,
+    };
+
+    if let Some(ent) = next.filter(|_| !halt) {
+      let current_file = (i as u64) + 1;
+      i += 1;
+      if let Some(prev) = in_flight.take() {
+        state.complete(&prev);
+      }
+
+      in_flight = Some(ent.src.to_string_lossy().to_string());
+      let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
+      let bytes = meta.len();
+      let src_meta = SourceMeta::from_metadata(&meta);
+      let (cat, ext) = category_for(&ent.src);
+
+      // Destination keeps folder trees together
+      // - Loose files: Transfers/<day>/<run>/Files/<filename>
+      // - Folder picks: Transfers/<day>/<run>/Folders/<TopFolder>/<relative>
+      let mut dst = match &ent.dest {
+        Some(d) => d.clone(),
+        None => {
+          let dst_rel = layout_rel(&ent, &options);
+          session_dir.join(&dst_rel)
+        }
+      };
+      logging::trace(&format!("[{current_file}/{total_files}] {} -> {}", ent.src.display(), dst.display()));
+
+      // Skipped by the user before we got to it
+      let item_id = ent.src.to_string_lossy().to_string();
+      if job.is_skipped(&item_id) {
+        skipped_files += 1;
+        bytes_done = bytes_done.saturating_add(bytes);
+        push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+          source: item_id,
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status: "skipped".to_string(),
+          error: Some("skipped by user".to_string()),
+          checksum: None,
+          checksum_algo: None,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error: None,
+          retries: 0,
+          copy_started_at: None,
+          copy_finished_at: None,
+        });
+        continue;
+      }
+
+      // Online-only cloud files: skipped by policy, or (iCloud stubs) not readable at all
+      let placeholder = cloud::placeholder_kind(&ent.src, &meta);
+      if placeholder == Some("icloud_stub") || (placeholder.is_some() && options.cloud_placeholders == "skip") {
+        let stub = placeholder == Some("icloud_stub");
+        if stub {
+          error_files += 1;
+        } else {
+          skipped_files += 1;
+        }
+        bytes_done = bytes_done.saturating_add(bytes);
+        push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+          source: item_id,
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status: if stub { "error" } else { "skipped" }.to_string(),
+          error: Some(if stub {
+            "iCloud placeholder: download the file in Finder first".to_string()
+          } else {
+            "online-only cloud placeholder".to_string()
+          }),
+          checksum: None,
+          checksum_algo: None,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error: None,
+          retries: 0,
+          copy_started_at: None,
+          copy_finished_at: None,
+        });
+        continue;
+      }
+      if placeholder.is_some() {
+        logging::info(&format!("{}: cloud placeholder, downloading while copying", ent.src.display()));
+      }
+
+      // Flatten: two sources with the same name must never land on one file,
+      // whatever the conflict policy says about files from earlier runs
+      if options.flatten && ent.dest.is_none() && flat_taken.contains(&dst) {
+        dst = flatten_unique_path(&dst, &ent.src, options.flatten_hash);
+      }
+      if options.flatten {
+        flat_taken.insert(dst.clone());
+      }
+
+      // Conflict handling
+      if dst.exists() && !replace.contains(&dst) {
+        let keep_existing = matches!(conflict_policy.as_str(), "newer" | "sync")
+          && match (fs::metadata(&ent.src), fs::metadata(&dst)) {
+            (Ok(s), Ok(d)) => !timestamps::should_replace(&conflict_policy, &s, &d, mtime_tol_ms),
+            _ => false,
+          };
+        match conflict_policy.as_str() {
+          "overwrite" => {}
+          "newer" | "sync" if !keep_existing => {}
+          "skip" | "newer" | "sync" => {
+            skipped_files += 1;
+            push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+              source: ent.src.to_string_lossy().to_string(),
+              dest: dst.to_string_lossy().to_string(),
+              category: cat,
+              ext,
+              bytes,
+              status: "skipped".to_string(),
+              error: None,
+              checksum: None,
+              checksum_algo: None,
+              source_meta: src_meta.clone(),
+              error_class: None,
+              suggestion: None,
+              metadata_error: None,
+              retries: 0,
+              copy_started_at: None,
+              copy_finished_at: None,
+            });
+            continue;
+          }
+          _ => {
+            dst = unique_dest_path(&dst);
+          }
+        }
+      }
+
+      // Leave the configured reserve free; a smaller file later on may still fit
+      if let Err(e) = dest_space.check(bytes) {
+        logging::warn(&format!("{}: {e}", ent.src.display()));
+        error_files += 1;
+        bytes_done = bytes_done.saturating_add(bytes);
+        push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+          source: item_id,
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status: "error".to_string(),
+          error: Some(e),
+          checksum: None,
+          checksum_algo: None,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error: None,
+          retries: 0,
+          copy_started_at: None,
+          copy_finished_at: None,
+        });
+        continue;
+      }
+
+      let limiter = source_limiters
+        .iter()
+        .filter(|(root, _)| ent.src.starts_with(root))
+        .max_by_key(|(root, _)| root.as_os_str().len())
+        .map(|(_, l)| l.clone());
+      let planned = Planned {
+        ent,
+        dst,
+        cat,
+        ext,
+        bytes,
+        src_meta,
+        current_file,
+        limiter,
+      };
+      if workers > 1 && bytes <= PARALLEL_MAX_BYTES {
+        // Only queued: it's in flight once the batch is copied and finished
+        in_flight = None;
+        batch.push(planned);
+        let batch_bytes: u64 = batch.iter().map(|p| p.bytes).sum();
+        if batch.len() < PARALLEL_BATCH_FILES && batch_bytes < PARALLEL_BATCH_BYTES {
+          continue;
+        }
+      } else {
+        inline = Some(planned);
+      }
+    }
+
+    let mut ready: Vec<(Planned, Option<(Result<Option<String>, String>, CopyTimes)>)> = vec![];
+    if !batch.is_empty() && cancel.load(Ordering::SeqCst) {
+      // Hard cancel before the batch was copied: nothing of it is on the drive
+      ready.extend(batch.drain(..).map(|p| (p, Some((Err("cancelled".to_string()), CopyTimes::default())))));
+    } else if !batch.is_empty() {
+      let results = copy_parallel(
+        &app,
+        &batch,
+        &job,
+        &watchdog,
+        workers,
+        copy_limit.as_ref(),
+        durability == Policy::PerFile,
+        hash_while_copying,
+        bytes_done,
+        total_bytes,
+        total_files,
+        emit_rate,
+      );
+      bytes_done = bytes_done.saturating_add(batch.iter().map(|p| p.bytes).sum());
+      ready.extend(batch.drain(..).zip(results).map(|(p, r)| (p, Some(r))));
+      // A cancelled copy ends the run, so let the files that did finish get their rows first
+      ready.sort_by_key(|(_, r)| matches!(r, Some((Err(e), _)) if e == "cancelled"));
+    }
+    ready.extend(inline.map(|p| (p, None)));
+
+    for (planned, pre) in ready {
+      let Planned {
+        ent,
+        mut dst,
+        cat,
+        ext,
+        bytes,
+        src_meta,
+        current_file,
+        limiter,
+      } = planned;
+
+      // Copy streamed (cancel-aware); batched files come back already copied
+      let mut status = "copied".to_string();
+      let mut err: Option<String> = None;
+      let mut file_checksum: Option<String> = None;
+
+      // Batched files are already counted in bytes_done (and timed)
+      let batched = pre.is_some();
+      if batched {
+        if let Some(prev) = in_flight.replace(ent.src.to_string_lossy().to_string()) {
+          state.complete(&prev);
+        }
+      }
+      let (pre, mut times) = match pre {
+        Some((r, t)) => (Some(r), t),
+        None => (None, CopyTimes::default()),
+      };
+      let bytes_before = if batched { bytes_done.saturating_sub(bytes) } else { bytes_done };
+      let mut retries = 0u32;
+      // A batched file that failed gets its retries on the streamed path
+      let pre = match pre {
+        Some(Err(e))
+          if options.retry_policy.max_attempts > 1 && e != "cancelled" && e != "skipped" && errors::is_retryable(&e) =>
+        {
+          retries = 1;
+          emit_file_error(&app, &job.id, &ent.src.to_string_lossy(), &dst.to_string_lossy(), &e, true, 1);
+          logging::warn(&format!(
+            "{}: {e}; retry 1/{}",
+            ent.src.display(),
+            options.retry_policy.max_attempts - 1
+          ));
+          thread::sleep(options.retry_policy.delay(1));
+          bytes_done = bytes_before;
+          None
+        }
+        other => other,
+      };
+      let copy_res = match pre {
+        Some(r) => r,
+        None => {
+          // A batched file retried here is timed from its first try
+          if times.started.is_none() {
+            times = CopyTimes::start();
+          }
+          // emit start-of-file so UI updates immediately
+          emit_progress(
+            &app,
+            &job,
+            &TransferProgress {
+              phase: "copying".to_string(),
+              current_file,
+              total_files,
+              current_path: ent.src.to_string_lossy().to_string(),
+              bytes_done,
+              bytes_total: total_bytes,
+              percent: pct(bytes_done, total_bytes),
+              io: None,
+              bytes_per_sec: 0,
+              eta_seconds: None,
+              elapsed_ms: 0,
+            },
+          );
+
+          watchdog.set_current(&ent.src.to_string_lossy());
+          let mut net_attempt = 0u32;
+          // Only the first attempt picks up an earlier run's checkpoint; stall retries start the file over
+          let mut resume_from = state.partial().and_then(|p| p.resume_offset(&ent.src, &dst));
+          let r = loop {
+            let r = copy_file_streamed(
+              &ent.src,
+              &dst,
+              &job,
+              &mut bytes_done,
+              total_bytes,
+              &app,
+              current_file,
+              total_files,
+              &watchdog,
+              limiter.as_deref(),
+              durability == Policy::PerFile,
+              buf_bytes,
+              emit_rate,
+              resume_from.take(),
+              Some(&state),
+              hash_while_copying,
+            );
+            // User asked to retry a stalled file: start it over from byte 0
+            if matches!(&r, Err(e) if e == "retry") {
+              logging::info(&format!("retrying stalled file {}", ent.src.display()));
+              bytes_done = bytes_before;
+              watchdog.set_current(&ent.src.to_string_lossy());
+              continue;
+            }
+            // A share that dropped for a moment usually comes back; try the file again
+            if dest_network && net_attempt < netfs::NETWORK_RETRIES && matches!(&r, Err(e) if netfs::is_transient(e)) {
+              net_attempt += 1;
+              logging::warn(&format!(
+                "network error on {}, retry {net_attempt}/{}: {}",
+                ent.src.display(),
+                netfs::NETWORK_RETRIES,
+                r.as_ref().err().map(|e| e.as_str()).unwrap_or("")
+              ));
+              let e = r.as_ref().err().map(|e| e.as_str()).unwrap_or("");
+              emit_file_error(&app, &job.id, &ent.src.to_string_lossy(), &dst.to_string_lossy(), e, true, retries + 1);
+              netfs::backoff(net_attempt);
+              retries += 1;
+              bytes_done = bytes_before;
+              watchdog.set_current(&ent.src.to_string_lossy());
+              continue;
+            }
+
+            // Destination vanished mid-file: pause until the same drive is back, then redo this file
+            if matches!(&r, Err(e) if e != "cancelled") && dest_is_mount && !device::is_mounted(&dest_root) {
+              emit_progress(
+                &app,
+                &job,
+                &TransferProgress {
+                  phase: "paused".to_string(),
+                  current_file,
+                  total_files,
+                  current_path: ent.src.to_string_lossy().to_string(),
+                  bytes_done: bytes_before,
+                  bytes_total: total_bytes,
+                  percent: pct(bytes_before, total_bytes),
+                  io: None,
+                  bytes_per_sec: 0,
+                  eta_seconds: None,
+                  elapsed_ms: 0,
+                },
+              );
+
+              match device::wait_for_reattach(&app, &job.id, &dest_root, dest_uuid.as_deref(), &cancel) {
+                Some(new_root) => {
+                  session_dir = rebase_path(&session_dir, &dest_root, &new_root);
+                  dst = rebase_path(&dst, &dest_root, &new_root);
+                  dest_root = new_root;
+                  dest_space = SpaceMonitor::new(&app, &job.id, &dest_root, &options, total_bytes.saturating_sub(bytes_before));
+                  job.add_session_dir(&session_dir);
+                  journal = Journal::open(&session_dir)?;
+                  state.move_to(&session_dir);
+                  bytes_done = bytes_before;
+                  watchdog.set_current(&ent.src.to_string_lossy());
+                  continue;
+                }
+                None => break Err("cancelled".to_string()),
+              }
+            }
+
+            // Flaky media: try the file again, from its last synced checkpoint if it has one
+            if retries + 1 < options.retry_policy.max_attempts && matches!(&r, Err(e) if errors::is_retryable(e)) {
+              let e = r.as_ref().err().map(|e| e.as_str()).unwrap_or("");
+              emit_file_error(&app, &job.id, &ent.src.to_string_lossy(), &dst.to_string_lossy(), e, true, retries + 1);
+              retries += 1;
+              logging::warn(&format!(
+                "{}: {}; retry {retries}/{}",
+                ent.src.display(),
+                r.as_ref().err().map(|e| e.as_str()).unwrap_or(""),
+                options.retry_policy.max_attempts - 1
+              ));
+              thread::sleep(options.retry_policy.delay(retries));
+              bytes_done = bytes_before;
+              resume_from = state.partial().and_then(|p| p.resume_offset(&ent.src, &dst));
+              watchdog.set_current(&ent.src.to_string_lossy());
+              continue;
+            }
+            break r;
+          };
+          times.finish();
+          r
+        }
+      };
+
+      let mut metadata_error: Option<String> = None;
+      let mut src_checksum: Option<String> = None;
+      match copy_res {
+        Ok(h) => {
+          src_checksum = h;
+          dest_space.consumed(bytes);
+          if options.preserve_metadata {
+            metadata_error = preserve::apply(&ent.src, &dst).err();
+            if let Some(e) = &metadata_error {
+              logging::warn(&format!("{}: metadata not fully preserved: {e}", dst.display()));
+            }
+          }
+        }
+        Err(e) => {
+          if e == "cancelled" {
+            push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+              source: ent.src.to_string_lossy().to_string(),
+              dest: dst.to_string_lossy().to_string(),
+              category: cat,
+              ext,
+              bytes,
+              status: "cancelled".to_string(),
+              error: None,
+              checksum: None,
+              checksum_algo: None,
+              source_meta: src_meta.clone(),
+              error_class: None,
+              suggestion: None,
+              metadata_error: None,
+              retries,
+              copy_started_at: times.started.clone(),
+              copy_finished_at: times.finished.clone(),
+            });
+            emit_progress(
+              &app,
+              &job,
+              &TransferProgress {
+                phase: "cancelled".to_string(),
+                current_file,
+                total_files,
+                current_path: ent.src.to_string_lossy().to_string(),
+                bytes_done,
+                bytes_total: total_bytes,
+                percent: pct(bytes_done, total_bytes),
+                io: None,
+                bytes_per_sec: 0,
+                eta_seconds: None,
+                elapsed_ms: 0,
+              },
+            );
+            // The rest of a cancelled batch still gets its rows (none of it is complete)
+            if batched {
+              in_flight = None;
+              continue;
+            }
+            break;
+          } else if e == "skipped" {
+            // Skipped mid-file: drop the partial copy and count its bytes as handled
+            // (a batched file is skipped before its dest is touched)
+            if !batched {
+              rollback_dest(&ent.src, &dst, "skipped mid-file");
+            }
+            skipped_files += 1;
+            bytes_done = bytes_before.saturating_add(bytes);
+            push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+              source: ent.src.to_string_lossy().to_string(),
+              dest: dst.to_string_lossy().to_string(),
+              category: cat,
+              ext,
+              bytes,
+              status: "skipped".to_string(),
+              error: Some("skipped by user".to_string()),
+              checksum: None,
+              checksum_algo: None,
+              source_meta: src_meta.clone(),
+              error_class: None,
+              suggestion: None,
+              metadata_error: None,
+              retries,
+              copy_started_at: times.started.clone(),
+              copy_finished_at: times.finished.clone(),
+            });
+            continue;
+          } else {
+            err = Some(e);
+          }
+        }
+      }
+
+      // Batch strategy: copy everything first, verify in a dedicated pass afterwards
+      let deferred = err.is_none() && options.verify_strategy == "batch" && verify_mode != "none";
+
+      // Verify + move cleanup
+      if err.is_none() && !deferred {
+        if Algo::from_verify_mode(&verify_mode).is_some() {
+          emit_progress(
+            &app,
+            &job,
+            &TransferProgress {
+              phase: "verifying".to_string(),
+              current_file,
+              total_files,
+              current_path: ent.src.to_string_lossy().to_string(),
+              bytes_done,
+              bytes_total: total_bytes,
+              percent: pct(bytes_done, total_bytes),
+              io: None,
+              bytes_per_sec: 0,
+              eta_seconds: None,
+              elapsed_ms: 0,
+            },
+          );
+        }
+
+        let v = verify_copy(&ent.src, &dst, &verify_mode, options.verify_bypass_cache, &watchdog, src_checksum.take())?;
+        if v.mismatch.is_some() {
+          rollback_dest(&ent.src, &dst, "verify mismatch");
+        }
+        err = v.mismatch;
+        file_checksum = v.checksum;
+
+        if err.is_none() {
+          err = check_camera_hash(&camera, &ent.src, &dst, bytes, file_checksum.as_deref(), &verify_mode, &options, &watchdog);
+        }
+
+        if err.is_none() && copy_mode == "move" {
+          match remove_moved_source(&ent.src, &dst, bytes, &verify_mode, options.secure_erase_passes) {
+            Ok(_) => status = "moved".to_string(),
+            Err(e) => {
+              rollback_failed_move(&ent.src, &dst, options.secure_erase_passes);
+              err = Some(e);
+            }
+          }
+        }
+      }
+
+      // Record manifest row
+      if deferred {
+        pending_verify.push(PendingVerify {
+          manifest_idx: manifest.len(),
+          src: ent.src.clone(),
+          dst: dst.clone(),
+          bytes,
+          src_checksum,
+        });
+        manifest.push(ManifestItem {
+          source: ent.src.to_string_lossy().to_string(),
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status,
+          error: None,
+          checksum: None,
+          checksum_algo: None,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error,
+          retries,
+          copy_started_at: times.started.clone(),
+          copy_finished_at: times.finished.clone(),
+        });
+        journal.append(&manifest[manifest.len() - 1]);
+      } else if let Some(e) = err.clone() {
+        logging::warn(&format!("{}: {e}", ent.src.display()));
+        error_files += 1;
+        push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+          source: ent.src.to_string_lossy().to_string(),
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status: "error".to_string(),
+          error: Some(e),
+          checksum_algo: file_checksum.as_ref().map(|_| verify_mode.clone()),
+          checksum: file_checksum,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error,
+          retries,
+          copy_started_at: times.started.clone(),
+          copy_finished_at: times.finished.clone(),
+        });
+      } else {
+        if copy_mode == "move" {
+          moved_files += 1;
+        } else {
+          copied_files += 1;
+        }
+        push_row(&app, &job.id, &journal, &mut manifest, ManifestItem {
+          source: ent.src.to_string_lossy().to_string(),
+          dest: dst.to_string_lossy().to_string(),
+          category: cat,
+          ext,
+          bytes,
+          status,
+          error: None,
+          checksum_algo: file_checksum.as_ref().map(|_| verify_mode.clone()),
+          checksum: file_checksum,
+          source_meta: src_meta.clone(),
+          error_class: None,
+          suggestion: None,
+          metadata_error,
+          retries,
+          copy_started_at: times.started.clone(),
+          copy_finished_at: times.finished.clone(),
+        });
+        if durability.is_deferred() {
+          pending_sync.push((manifest.len() - 1, dst.clone(), bytes));
+        }
+      }
+
+      if durability == Policy::PerBatch
+        && (pending_sync.len() >= durability::BATCH_FILES
+          || pending_sync.iter().map(|p| p.2).sum::<u64>() >= durability::BATCH_BYTES)
+      {
+        sync_pending(&app, &job.id, &journal, &mut manifest, &mut pending_sync, &mut copied_files, &mut error_files);
+      }
+
+      // end-of-file emit (ensures UI catches up)
+      emit_progress(
+        &app,
+        &job,
+        &TransferProgress {
+          phase: "copying".to_string(),
+          current_file,
+          total_files,
+          current_path: "".to_string(),
+          bytes_done,
+          bytes_total: total_bytes,
+          percent: pct(bytes_done, total_bytes),
+          io: None,
+          bytes_per_sec: 0,
+          eta_seconds: None,
+          elapsed_ms: 0,
+        },
+      );
+    }
+    if halt || cancel.load(Ordering::SeqCst) {
+      break;
+    }
+  }
+
+  // Batch verify pass (skipped on hard cancel; a soft stop still verifies what was copied)
+  if !pending_verify.is_empty() {
+    let mut roots: Vec<String> = items.iter().map(|it| it.path.clone()).collect();
+    roots.push(dest_root.clone());
+    let workers = verify_worker_count(options.verify_workers, &roots, pending_verify.len());
+    // A fixed verify_workers is respected as given
+    let adaptive = options.adaptive_workers && options.verify_workers == 0 && pending_verify.len() > 1;
+    logging::debug(&format!(
+      "batch verify: {} file(s) on {workers} worker(s){}",
+      pending_verify.len(),
+      if adaptive { ", adaptive" } else { "" }
+    ));
+
+    let results = verify_parallel(
+      &app,
+      &pending_verify,
+      &verify_mode,
+      options.verify_bypass_cache,
+      &watchdog,
+      &job,
+      workers,
+      adaptive,
+    );
+
+    for (pv, res) in pending_verify.iter().zip(results) {
+      let (mut err, file_checksum) = match res {
+        // Not reached before a hard cancel: fully copied, but nothing vouches for it
+        // (retry_failed copies these again)
+        None => {
+          unverified_files += 1;
+          let row = &mut manifest[pv.manifest_idx];
+          row.status = "unverified".to_string();
+          journal.append(row);
+          emit_manifest_row(&app, &job.id, &journal, pv.manifest_idx, row);
+          continue;
+        }
+        Some(Ok(v)) => {
+          if v.mismatch.is_some() {
+            rollback_dest(&pv.src, &pv.dst, "verify mismatch");
+          }
+          (v.mismatch, v.checksum)
+        }
+        Some(Err(e)) => (Some(format!("verify error: {e}")), None),
+      };
+      if err.is_none() {
+        err = check_camera_hash(
+          &camera,
+          &pv.src,
+          &pv.dst,
+          pv.bytes,
+          file_checksum.as_deref(),
+          &verify_mode,
+          &options,
+          &watchdog,
+        );
+      }
+
+      let mut moved = false;
+      if err.is_none() && copy_mode == "move" {
+        match remove_moved_source(&pv.src, &pv.dst, pv.bytes, &verify_mode, options.secure_erase_passes) {
+          Ok(_) => moved = true,
+          Err(e) => {
+            rollback_failed_move(&pv.src, &pv.dst, options.secure_erase_passes);
+            err = Some(e);
+          }
+        }
+      }
+
+      let row = &mut manifest[pv.manifest_idx];
+      row.checksum_algo = file_checksum.as_ref().map(|_| verify_mode.clone());
+      row.checksum = file_checksum;
+      if let Some(e) = err {
+        logging::warn(&format!("{}: {e}", pv.src.display()));
+        error_files += 1;
+        row.fail(e);
+      } else if moved {
+        moved_files += 1;
+        row.status = "moved".to_string();
+      } else {
+        copied_files += 1;
+        if durability.is_deferred() {
+          pending_sync.push((pv.manifest_idx, pv.dst.clone(), pv.bytes));
+        }
+      }
+      journal.append(row);
+      emit_manifest_row(&app, &job.id, &journal, pv.manifest_idx, row);
+    }
+  }
+
+  if !pending_sync.is_empty() {
+    logging::debug(&format!("durability: syncing {} file(s)", pending_sync.len()));
+    sync_pending(&app, &job.id, &journal, &mut manifest, &mut pending_sync, &mut copied_files, &mut error_files);
+  }
+
+  drop(watchdog);
+
+  // Retry/resume into an existing session: fold this run's rows into its manifest
+  // and recount the whole session
+  let retried_into_existing = base_rows.is_some();
+  let manifest = match base_rows {
+    Some(mut base) => {
+      merge_rows(&mut base, manifest);
+      base
+    }
+    None => manifest,
+  };
+  if retried_into_existing {
+    copied_files = manifest.iter().filter(|r| r.status == "copied").count() as u64;
+    moved_files = manifest.iter().filter(|r| r.status == "moved").count() as u64;
+    skipped_files = manifest.iter().filter(|r| r.status == "skipped").count() as u64;
+    error_files = manifest.iter().filter(|r| r.status == "error").count() as u64;
+    unverified_files = manifest.iter().filter(|r| r.status == "unverified").count() as u64;
+  }
+  let session_total_bytes: u64 = if retried_into_existing {
+    manifest.iter().map(|r| r.bytes).sum()
+  } else {
+    total_bytes
+  };
+
+  // Write manifest; a retry or resume keeps the header of the run that made the session
+  let header = if retried_into_existing {
+    load_session_header(&session_dir)
+  } else {
+    Some(SessionHeader::new(&copy_mode, &conflict_policy, &verify_mode, &options))
+  };
+  write_manifest(&session_dir, header.as_ref(), &manifest)?;
+  // A retry may run on default options: keep a manifest.csv the first run wrote in step
+  if options.manifest_csv || session_dir.join(crate::report::MANIFEST_CSV_FILE).exists() {
+    crate::report::write_manifest_csv(&session_dir, &manifest)?;
+  }
+  journal.finish();
+  // A cancelled file keeps its checkpoint for resume_session
+  let cancelled = cancel.load(Ordering::SeqCst);
+  if let Some(last) = in_flight.filter(|_| !cancelled) {
+    state.complete(&last);
+  }
+  state.finish(!cancelled && !stopped_early);
+  if durability != Policy::None {
+    sync_session_dirs(&session_dir, &manifest);
+  }
+
+  let finished_at = now_local_rfc3339();
+  let duration_ms = start.elapsed().as_millis() as u64;
+
+  logging::info(&format!(
+    "transfer finished in {duration_ms}ms: copied={copied_files} moved={moved_files} skipped={skipped_files} errors={error_files}"
+  ));
+
+  // Final emit
+  let final_phase = if cancel.load(Ordering::SeqCst) {
+    "cancelled"
+  } else if stopped_early {
+    "stopped"
+  } else {
+    "done"
+  };
+
+  if final_phase != "done" {
+    audit::record(
+      "transfer_cancel",
+      serde_json::json!({
+        "job_id": job.id,
+        "soft": final_phase == "stopped",
+        "session_dir": session_dir.to_string_lossy(),
+        "completed_files": copied_files + moved_files,
+        "bytes_done": bytes_done,
+      }),
+    );
+  }
+
+  emit_progress(
+    &app,
+    &job,
+    &TransferProgress {
+      phase: final_phase.to_string(),
+      current_file: total_files,
+      total_files,
+      current_path: session_dir.to_string_lossy().to_string(),
+      bytes_done,
+      bytes_total: total_bytes,
+      percent: if final_phase == "done" { 100.0 } else { pct(bytes_done, total_bytes) },
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
+    },
+  );
+
+  let summary = TransferSummary {
+    job_id: job.id.clone(),
+    started_at_utc: utc_rfc3339(&started_at),
+    finished_at_utc: utc_rfc3339(&finished_at),
+    started_at,
+    finished_at,
+    duration_ms,
+    total_files: copied_files + moved_files + skipped_files + error_files + unverified_files,
+    total_bytes: session_total_bytes,
+    copied_files,
+    moved_files,
+    skipped_files,
+    error_files,
+    unverified_files,
+    output_session_dir: session_dir.to_string_lossy().to_string(),
+    dry_run: false,
+    samples: job.speed_samples(),
+  };
+
+  write_summary(&session_dir, &summary);
+  record_history(
+    &job.id,
+    "transfer",
+    final_phase,
+    &summary,
+    &dest_root,
+    dest_uuid,
+    &copy_mode,
+    &verify_mode,
+    &options,
+    &manifest,
+  );
+  if options.lock_readonly {
+    lock_readonly(&session_dir, &manifest, options.lock_session_dir && final_phase == "done");
+  }
+  if final_phase == "done" {
+    completion::run(&app, &job.id, &options, &summary, &[dest_root.clone()], summary.error_files);
+  }
+
+  Ok(summary)
+}
+
+// Fsyncs files copied under a deferred durability policy. A file that can't be
+// flushed isn't safely on the drive, so its row becomes an error.
+fn sync_pending(
+  app: &AppHandle,
+  job_id: &str,
+  journal: &Journal,
+  manifest: &mut [ManifestItem],
+  pending: &mut Vec<(usize, PathBuf, u64)>,
+  copied_files: &mut u64,
+  error_files: &mut u64,
+) {
+  for (idx, path, _) in pending.drain(..) {
+    if let Err(e) = durability::sync_file(&path) {
+      logging::warn(&format!("{}: {e}", path.display()));
+      let row = &mut manifest[idx];
+      row.fail(e);
+      *copied_files = copied_files.saturating_sub(1);
+      *error_files += 1;
+      journal.append(row);
+      emit_manifest_row(app, job_id, journal, idx, row);
+    }
+  }
+}
+
+// Makes the directory entries durable: every dir we wrote a file into, the session
+// dir (manifest/summary) and its Transfers/<day> parents.
+fn sync_session_dirs(session_dir: &Path, rows: &[ManifestItem]) {
+  let written = rows
+    .iter()
+    .filter(|r| r.status == "copied" || r.status == "moved")
+    .map(|r| Path::new(&r.dest));
+  let extra: Vec<PathBuf> = session_dir.ancestors().take(3).map(|p| p.to_path_buf()).collect();
+  let failed = durability::sync_dirs(written, &extra);
+  if failed > 0 {
+    logging::error(&format!("{failed} director(ies) in {} could not be synced", session_dir.display()));
+  }
+}
+
+fn set_readonly(path: &Path) -> Result<(), String> {
+  let mut perms = fs::metadata(path).map_err(|e| format!("metadata error: {e}"))?.permissions();
+  perms.set_readonly(true);
+  fs::set_permissions(path, perms).map_err(|e| format!("set permissions error: {e}"))
+}
+
+// Archival lock: clears write permission on every verified copy and, with
+// `whole_session`, on everything else in the session dir, deepest first.
+fn lock_readonly(session_dir: &Path, rows: &[ManifestItem], whole_session: bool) {
+  let mut failed = 0;
+  for r in rows.iter().filter(|r| r.status == "copied" || r.status == "moved") {
+    if let Err(e) = set_readonly(Path::new(&r.dest)) {
+      logging::warn(&format!("{}: {e}", r.dest));
+      failed += 1;
+    }
+  }
+  if whole_session {
+    for ent in WalkDir::new(session_dir).contents_first(true).into_iter().flatten() {
+      if let Err(e) = set_readonly(ent.path()) {
+        logging::warn(&format!("{}: {e}", ent.path().display()));
+        failed += 1;
+      }
+    }
+  }
+  if failed > 0 {
+    logging::error(&format!("{failed} item(s) in {} could not be locked read-only", session_dir.display()));
+  }
+}
+
+// Later rows replace earlier ones for the same source; new sources are appended.
+fn merge_rows(base: &mut Vec<ManifestItem>, rows: Vec<ManifestItem>) {
+  let mut index: HashMap<String, usize> = base
+    .iter()
+    .enumerate()
+    .map(|(i, r)| (r.source.clone(), i))
+    .collect();
+  for row in rows {
+    match index.get(&row.source) {
+      Some(&i) => base[i] = row,
+      None => {
+        index.insert(row.source.clone(), base.len());
+        base.push(row);
+      }
+    }
+  }
+}
+
+/// Re-copies only the rows a session recorded as `error` or `unverified`, back
+/// into the same session dir at their recorded destinations, then rewrites
+/// manifest.json and summary.json for the whole session. `session_dir` may also be its manifest.json.
+pub async fn retry_failed(
+  app: tauri::AppHandle,
+  session_dir: String,
+  copy_mode: Option<String>,
+  verify_mode: Option<String>,
+  options: Option<TransferOptions>,
+  job: Arc<JobControl>,
+) -> Result<TransferSummary, String> {
+  let dir = session_dir_of(PathBuf::from(&session_dir));
+  let rows = load_manifest_rows(&dir)?;
+
+  let entries: Vec<FileEntry> = rows
+    .iter()
+    .filter(|r| matches!(r.status.as_str(), "error" | "unverified") && Path::new(&r.source).is_file())
+    .map(|r| FileEntry {
+      src: PathBuf::from(&r.source),
+      folder_rel: None,
+      dest: Some(PathBuf::from(&r.dest)),
+      burst: None,
+    })
+    .collect();
+
+  if entries.is_empty() {
+    return Err("no retryable failed files in this session".to_string());
+  }
+  logging::info(&format!("retrying {} failed file(s) in {}", entries.len(), dir.display()));
+
+  let items: Vec<PickedItem> = entries
+    .iter()
+    .map(|e| PickedItem {
+      kind: "file".to_string(),
+      path: e.src.to_string_lossy().to_string(),
+      contents_only: false,
+    })
+    .collect();
+
+  run_transfer(
+    app,
+    items,
+    Some(entries),
+    SessionTarget::Existing {
+      session_dir: dir,
+      rows,
+      replace: HashSet::new(),
+    },
+    copy_mode.unwrap_or_else(|| "copy".to_string()),
+    // The failed attempt may have left a partial file at the recorded dest
+    "overwrite".to_string(),
+    verify_mode.unwrap_or_else(|| checksum::DEFAULT_VERIFY_MODE.to_string()),
+    options.unwrap_or_default(),
+    job,
+  )
+  .await
+}
+
+/// Copies a session's permission-denied files again with administrator rights
+/// (see elevate.rs), then updates their manifest rows and summary.json. Copies are
+/// checked with the session's verify_mode, or by size where the source still can't
+/// be read without elevation. Files a cancel keeps from being checked are left
+/// "unverified".
+pub fn retry_elevated(app: &AppHandle, session_dir: String, job: &JobControl) -> Result<TransferSummary, String> {
+  let dir = PathBuf::from(&session_dir);
+  let mut rows = load_manifest_rows(&dir)?;
+  for r in rows.iter_mut() {
+    r.classify();
+  }
+
+  let denied: Vec<usize> = (0..rows.len())
+    .filter(|&i| rows[i].error_class.as_deref() == Some("permission_denied"))
+    .collect();
+  if denied.is_empty() {
+    return Err("no permission-denied files in this session".to_string());
+  }
+  if job.is_cancelled() {
+    return Err("cancelled".to_string());
+  }
+  events::job(app, &job.id, "transfer", "started", None);
+  logging::info(&format!("elevated retry of {} file(s) in {session_dir}", denied.len()));
+
+  let pairs: Vec<(PathBuf, PathBuf)> = denied
+    .iter()
+    .map(|&i| (PathBuf::from(&rows[i].source), PathBuf::from(&rows[i].dest)))
+    .collect();
+  elevate::copy_elevated(&pairs)?;
+
+  // Checked the way the session checked its own copies; a "none" session still
+  // gets the size check, as nothing else looked at what the helper wrote
+  let header = load_session_header(&dir);
+  let verify_mode = header
+    .as_ref()
+    .map(|h| h.verify_mode.clone())
+    .filter(|m| m != "none")
+    .unwrap_or_else(|| "size".to_string());
+  let bypass_cache = header.as_ref().is_some_and(|h| h.settings.verify_bypass_cache);
+
+  let total_files = pairs.len() as u64;
+  let total_bytes: u64 = denied.iter().map(|&i| rows[i].bytes).sum();
+  let watchdog = Watchdog::disabled();
+  let mut bytes_done = 0u64;
+  for (n, (&i, (src, dst))) in denied.iter().zip(&pairs).enumerate() {
+    let row = &mut rows[i];
+    // Copied by the helper, but never checked
+    if job.is_cancelled() {
+      row.status = "unverified".to_string();
+      row.error = None;
+      row.classify();
+      continue;
+    }
+    emit_progress(
+      app,
+      job,
+      &TransferProgress {
+        phase: "verifying".to_string(),
+        current_file: n as u64 + 1,
+        total_files,
+        current_path: row.source.clone(),
+        bytes_done,
+        bytes_total: total_bytes,
+        percent: pct(bytes_done, total_bytes),
+        io: None,
+        bytes_per_sec: 0,
+        eta_seconds: None,
+        elapsed_ms: 0,
+      },
+    );
+    bytes_done += row.bytes;
+    let checked = verify_copy(src, dst, &verify_mode, bypass_cache, &watchdog, None).or_else(|e| {
+      logging::warn(&format!("{}: {e}; checking the copy by size only", src.display()));
+      verify_copy(src, dst, "size", false, &watchdog, None)
+    });
+    match checked {
+      Ok(Verified { mismatch: Some(e), .. }) => {
+        rollback_dest(src, dst, &e);
+        row.fail(e);
+      }
+      Ok(Verified { checksum, .. }) => {
+        row.status = "copied".to_string();
+        row.error = None;
+        row.checksum_algo = checksum.as_ref().map(|_| verify_mode.clone());
+        row.checksum = checksum;
+        row.classify();
+      }
+      Err(e) => row.fail(e),
+    }
+  }
+
+  // The header still describes how the session was made
+  write_manifest(&dir, header.as_ref(), &rows)?;
+  // No options here: keep a manifest.csv the run wrote in step
+  if dir.join(crate::report::MANIFEST_CSV_FILE).exists() {
+    crate::report::write_manifest_csv(&dir, &rows)?;
+  }
+
+  let count = |s: &str| rows.iter().filter(|r| r.status == s).count() as u64;
+  let mut summary = crate::report::load_summary(&dir, &rows);
+  summary.job_id = job.id.clone();
+  summary.copied_files = count("copied");
+  summary.moved_files = count("moved");
+  summary.skipped_files = count("skipped");
+  summary.error_files = count("error");
+  summary.unverified_files = count("unverified");
+  summary.total_files =
+    summary.copied_files + summary.moved_files + summary.skipped_files + summary.error_files + summary.unverified_files;
+  write_summary(&dir, &summary);
+  let cancelled = job.is_cancelled();
+  emit_progress(
+    app,
+    job,
+    &TransferProgress {
+      phase: if cancelled { "cancelled" } else { "done" }.to_string(),
+      current_file: total_files,
+      total_files,
+      current_path: summary.output_session_dir.clone(),
+      bytes_done,
+      bytes_total: total_bytes,
+      percent: pct(bytes_done, total_bytes),
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
+    },
+  );
+  audit::record(
+    "transfer_retry_elevated",
+    serde_json::json!({
+      "session_dir": session_dir,
+      "files": denied.len(),
+      "cancelled": cancelled,
+    }),
+  );
+  Ok(summary)
+}
+
+/* ------------------------------ Verify session ------------------------------ */
+/* Re-checks a finished session against its manifest, months later if need be:
+   every copied file must still be on the drive at its recorded size and hash to
+   its recorded checksum. Rows from a "none" or "size" run only get the size
+   check. Nothing on the drive is changed; the outcome goes to the audit log. */
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionVerifyIssue {
+  // "missing" | "size_mismatch" | "checksum_mismatch" | "read_error"
+  pub kind: String,
+  pub source: String,
+  pub dest: String,
+  pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionVerifyReport {
+  pub session_dir: String,
+  // Copied and moved rows; the rest never had a file to check
+  pub files: u64,
+  pub bytes: u64,
+  pub ok: u64,
+  // Of those ok, how many had no recorded checksum and were checked by size only
+  pub size_only: u64,
+  pub issues: Vec<SessionVerifyIssue>,
+  // Cancelled part way: files not reached count as neither ok nor issues
+  pub cancelled: bool,
+}
+
+// Ok(hashed), or the issue's (kind, message)
+fn check_session_row(row: &ManifestItem, watchdog: &Watchdog) -> Result<bool, (&'static str, String)> {
+  let meta = match fs::metadata(&row.dest) {
+    Ok(m) => m,
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Err(("missing", "not on the drive".to_string())),
+    Err(e) => return Err(("read_error", format!("metadata error: {e}"))),
+  };
+  if meta.len() != row.bytes {
+    return Err(("size_mismatch", format!("{} bytes; the manifest says {}", meta.len(), row.bytes)));
+  }
+  match (row.checksum.as_deref(), row.checksum_algo.as_deref().and_then(Algo::from_verify_mode)) {
+    (Some(sum), Some(algo)) => {
+      let h = checksum::hash_file(Path::new(&row.dest), algo, true, watchdog).map_err(|e| ("read_error", e))?;
+      if h.eq_ignore_ascii_case(sum) {
+        Ok(true)
+      } else {
+        Err(("checksum_mismatch", format!("{} is {h}; the manifest says {sum}", algo.name())))
+      }
+    }
+    _ => Ok(false),
+  }
+}
+
+/// Hashes every file a session copied and compares it with the manifest. Sends
+/// "verifying" progress for `job`; cancelling it ends the check early.
+pub fn verify_session(app: &AppHandle, session_dir: String, job: &JobControl) -> Result<SessionVerifyReport, String> {
+  let dir = session_dir_of(PathBuf::from(&session_dir));
+  let rows: Vec<ManifestItem> = load_manifest_rows(&dir)?
+    .into_iter()
+    .filter(|r| matches!(r.status.as_str(), "copied" | "moved" | "unverified"))
+    .collect();
+  if rows.is_empty() {
+    return Err("this session has no copied files".to_string());
+  }
+  events::job(app, &job.id, "verify", "started", None);
+
+  let total_files = rows.len() as u64;
+  let total_bytes: u64 = rows.iter().map(|r| r.bytes).sum();
+  let mut report = SessionVerifyReport {
+    session_dir: dir.to_string_lossy().to_string(),
+    files: total_files,
+    bytes: total_bytes,
+    ..Default::default()
+  };
+  let progress = |phase: &str, current_file: u64, current_path: &str, bytes_done: u64| TransferProgress {
+    phase: phase.to_string(),
+    current_file,
+    total_files,
+    current_path: current_path.to_string(),
+    bytes_done,
+    bytes_total: total_bytes,
+    percent: pct(bytes_done, total_bytes),
+    io: None,
+    bytes_per_sec: 0,
+    eta_seconds: None,
+    elapsed_ms: 0,
+  };
+
+  let watchdog = Watchdog::disabled();
+  let emit_rate = EmitRate::from_options(&TransferOptions::default());
+  emit_progress(app, job, &progress("verifying", 0, "", 0));
+  let mut last_emit = Instant::now();
+  let mut bytes_done = 0u64;
+  for (i, row) in rows.iter().enumerate() {
+    if job.is_cancelled() {
+      report.cancelled = true;
+      break;
+    }
+    if emit_rate.due(last_emit, 0) {
+      emit_progress(app, job, &progress("verifying", i as u64 + 1, &row.dest, bytes_done));
+      last_emit = Instant::now();
+    }
+    match check_session_row(row, &watchdog) {
+      Ok(hashed) => {
+        report.ok += 1;
+        if !hashed {
+          report.size_only += 1;
+        }
+      }
+      Err((kind, message)) => report.issues.push(SessionVerifyIssue {
+        kind: kind.to_string(),
+        source: row.source.clone(),
+        dest: row.dest.clone(),
+        message,
+      }),
+    }
+    bytes_done += row.bytes;
+  }
+
+  let phase = if report.cancelled { "cancelled" } else { "done" };
+  emit_progress(app, job, &progress(phase, total_files, &report.session_dir, bytes_done));
+  logging::info(&format!(
+    "session verify {}: {} ok, {} issue(s){}",
+    dir.display(),
+    report.ok,
+    report.issues.len(),
+    if report.cancelled { " (cancelled)" } else { "" }
+  ));
+  audit::record(
+    "session_verify",
+    serde_json::json!({
+      "session_dir": report.session_dir,
+      "files": report.files,
+      "ok": report.ok,
+      "issues": report.issues.len(),
+      "cancelled": report.cancelled,
+    }),
+  );
+  Ok(report)
+}
+
+// A finished row's copy is still intact: same size, and the same hash where the
+// row recorded one (a crash can leave a full-size file whose tail never hit disk)
+fn copy_intact(row: &ManifestItem) -> bool {
+  if !fs::metadata(&row.dest).is_ok_and(|m| m.len() == row.bytes) {
+    return false;
+  }
+  match (row.checksum.as_deref(), row.checksum_algo.as_deref().and_then(Algo::from_verify_mode)) {
+    (Some(sum), Some(algo)) => checksum::hash_file(Path::new(&row.dest), algo, false, &Watchdog::disabled())
+      .is_ok_and(|h| h.eq_ignore_ascii_case(sum)),
+    _ => true,
+  }
+}
+
+/// Continues an interrupted session in place: rescans its saved selection, skips
+/// files whose copies check out (see copy_intact) and picks the in-flight file
+/// up from its session.state.json checkpoint.
+pub async fn resume_session(
+  app: tauri::AppHandle,
+  session_dir: String,
+  job: Arc<JobControl>,
+) -> Result<TransferSummary, String> {
+  let dir = PathBuf::from(&session_dir);
+  let selection = dir.join(SELECTION_FILE);
+  if !selection.exists() {
+    return Err(format!("{session_dir} has no {SELECTION_FILE}; it can't be resumed"));
+  }
+  let plan = plan::import_plan(&selection.to_string_lossy())?.plan;
+
+  // A crash before the first file finished leaves no manifest at all
+  let rows = load_manifest_rows(&dir).unwrap_or_default();
+  let mut done: HashSet<String> = HashSet::new();
+  // Source -> dest of this session's own copies that get written again in place
+  let mut redo: HashMap<String, PathBuf> = HashMap::new();
+  for r in rows.iter().filter(|r| r.status == "unverified") {
+    redo.insert(r.source.clone(), PathBuf::from(&r.dest));
+  }
+  for r in rows.iter().filter(|r| matches!(r.status.as_str(), "copied" | "moved" | "skipped")) {
+    if r.status == "skipped" || copy_intact(r) {
+      done.insert(r.source.clone());
+    } else if r.status == "moved" {
+      // The source is gone, so there's nothing to copy again
+      logging::warn(&format!("{}: moved copy no longer matches its manifest row", r.dest));
+    } else {
+      logging::warn(&format!("{}: copy doesn't match its manifest row, copying again", r.dest));
+      redo.insert(r.source.clone(), PathBuf::from(&r.dest));
+    }
+  }
+
+  let state = session_state::load(&dir).unwrap_or_default();
+  // The in-flight file goes back to its checkpointed dest
+  if let Some(p) = &state.partial {
+    redo.insert(p.source.clone(), PathBuf::from(&p.dest));
+  }
+
+  let mut entries = scan_entries(&plan.items, &plan.options)?;
+  entries.retain(|e| !done.contains(&*e.src.to_string_lossy()));
+  if entries.is_empty() {
+    return Err("nothing left to copy in this session".to_string());
+  }
+  for e in &mut entries {
+    if let Some(d) = redo.get(&*e.src.to_string_lossy()) {
+      e.dest = Some(d.clone());
+    }
+  }
+
+  let remaining: HashSet<String> = entries.iter().map(|e| e.src.to_string_lossy().to_string()).collect();
+  let vanished = state
+    .queue
+    .iter()
+    .filter(|q| !done.contains(*q) && !remaining.contains(*q))
+    .count();
+  if vanished > 0 {
+    logging::warn(&format!("resuming {session_dir}: {vanished} queued file(s) are no longer at the source"));
+  }
+  logging::info(&format!(
+    "resuming {session_dir}: {} done, {} remaining{}",
+    done.len(),
+    entries.len(),
+    state
+      .partial
+      .map(|p| format!(", {} partly copied ({} bytes)", p.source, p.offset))
+      .unwrap_or_default()
+  ));
+
+  run_transfer(
+    app,
+    plan.items,
+    Some(entries),
+    SessionTarget::Existing {
+      session_dir: dir,
+      rows,
+      replace: redo.into_values().collect(),
+    },
+    plan.copy_mode,
+    // Anything else already at a remaining file's dest gets the session's own policy
+    plan.conflict_policy,
+    plan.verify_mode,
+    plan.options,
+    job,
+  )
+  .await
+}
+
+/* ---------------------------------- Offload --------------------------------- */
+/* DIT-style offload: each source file is read once and teed to a primary and a
+   backup drive, then both copies are hashed and checked against the source hash
+   taken during that single read. Each drive gets a normal session (manifest.json,
+   summary.json); offload_report.json in both lists every file across the pair. */
+
+pub const OFFLOAD_REPORT_FILE: &str = "offload_report.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffloadCopy {
+  pub dest: String,
+  pub status: String, // copied|error|skipped|cancelled
+  pub error: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub error_class: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffloadItem {
+  pub source: String,
+  pub bytes: u64,
+  // Source digest from the single read
+  pub checksum: Option<String>,
+  pub primary: OffloadCopy,
+  pub backup: OffloadCopy,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffloadReport {
+  pub started_at: String,
+  pub finished_at: String,
+  pub duration_ms: u64,
+  pub verify_mode: String,
+  pub total_files: u64,
+  pub total_bytes: u64,
+  // Files that verified on both drives
+  pub verified_files: u64,
+  pub error_files: u64,
+  pub skipped_files: u64,
+  pub primary: TransferSummary,
+  pub backup: TransferSummary,
+  pub items: Vec<OffloadItem>,
+}
+
+// Reads `src` once, hashing it and writing every chunk to each dest. A dest that
+// fails stops receiving data (its error is returned in its slot) while the others
+// carry on; only cancel/skip/retry or a source read error abort the whole file.
+#[allow(clippy::too_many_arguments)]
+fn copy_file_tee(
+  src: &Path,
+  dsts: &[PathBuf],
+  algo: Algo,
+  job: &JobControl,
+  bytes_done: &mut u64,
+  bytes_total: u64,
+  app: &AppHandle,
+  current_file: u64,
+  total_files: u64,
+  watchdog: &Watchdog,
+  limiter: Option<&RateLimiter>,
+  sync: bool,
+  buf_bytes: usize,
+  rate: EmitRate,
+) -> Result<(String, Vec<Option<String>>), String> {
+  let mut in_f = fs::File::open(src).map_err(|e| format!("open src error: {e}"))?;
 
-  let mut bytes_done: u64 = 0;
+  let mut errors: Vec<Option<String>> = vec![None; dsts.len()];
+  let mut outs: Vec<Option<fs::File>> = dsts
+    .iter()
+    .zip(errors.iter_mut())
+    .map(|(dst, err)| {
+      let res = match dst.parent() {
+        Some(parent) => ensure_dir(parent),
+        None => Ok(()),
+      }
+      .and_then(|_| fs::File::create(dst).map_err(|e| format!("create dst error: {e}")));
+      match res {
+        Ok(f) => Some(f),
+        Err(e) => {
+          *err = Some(e);
+          None
+        }
+      }
+    })
+    .collect();
 
-  for (i, ent) in entries.into_iter().enumerate() {
-    let current_file = (i as u64) + 1;
+  let mut hasher = checksum::Hasher::new(algo);
+  let mut buf = vec![0u8; buf_bytes];
+  let mut last_emit = Instant::now();
+  let mut last_emit_bytes = *bytes_done;
+  let item_id = src.to_string_lossy();
 
-    if cancel.load(Ordering::SeqCst) {
+  loop {
+    if job.is_cancelled() {
+      return Err("cancelled".to_string());
+    }
+    if job.is_skipped(&item_id) {
+      return Err("skipped".to_string());
+    }
+    if job.is_paused() {
+      hold_paused(app, job, watchdog, &item_id, current_file, total_files, *bytes_done, bytes_total);
+      continue;
+    }
+
+    match watchdog.take_action() {
+      watchdog::STALL_RETRY => return Err("retry".to_string()),
+      watchdog::STALL_ABORT => {
+        job.cancel.store(true, Ordering::SeqCst);
+        return Err("cancelled".to_string());
+      }
+      _ => {}
+    }
+
+    let t = Instant::now();
+    let n = in_f.read(&mut buf).map_err(|e| format!("read error: {e}"))?;
+    if n == 0 {
+      break;
+    }
+    job.record_read(n as u64, t.elapsed());
+    watchdog.tick();
+    if let Some(l) = limiter {
+      l.consume(n as u64);
+    }
+    job.limit.consume(n as u64);
+    hasher.update(&buf[..n]);
+
+    // The slower of the two drives sets the pace, so the pair counts as one write
+    let t = Instant::now();
+    for (out, err) in outs.iter_mut().zip(errors.iter_mut()) {
+      if let Some(f) = out {
+        if let Err(e) = f.write_all(&buf[..n]) {
+          *err = Some(format!("write error: {e}"));
+          *out = None;
+        }
+      }
+    }
+    job.record_write(n as u64, t.elapsed());
+    if outs.iter().all(|o| o.is_none()) {
+      return Err(errors.iter().flatten().next().cloned().unwrap_or_default());
+    }
+
+    *bytes_done = bytes_done.saturating_add(n as u64);
+    watchdog.tick();
+
+    if rate.due(last_emit, *bytes_done - last_emit_bytes) {
       emit_progress(
-        &app,
+        app,
+        job,
         &TransferProgress {
-          phase: "cancelled".to_string(),
+          phase: "copying".to_string(),
           current_file,
           total_files,
-          current_path: ent.src.to_string_lossy().to_string(),
-          bytes_done,
-          bytes_total: total_bytes,
-          percent: pct(bytes_done, total_bytes),
+          current_path: src.to_string_lossy().to_string(),
+          bytes_done: *bytes_done,
+          bytes_total,
+          percent: pct(*bytes_done, bytes_total),
+          io: job.io_stats(),
+          bytes_per_sec: 0,
+          eta_seconds: None,
+          elapsed_ms: 0,
         },
       );
-      break;
+      last_emit = Instant::now();
+      last_emit_bytes = *bytes_done;
+    }
+  }
+
+  if sync {
+    for (out, err) in outs.into_iter().zip(errors.iter_mut()) {
+      if let Some(f) = out {
+        if let Err(e) = f.sync_all() {
+          *err = Some(format!("sync error: {e}"));
+        }
+      }
+    }
+  }
+  Ok((hasher.finalize(), errors))
+}
+
+fn write_summary(session_dir: &Path, summary: &TransferSummary) {
+  match serde_json::to_string_pretty(summary) {
+    Ok(json) => {
+      if let Err(e) = fs::write(session_dir.join("summary.json"), json) {
+        logging::warn(&format!("summary write error: {e}"));
+      }
     }
+    Err(e) => logging::warn(&format!("summary json error: {e}")),
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_history(
+  job_id: &str,
+  kind: &str,
+  outcome: &str,
+  summary: &TransferSummary,
+  dest_mount_point: &str,
+  dest_uuid: Option<String>,
+  copy_mode: &str,
+  verify_mode: &str,
+  options: &TransferOptions,
+  rows: &[ManifestItem],
+) {
+  let rec = history::HistoryRecord {
+    job_id: job_id.to_string(),
+    kind: kind.to_string(),
+    started_at: summary.started_at.clone(),
+    finished_at: summary.finished_at.clone(),
+    duration_ms: summary.duration_ms,
+    outcome: outcome.to_string(),
+    session_dir: summary.output_session_dir.clone(),
+    dest_mount_point: dest_mount_point.to_string(),
+    dest_uuid,
+    copy_mode: copy_mode.to_string(),
+    verify_mode: verify_mode.to_string(),
+    total_files: summary.total_files,
+    total_bytes: summary.total_bytes,
+    copied_files: summary.copied_files,
+    moved_files: summary.moved_files,
+    skipped_files: summary.skipped_files,
+    error_files: summary.error_files,
+  };
+  history_db::record(&rec, options, rows);
+}
 
+fn side_summary(
+  job_id: &str,
+  rows: &[ManifestItem],
+  session_dir: &Path,
+  started_at: &str,
+  finished_at: &str,
+  duration_ms: u64,
+  samples: Vec<ThroughputSample>,
+) -> TransferSummary {
+  let count = |s: &str| rows.iter().filter(|r| r.status == s).count() as u64;
+  let copied_files = count("copied");
+  let skipped_files = count("skipped");
+  let error_files = count("error");
+  TransferSummary {
+    job_id: job_id.to_string(),
+    started_at: started_at.to_string(),
+    finished_at: finished_at.to_string(),
+    started_at_utc: utc_rfc3339(started_at),
+    finished_at_utc: utc_rfc3339(finished_at),
+    duration_ms,
+    total_files: copied_files + skipped_files + error_files,
+    total_bytes: rows.iter().map(|r| r.bytes).sum(),
+    copied_files,
+    moved_files: 0,
+    skipped_files,
+    error_files,
+    unverified_files: 0,
+    output_session_dir: session_dir.to_string_lossy().to_string(),
+    dry_run: false,
+    samples,
+  }
+}
+
+/// Offloads the selection to `primary_dest` and `backup_dest` in one pass over
+/// the source. Always a copy (the card is never touched) and always hashed:
+/// "none"/"size" fall back to the default algorithm.
+pub async fn start_offload(
+  app: tauri::AppHandle,
+  items: Vec<PickedItem>,
+  primary_dest: String,
+  backup_dest: String,
+  verify_mode: String,
+  options: TransferOptions,
+  job: Arc<JobControl>,
+) -> Result<OffloadReport, String> {
+  let _debug = logging::DebugGuard::new(options.debug);
+  let cancel = job.cancel.clone();
+  job.limit.set_rate(options.max_bytes_per_sec);
+
+  let algo = Algo::from_verify_mode(&verify_mode)
+    .or_else(|| Algo::from_verify_mode(checksum::DEFAULT_VERIFY_MODE))
+    .unwrap_or(Algo::Xxh3);
+  let verify_mode = algo.name().to_string();
+
+  if Path::new(&primary_dest) == Path::new(&backup_dest) {
+    return Err("primary and backup destinations must differ".to_string());
+  }
+
+  events::job(&app, &job.id, "offload", "started", None);
+  let started_at = now_local_rfc3339();
+  let start = Instant::now();
+
+  logging::info(&format!(
+    "offload start: {} item(s) -> {primary_dest} + {backup_dest} (verify={verify_mode})",
+    items.len()
+  ));
+
+  emit_progress(
+    &app,
+    &job,
+    &TransferProgress {
+      phase: "scanning".to_string(),
+      current_file: 0,
+      total_files: 0,
+      current_path: "".to_string(),
+      bytes_done: 0,
+      bytes_total: 0,
+      percent: 0.0,
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
+    },
+  );
+
+  let items = with_contents_only(items, &options);
+  let mut entries = scan_entries(&items, &options)?;
+  order_entries(&mut entries, &options.ordering);
+  group_bursts(&mut entries, &options);
+  let camera = detect_camera_hashes(&entries);
+
+  let mut total_bytes: u64 = 0;
+  for ent in &entries {
     let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
-    let bytes = meta.len();
-    let (cat, ext) = category_for(&ent.src);
+    total_bytes = total_bytes.saturating_add(meta.len());
+  }
+  let total_files = entries.len() as u64;
 
-    // Destination keeps folder trees together
-    // - Loose files: Transfers/<day>/<run>/Files/<filename>
-    // - Folder picks: Transfers/<day>/<run>/Folders/<TopFolder>/<relative>
-    let dst_rel = if let Some(rel) = ent.folder_rel.clone() {
-      PathBuf::from("Folders").join(rel)
-    } else {
-      let file_name = ent
-        .src
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("file");
-      PathBuf::from("Files").join(file_name)
+  let mut session_dirs: Vec<PathBuf> = vec![];
+  for dest in [&primary_dest, &backup_dest] {
+    quota::enforce(dest, total_bytes)?;
+    let base = match options.dest_subdir.as_deref().filter(|d| !d.is_empty()) {
+      Some(sub) => PathBuf::from(dest).join(sub),
+      None => PathBuf::from(dest),
     };
+    let dir = create_session_dir(&base.to_string_lossy(), &options.session_naming)?;
+    write_selection(&dir, &items, dest, "copy", "rename", &verify_mode, &options);
+    logging::info(&format!("offload session dir: {}", dir.display()));
+    job.add_session_dir(&dir);
+    session_dirs.push(dir);
+  }
+  let journals = [Journal::open(&session_dirs[0])?, Journal::open(&session_dirs[1])?];
 
-    let mut dst = session_dir.join(&dst_rel);
+  audit::record(
+    "offload_start",
+    serde_json::json!({
+      "primary_session_dir": session_dirs[0].to_string_lossy(),
+      "backup_session_dir": session_dirs[1].to_string_lossy(),
+      "sources": items.iter().map(|it| it.path.clone()).collect::<Vec<_>>(),
+      "total_files": total_files,
+      "total_bytes": total_bytes,
+      "verify_mode": verify_mode,
+    }),
+  );
 
-    // Conflict handling
-    if dst.exists() {
-      match conflict_policy.as_str() {
-        "overwrite" => {}
-        "skip" => {
-          skipped_files += 1;
-          manifest.push(ManifestItem {
-            source: ent.src.to_string_lossy().to_string(),
-            dest: dst.to_string_lossy().to_string(),
-            category: cat,
-            ext,
-            bytes,
-            status: "skipped".to_string(),
-            error: None,
-          });
-          continue;
-        }
-        _ => {
-          dst = unique_dest_path(&dst);
-        }
-      }
+  let source_limiters: Vec<(PathBuf, Arc<RateLimiter>)> = if options.device_bytes_per_sec > 0 {
+    items
+      .iter()
+      .filter_map(|it| {
+        device::mount_point_for(&it.path)
+          .map(|m| (PathBuf::from(&it.path), throttle::device_limiter(&m, options.device_bytes_per_sec)))
+      })
+      .collect()
+  } else {
+    vec![]
+  };
+
+  let watchdog = Watchdog::spawn(
+    app.clone(),
+    &job.id,
+    Duration::from_secs(options.stall_timeout_secs),
+    job.stall_action.clone(),
+  );
+
+  let mut spaces = [
+    SpaceMonitor::new(&app, &job.id, &primary_dest, &options, total_bytes),
+    SpaceMonitor::new(&app, &job.id, &backup_dest, &options, total_bytes),
+  ];
+
+  // Offloads sync each file before verifying it; only "none" opts out
+  let sync = Policy::parse(&options.durability)? != Policy::None;
+  let dest_network =
+    options.network_tuning && (netfs::is_network_path(&primary_dest) || netfs::is_network_path(&backup_dest));
+  let emit_rate = EmitRate::from_options(&options);
+  let buf_bytes = emit_rate.cap_buf(netfs::buf_bytes(dest_network));
+
+  let mut manifests: [Vec<ManifestItem>; 2] = [vec![], vec![]];
+  let mut report_items: Vec<OffloadItem> = vec![];
+  let mut bytes_done: u64 = 0;
+  let mut stopped_early = false;
+
+  let mut queue: VecDeque<FileEntry> = entries.into();
+  let mut i: usize = 0;
+
+  while let Some(ent) = next_entry(&mut queue, &job) {
+    let current_file = (i as u64) + 1;
+    i += 1;
+
+    if job.should_stop() {
+      logging::info(&format!("job {}: offload stopped after {} file(s)", job.id, i - 1));
+      stopped_early = true;
+      break;
+    }
+    if cancel.load(Ordering::SeqCst) {
+      break;
     }
 
-    // emit start-of-file so UI updates immediately
+    let meta = fs::metadata(&ent.src).map_err(|e| format!("metadata error: {e}"))?;
+    let bytes = meta.len();
+    let src_meta = SourceMeta::from_metadata(&meta);
+    let (cat, ext) = category_for(&ent.src);
+    let source = ent.src.to_string_lossy().to_string();
+
+    let rel = layout_rel(&ent, &options);
+    let dsts: Vec<PathBuf> = session_dirs
+      .iter()
+      .map(|d| {
+        let dst = d.join(&rel);
+        if options.flatten && dst.exists() {
+          flatten_unique_path(&dst, &ent.src, options.flatten_hash)
+        } else {
+          unique_dest_path(&dst)
+        }
+      })
+      .collect();
+
+    let row = |dst: &Path, status: &str, error: Option<String>, checksum: Option<String>| ManifestItem {
+      source: source.clone(),
+      dest: dst.to_string_lossy().to_string(),
+      category: cat.clone(),
+      ext: ext.clone(),
+      bytes,
+      status: status.to_string(),
+      error,
+      checksum_algo: checksum.as_ref().map(|_| verify_mode.clone()),
+      checksum,
+      source_meta: src_meta.clone(),
+      error_class: None,
+      suggestion: None,
+      metadata_error: None,
+      retries: 0,
+      copy_started_at: None,
+      copy_finished_at: None,
+    };
+
     emit_progress(
       &app,
+      &job,
       &TransferProgress {
         phase: "copying".to_string(),
         current_file,
         total_files,
-        current_path: ent.src.to_string_lossy().to_string(),
+        current_path: source.clone(),
         bytes_done,
         bytes_total: total_bytes,
         percent: pct(bytes_done, total_bytes),
+        io: None,
+        bytes_per_sec: 0,
+        eta_seconds: None,
+        elapsed_ms: 0,
       },
     );
 
-    // Copy streamed (cancel-aware)
-    let mut status = "copied".to_string();
-    let mut err: Option<String> = None;
-
-    match copy_file_streamed(
-      &ent.src,
-      &dst,
-      &cancel,
-      &mut bytes_done,
-      total_bytes,
-      &app,
-      current_file,
-      total_files,
-    ) {
-      Ok(_) => {}
-      Err(e) => {
-        if e == "cancelled" {
-          manifest.push(ManifestItem {
-            source: ent.src.to_string_lossy().to_string(),
-            dest: dst.to_string_lossy().to_string(),
-            category: cat,
-            ext,
-            bytes,
-            status: "cancelled".to_string(),
-            error: None,
-          });
-          emit_progress(
-            &app,
-            &TransferProgress {
-              phase: "cancelled".to_string(),
-              current_file,
-              total_files,
-              current_path: ent.src.to_string_lossy().to_string(),
-              bytes_done,
-              bytes_total: total_bytes,
-              percent: pct(bytes_done, total_bytes),
-            },
-          );
-          break;
+    let mut times = CopyTimes::default();
+    let outcome: Result<(String, Vec<Option<String>>), String> = if job.is_skipped(&source) {
+      Err("skipped".to_string())
+    } else if let Some(e) = spaces.iter_mut().find_map(|m| m.check(bytes).err()) {
+      Err(e)
+    } else {
+      watchdog.set_current(&source);
+      let limiter = source_limiters
+        .iter()
+        .filter(|(root, _)| ent.src.starts_with(root))
+        .max_by_key(|(root, _)| root.as_os_str().len())
+        .map(|(_, l)| l.clone());
+      let bytes_before = bytes_done;
+      let mut net_attempt = 0u32;
+      times = CopyTimes::start();
+      let r = loop {
+        let r = copy_file_tee(
+          &ent.src,
+          &dsts,
+          algo,
+          &job,
+          &mut bytes_done,
+          total_bytes,
+          &app,
+          current_file,
+          total_files,
+          &watchdog,
+          limiter.as_deref(),
+          sync,
+          buf_bytes,
+          emit_rate,
+        );
+        if matches!(&r, Err(e) if e == "retry") {
+          logging::info(&format!("retrying stalled file {}", ent.src.display()));
+          bytes_done = bytes_before;
+          watchdog.set_current(&source);
+          continue;
+        }
+        // Write failures come back per destination rather than as Err
+        let transient = match &r {
+          Err(e) => netfs::is_transient(e),
+          Ok((_, write_errors)) => write_errors.iter().flatten().any(|e| netfs::is_transient(e)),
+        };
+        if dest_network && net_attempt < netfs::NETWORK_RETRIES && transient {
+          net_attempt += 1;
+          logging::warn(&format!("network error on {source}, retry {net_attempt}/{}", netfs::NETWORK_RETRIES));
+          netfs::backoff(net_attempt);
+          bytes_done = bytes_before;
+          watchdog.set_current(&source);
+          continue;
+        }
+        if r.is_err() {
+          bytes_done = bytes_before;
         } else {
-          err = Some(e);
+          spaces.iter_mut().for_each(|m| m.consumed(bytes));
         }
-      }
-    }
+        break r;
+      };
+      times.finish();
+      r
+    };
 
-    // Verify + move cleanup
-    if err.is_none() {
-      if verify_mode == "size" {
-        let dst_meta = fs::metadata(&dst).map_err(|e| format!("dst metadata error: {e}"))?;
-        if dst_meta.len() != meta.len() {
-          err = Some("verify failed: size mismatch".to_string());
-        }
-      } else if verify_mode == "sha256" {
+    let (sides, checksum): ([(String, Option<String>); 2], Option<String>) = match outcome {
+      Ok((src_hash, write_errors)) => {
         emit_progress(
           &app,
+          &job,
           &TransferProgress {
             phase: "verifying".to_string(),
             current_file,
             total_files,
-            current_path: ent.src.to_string_lossy().to_string(),
+            current_path: source.clone(),
             bytes_done,
             bytes_total: total_bytes,
             percent: pct(bytes_done, total_bytes),
+            io: None,
+            bytes_per_sec: 0,
+            eta_seconds: None,
+            elapsed_ms: 0,
           },
         );
 
-        let a = sha256_file(&ent.src)?;
-        let b = sha256_file(&dst)?;
-        if a != b {
-          err = Some("verify failed: sha256 mismatch".to_string());
+        // The two copies live on different drives, so read them back concurrently
+        let debug = logging::debug_active();
+        let results: Vec<Option<String>> = thread::scope(|s| {
+          let handles: Vec<_> = dsts
+            .iter()
+            .zip(write_errors)
+            .map(|(dst, write_err)| {
+              let (src_hash, watchdog, src) = (&src_hash, &watchdog, &ent.src);
+              s.spawn(move || {
+                let _debug = logging::DebugGuard::new(debug);
+                if write_err.is_some() {
+                  return write_err;
+                }
+                match checksum::hash_file(dst, algo, options.verify_bypass_cache, watchdog) {
+                  Ok(h) if &h == src_hash => None,
+                  Ok(_) => {
+                    rollback_dest(src, dst, "verify mismatch");
+                    Some(format!("verify failed: {} mismatch", algo.name()))
+                  }
+                  Err(e) => Some(format!("verify error: {e}")),
+                }
+              })
+            })
+            .collect();
+          handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|_| Some("verify error: worker panicked".to_string())))
+            .collect()
+        });
+
+        // Card corruption taints both copies alike
+        let good_copy = dsts.iter().zip(&results).find(|(_, r)| r.is_none()).map(|(d, _)| d);
+        let card_err = good_copy.and_then(|d| {
+          check_camera_hash(&camera, &ent.src, d, bytes, Some(&src_hash), &verify_mode, &options, &watchdog)
+        });
+
+        let side = |err: &Option<String>| match err.as_ref().or(card_err.as_ref()) {
+          Some(e) => ("error".to_string(), Some(e.clone())),
+          None => ("copied".to_string(), None),
+        };
+        ([side(&results[0]), side(&results[1])], Some(src_hash))
+      }
+      // No checkpoint to resume from here: a truncated copy under its final name
+      // would pass for a whole clip on the drive, so both sides are removed
+      Err(e) if e == "cancelled" => {
+        for d in &dsts {
+          rollback_dest(&ent.src, d, "cancelled mid-file");
         }
+        let c = ("cancelled".to_string(), None);
+        ([c.clone(), c], None)
       }
-
-      if err.is_none() && copy_mode == "move" {
-        if let Err(e) = fs::remove_file(&ent.src) {
-          err = Some(format!("move cleanup failed: {e}"));
-        } else {
-          status = "moved".to_string();
+      Err(e) if e == "skipped" => {
+        for d in &dsts {
+          rollback_dest(&ent.src, d, "skipped mid-file");
         }
+        bytes_done = bytes_done.saturating_add(bytes);
+        let s = ("skipped".to_string(), Some("skipped by user".to_string()));
+        ([s.clone(), s], None)
       }
-    }
+      // Read errors and watchdog aborts stop both sides part-way too
+      Err(e) => {
+        for d in &dsts {
+          rollback_dest(&ent.src, d, &format!("copy stopped: {e}"));
+        }
+        let s = ("error".to_string(), Some(e));
+        ([s.clone(), s], None)
+      }
+    };
 
-    // Record manifest row
-    if let Some(e) = err.clone() {
-      error_files += 1;
-      manifest.push(ManifestItem {
-        source: ent.src.to_string_lossy().to_string(),
-        dest: dst.to_string_lossy().to_string(),
-        category: cat,
-        ext,
-        bytes,
-        status: "error".to_string(),
-        error: Some(e),
-      });
-    } else {
-      if copy_mode == "move" {
-        moved_files += 1;
-      } else {
-        copied_files += 1;
+    for (side, (status, error)) in sides.iter().enumerate() {
+      if let Some(e) = error.as_ref().filter(|_| status == "error") {
+        logging::warn(&format!("{} ({}): {e}", ent.src.display(), ["primary", "backup"][side]));
       }
-      manifest.push(ManifestItem {
-        source: ent.src.to_string_lossy().to_string(),
-        dest: dst.to_string_lossy().to_string(),
-        category: cat,
-        ext,
-        bytes,
-        status,
-        error: None,
-      });
+      let mut item = row(&dsts[side], status, error.clone(), checksum.clone());
+      item.copy_started_at = times.started.clone();
+      item.copy_finished_at = times.finished.clone();
+      item.classify();
+      journals[side].append(&item);
+      emit_manifest_row(&app, &job.id, &journals[side], manifests[side].len(), &item);
+      manifests[side].push(item);
     }
 
-    // end-of-file emit (ensures UI catches up)
-    emit_progress(
-      &app,
-      &TransferProgress {
-        phase: "copying".to_string(),
-        current_file,
-        total_files,
-        current_path: "".to_string(),
-        bytes_done,
-        bytes_total: total_bytes,
-        percent: pct(bytes_done, total_bytes),
-      },
-    );
+    let copy = |side: usize| {
+      let row = &manifests[side][manifests[side].len() - 1];
+      OffloadCopy {
+        dest: dsts[side].to_string_lossy().to_string(),
+        status: sides[side].0.clone(),
+        error: sides[side].1.clone(),
+        error_class: row.error_class.clone(),
+        suggestion: row.suggestion.clone(),
+      }
+    };
+    let item = OffloadItem {
+      source,
+      bytes,
+      checksum,
+      primary: copy(0),
+      backup: copy(1),
+    };
+    events::emit(&app, events::OFFLOAD_ITEM, &OffloadItemEvent { job_id: &job.id, item: &item });
+    report_items.push(item);
+
+    if sides.iter().any(|(s, _)| s == "cancelled") {
+      break;
+    }
   }
 
-  // Write manifest
-  let manifest_path = session_dir.join("manifest.json");
-  let manifest_json =
-    serde_json::to_string_pretty(&manifest).map_err(|e| format!("manifest json error: {e}"))?;
-  fs::write(&manifest_path, manifest_json).map_err(|e| format!("manifest write error: {e}"))?;
+  drop(watchdog);
 
   let finished_at = now_local_rfc3339();
   let duration_ms = start.elapsed().as_millis() as u64;
 
-  // Final emit
+  // Offload copies and names clashes apart, as in write_selection above
+  let header = SessionHeader::new("copy", "rename", &verify_mode, &options);
+  let mut summaries = vec![];
+  for (side, journal) in journals.into_iter().enumerate() {
+    let dir = &session_dirs[side];
+    write_manifest(dir, Some(&header), &manifests[side])?;
+    if options.manifest_csv {
+      crate::report::write_manifest_csv(dir, &manifests[side])?;
+    }
+    journal.finish();
+    if sync {
+      sync_session_dirs(dir, &manifests[side]);
+    }
+    let summary = side_summary(&job.id, &manifests[side], dir, &started_at, &finished_at, duration_ms, job.speed_samples());
+    write_summary(dir, &summary);
+    summaries.push(summary);
+  }
+
+  let verified_files = report_items
+    .iter()
+    .filter(|it| it.primary.status == "copied" && it.backup.status == "copied")
+    .count() as u64;
+  let skipped_files = report_items.iter().filter(|it| it.primary.status == "skipped").count() as u64;
+  let error_files = report_items
+    .iter()
+    .filter(|it| it.primary.status == "error" || it.backup.status == "error")
+    .count() as u64;
+
+  let backup = summaries.pop().unwrap_or_else(|| side_summary(&job.id, &[], &session_dirs[1], &started_at, &finished_at, duration_ms, vec![]));
+  let primary = summaries.pop().unwrap_or_else(|| side_summary(&job.id, &[], &session_dirs[0], &started_at, &finished_at, duration_ms, vec![]));
+
+  let report = OffloadReport {
+    started_at,
+    finished_at,
+    duration_ms,
+    verify_mode: verify_mode.clone(),
+    total_files,
+    total_bytes,
+    verified_files,
+    error_files,
+    skipped_files,
+    primary,
+    backup,
+    items: report_items,
+  };
+
+  match serde_json::to_string_pretty(&report) {
+    Ok(json) => {
+      for dir in &session_dirs {
+        if let Err(e) = fs::write(dir.join(OFFLOAD_REPORT_FILE), &json) {
+          logging::warn(&format!("offload report write error: {e}"));
+        }
+      }
+    }
+    Err(e) => logging::warn(&format!("offload report json error: {e}")),
+  }
+
+  logging::info(&format!(
+    "offload finished in {duration_ms}ms: verified={verified_files} skipped={skipped_files} errors={error_files}"
+  ));
+
   let final_phase = if cancel.load(Ordering::SeqCst) {
     "cancelled"
+  } else if stopped_early {
+    "stopped"
   } else {
     "done"
   };
 
+  let sides = [(&report.primary, &primary_dest), (&report.backup, &backup_dest)];
+  for (side, (summary, dest)) in sides.into_iter().enumerate() {
+    let mount = device::mount_point_for(dest).unwrap_or_else(|| dest.clone());
+    let uuid = device::volume_uuid(&mount);
+    record_history(
+      &job.id,
+      "offload",
+      final_phase,
+      summary,
+      &mount,
+      uuid,
+      "copy",
+      &verify_mode,
+      &options,
+      &manifests[side],
+    );
+  }
+
+  if final_phase != "done" {
+    audit::record(
+      "transfer_cancel",
+      serde_json::json!({
+        "job_id": job.id,
+        "soft": final_phase == "stopped",
+        "session_dir": session_dirs[0].to_string_lossy(),
+        "completed_files": verified_files,
+        "bytes_done": bytes_done,
+      }),
+    );
+  }
+
   emit_progress(
     &app,
+    &job,
     &TransferProgress {
       phase: final_phase.to_string(),
       current_file: total_files,
       total_files,
-      current_path: session_dir.to_string_lossy().to_string(),
+      current_path: session_dirs[0].to_string_lossy().to_string(),
       bytes_done,
       bytes_total: total_bytes,
       percent: if final_phase == "done" { 100.0 } else { pct(bytes_done, total_bytes) },
+      io: None,
+      bytes_per_sec: 0,
+      eta_seconds: None,
+      elapsed_ms: 0,
     },
   );
 
-  Ok(TransferSummary {
-    started_at,
-    finished_at,
-    duration_ms,
-    total_files: copied_files + moved_files + skipped_files + error_files,
-    total_bytes,
-    copied_files,
-    moved_files,
-    skipped_files,
-    error_files,
-    output_session_dir: session_dir.to_string_lossy().to_string(),
-  })
-}
\ No newline at end of file
+  if options.lock_readonly {
+    for (dir, rows) in session_dirs.iter().zip(&manifests) {
+      lock_readonly(dir, rows, options.lock_session_dir && final_phase == "done");
+    }
+  }
+  if final_phase == "done" {
+    completion::run(&app, &job.id, &options, &report.primary, &[primary_dest, backup_dest], report.error_files);
+  }
+
+  Ok(report)
+}
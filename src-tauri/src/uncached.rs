@@ -0,0 +1,86 @@
+use std::{fs, path::Path};
+
+/* ------------------------------ Cache-bypass open ---------------------------- */
+/* Read-back verification straight after a copy mostly hits the OS page cache, so it
+   proves the bytes reached RAM, not the drive. These helpers open a file so reads
+   come from the media instead. */
+
+/// Block size the verify buffers are aligned to (required for unbuffered I/O on Windows).
+pub const ALIGN: usize = 4096;
+
+#[repr(C, align(4096))]
+#[derive(Clone, Copy)]
+struct Block([u8; ALIGN]);
+
+/// Heap buffer of `len` bytes (rounded up to ALIGN) whose start is ALIGN-aligned.
+pub struct AlignedBuf {
+  blocks: Vec<Block>,
+}
+
+impl AlignedBuf {
+  pub fn new(len: usize) -> Self {
+    let n = len.div_ceil(ALIGN).max(1);
+    AlignedBuf {
+      blocks: vec![Block([0u8; ALIGN]); n],
+    }
+  }
+
+  pub fn as_mut_slice(&mut self) -> &mut [u8] {
+    let len = self.blocks.len() * ALIGN;
+    // SAFETY: Block is repr(C) over a plain byte array, so the Vec's storage is
+    // `len` contiguous, initialised bytes.
+    unsafe { std::slice::from_raw_parts_mut(self.blocks.as_mut_ptr() as *mut u8, len) }
+  }
+}
+
+pub fn open(path: &Path, bypass_cache: bool) -> Result<fs::File, String> {
+  if !bypass_cache {
+    return fs::File::open(path).map_err(|e| format!("open error: {e}"));
+  }
+  open_uncached(path)
+}
+
+#[cfg(target_os = "macos")]
+fn open_uncached(path: &Path) -> Result<fs::File, String> {
+  use std::os::unix::io::AsRawFd;
+
+  let f = fs::File::open(path).map_err(|e| format!("open error: {e}"))?;
+  // SAFETY: fd is valid for the lifetime of `f`.
+  let rc = unsafe { libc::fcntl(f.as_raw_fd(), libc::F_NOCACHE, 1) };
+  if rc == -1 {
+    crate::logging::debug(&format!("F_NOCACHE failed for {}", path.display()));
+  }
+  Ok(f)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_uncached(path: &Path) -> Result<fs::File, String> {
+  use std::os::unix::io::AsRawFd;
+
+  let f = fs::File::open(path).map_err(|e| format!("open error: {e}"))?;
+  // Drop any clean cached pages so the following reads go to the device.
+  // SAFETY: fd is valid for the lifetime of `f`.
+  let rc = unsafe { libc::posix_fadvise(f.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+  if rc != 0 {
+    crate::logging::debug(&format!("posix_fadvise failed for {}", path.display()));
+  }
+  Ok(f)
+}
+
+#[cfg(windows)]
+fn open_uncached(path: &Path) -> Result<fs::File, String> {
+  use std::os::windows::fs::OpenOptionsExt;
+
+  const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+  // Reads must then be sector-aligned in offset and size; callers use AlignedBuf.
+  fs::OpenOptions::new()
+    .read(true)
+    .custom_flags(FILE_FLAG_NO_BUFFERING)
+    .open(path)
+    .map_err(|e| format!("open error: {e}"))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_uncached(path: &Path) -> Result<fs::File, String> {
+  fs::File::open(path).map_err(|e| format!("open error: {e}"))
+}
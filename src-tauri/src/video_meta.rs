@@ -0,0 +1,178 @@
+use chrono::{DateTime, Utc};
+use std::{
+  fs,
+  io::{Read, Seek, SeekFrom},
+  path::Path,
+};
+
+/* ------------------------------- Creation time ------------------------------ */
+/* Stills carry their capture time in EXIF; clips carry it in the container:
+   - MP4/MOV/M4V/3GP: moov/mvhd creation_time, seconds since 1904-01-01 UTC
+   - MKV/WebM: Segment/Info/DateUTC, nanoseconds since 2001-01-01 UTC
+   Only the headers are read; mdat/Cluster payloads are seeked past. */
+
+// Seconds between 1904-01-01 and 1970-01-01
+const MP4_EPOCH_OFFSET: i64 = 2_082_844_800;
+// Seconds between 1970-01-01 and 2001-01-01
+const MKV_EPOCH_OFFSET: i64 = 978_307_200;
+
+pub fn creation_time(path: &Path) -> Option<DateTime<Utc>> {
+  let ext = path
+    .extension()
+    .and_then(|s| s.to_str())
+    .unwrap_or("")
+    .to_lowercase();
+  let mut f = fs::File::open(path).ok()?;
+  match ext.as_str() {
+    "mp4" | "mov" | "m4v" | "3gp" | "3g2" | "qt" => mp4_creation_time(&mut f),
+    "mkv" | "webm" | "mka" => mkv_creation_time(&mut f),
+    _ => None,
+  }
+}
+
+/* ------------------------------------ MP4 ----------------------------------- */
+
+// (type, payload offset, payload size) of each box between `start` and `end`
+fn mp4_boxes(f: &mut fs::File, start: u64, end: u64) -> Vec<([u8; 4], u64, u64)> {
+  let mut out = vec![];
+  let mut pos = start;
+  while pos + 8 <= end {
+    let mut h = [0u8; 8];
+    if f.seek(SeekFrom::Start(pos)).is_err() || f.read_exact(&mut h).is_err() {
+      break;
+    }
+    let kind = [h[4], h[5], h[6], h[7]];
+    let (size, header) = match u32::from_be_bytes([h[0], h[1], h[2], h[3]]) {
+      // 64-bit size follows the type
+      1 => {
+        let mut big = [0u8; 8];
+        if f.read_exact(&mut big).is_err() {
+          break;
+        }
+        (u64::from_be_bytes(big), 16)
+      }
+      // Box runs to the end of its parent
+      0 => (end - pos, 8),
+      n => (n as u64, 8),
+    };
+    if size < header {
+      break;
+    }
+    out.push((kind, pos + header, size - header));
+    pos = pos.saturating_add(size);
+  }
+  out
+}
+
+fn mp4_creation_time(f: &mut fs::File) -> Option<DateTime<Utc>> {
+  let len = f.metadata().ok()?.len();
+  let (_, moov, moov_len) = mp4_boxes(f, 0, len).into_iter().find(|(k, _, _)| k == b"moov")?;
+  let (_, mvhd, _) = mp4_boxes(f, moov, moov + moov_len)
+    .into_iter()
+    .find(|(k, _, _)| k == b"mvhd")?;
+
+  f.seek(SeekFrom::Start(mvhd)).ok()?;
+  let mut b = [0u8; 12];
+  f.read_exact(&mut b).ok()?;
+  let secs = if b[0] == 1 {
+    u64::from_be_bytes([b[4], b[5], b[6], b[7], b[8], b[9], b[10], b[11]])
+  } else {
+    u32::from_be_bytes([b[4], b[5], b[6], b[7]]) as u64
+  };
+  // Many cameras leave it zeroed
+  if secs == 0 {
+    return None;
+  }
+  DateTime::from_timestamp(secs as i64 - MP4_EPOCH_OFFSET, 0)
+}
+
+/* ------------------------------------ MKV ----------------------------------- */
+
+const EBML_SEGMENT: u64 = 0x1853_8067;
+const EBML_INFO: u64 = 0x1549_A966;
+const EBML_CLUSTER: u64 = 0x1F43_B675;
+const EBML_DATE_UTC: u64 = 0x4461;
+
+// EBML variable-length integer; IDs keep their length marker, sizes don't.
+// Returns (value, bytes read, all value bits set = "unknown size").
+fn read_vint(f: &mut fs::File, keep_marker: bool) -> Option<(u64, u64, bool)> {
+  let mut first = [0u8; 1];
+  f.read_exact(&mut first).ok()?;
+  let len = first[0].leading_zeros() as usize + 1;
+  if len > 8 {
+    return None;
+  }
+  // Value bits of the first byte (none for an 8-byte vint)
+  let mask = (0xffu16 >> len) as u8;
+  let mut value = if keep_marker {
+    first[0] as u64
+  } else {
+    (first[0] & mask) as u64
+  };
+  let mut all_ones = first[0] & mask == mask;
+  for _ in 1..len {
+    let mut b = [0u8; 1];
+    f.read_exact(&mut b).ok()?;
+    value = (value << 8) | b[0] as u64;
+    all_ones &= b[0] == 0xff;
+  }
+  Some((value, len as u64, all_ones))
+}
+
+fn mkv_creation_time(f: &mut fs::File) -> Option<DateTime<Utc>> {
+  let len = f.metadata().ok()?.len();
+  let mut pos = 0u64;
+
+  // Top level: EBML header, then Segment
+  let segment_start = loop {
+    f.seek(SeekFrom::Start(pos)).ok()?;
+    let (id, id_len, _) = read_vint(f, true)?;
+    let (size, size_len, _) = read_vint(f, false)?;
+    pos += id_len + size_len;
+    if id == EBML_SEGMENT {
+      break pos;
+    }
+    pos = pos.saturating_add(size);
+    if pos >= len {
+      return None;
+    }
+  };
+
+  // Segment children up to the first Cluster, looking for Info
+  let mut pos = segment_start;
+  while pos < len {
+    f.seek(SeekFrom::Start(pos)).ok()?;
+    let (id, id_len, _) = read_vint(f, true)?;
+    let (size, size_len, unknown) = read_vint(f, false)?;
+    let body = pos + id_len + size_len;
+    if id == EBML_CLUSTER || unknown {
+      return None;
+    }
+    if id == EBML_INFO {
+      return mkv_date_in_info(f, body, body.saturating_add(size));
+    }
+    pos = body.saturating_add(size);
+  }
+  None
+}
+
+fn mkv_date_in_info(f: &mut fs::File, start: u64, end: u64) -> Option<DateTime<Utc>> {
+  let mut pos = start;
+  while pos < end {
+    f.seek(SeekFrom::Start(pos)).ok()?;
+    let (id, id_len, _) = read_vint(f, true)?;
+    let (size, size_len, _) = read_vint(f, false)?;
+    let body = pos + id_len + size_len;
+    if id == EBML_DATE_UTC && size == 8 {
+      let mut b = [0u8; 8];
+      f.read_exact(&mut b).ok()?;
+      let ns = i64::from_be_bytes(b);
+      return DateTime::from_timestamp(
+        MKV_EPOCH_OFFSET + ns.div_euclid(1_000_000_000),
+        ns.rem_euclid(1_000_000_000) as u32,
+      );
+    }
+    pos = body.saturating_add(size);
+  }
+  None
+}
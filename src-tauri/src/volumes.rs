@@ -0,0 +1,187 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fs,
+  path::Path,
+  sync::Mutex,
+  thread,
+  time::{Duration, Instant},
+};
+use sysinfo::{Disk, Disks};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{device, logging, VolumeInfo};
+
+/* ---------------------------------- Scanning -------------------------------- */
+/* sysinfo reads the mount table natively (getfsstat on macOS, /proc/mounts +
+   statvfs on Linux, GetLogicalDrives + GetDiskFreeSpaceExW on Windows), so sizes
+   and the filesystem type come straight from the OS instead of df's columns,
+   which differ between platforms and break on mount points with spaces. */
+
+// udev escapes spaces and other bytes in /dev/disk/by-label names as \xNN
+fn unescape_label(s: &str) -> String {
+  let mut out = Vec::with_capacity(s.len());
+  let bytes = s.as_bytes();
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'x') {
+      if let Some(Ok(b)) = s.get(i + 2..i + 4).map(|h| u8::from_str_radix(h, 16)) {
+        out.push(b);
+        i += 4;
+        continue;
+      }
+    }
+    out.push(bytes[i]);
+    i += 1;
+  }
+  String::from_utf8_lossy(&out).to_string()
+}
+
+// Linux: device node -> filesystem label. sysinfo names disks by device there.
+fn linux_labels() -> HashMap<String, String> {
+  let Ok(rd) = fs::read_dir("/dev/disk/by-label") else {
+    return HashMap::new();
+  };
+  rd.filter_map(|e| e.ok())
+    .filter_map(|e| {
+      let dev = fs::canonicalize(e.path()).ok()?;
+      Some((dev.to_string_lossy().to_string(), unescape_label(&e.file_name().to_string_lossy())))
+    })
+    .collect()
+}
+
+// The name a user would recognise: the filesystem label, else the mount point's last component
+fn volume_label(disk: &Disk, labels: &HashMap<String, String>) -> String {
+  let name = disk.name().to_string_lossy().to_string();
+  let label = if cfg!(target_os = "linux") {
+    labels.get(&name).cloned()
+  } else {
+    Some(name)
+  };
+  label.filter(|l| !l.trim().is_empty()).unwrap_or_else(|| {
+    let mount = disk.mount_point();
+    mount
+      .file_name()
+      .map(|n| n.to_string_lossy().to_string())
+      .unwrap_or_else(|| mount.to_string_lossy().to_string())
+  })
+}
+
+/// Every mounted volume with its label, filesystem and sizes.
+pub fn scan_volumes() -> Result<Vec<VolumeInfo>, String> {
+  let disks = Disks::new_with_refreshed_list();
+  let labels = if cfg!(target_os = "linux") { linux_labels() } else { HashMap::new() };
+
+  let mut seen: HashSet<&Path> = HashSet::new();
+  let mut vols: Vec<VolumeInfo> = vec![];
+  for disk in disks.list() {
+    // Bind mounts show the same mount point more than once
+    if !seen.insert(disk.mount_point()) {
+      continue;
+    }
+    let mount_point = disk.mount_point().to_string_lossy().to_string();
+    let fs_type = disk.file_system().to_string_lossy().to_string();
+    vols.push(VolumeInfo {
+      uuid: device::volume_uuid(&mount_point),
+      name: volume_label(disk, &labels),
+      fs_type: (!fs_type.is_empty()).then_some(fs_type),
+      total_bytes: disk.total_space(),
+      avail_bytes: disk.available_space(),
+      removable: device::is_removable(&mount_point).or(Some(disk.is_removable())),
+      mount_point,
+    });
+  }
+
+  Ok(vols)
+}
+
+/* ----------------------------------- Table ---------------------------------- */
+/* The frontend used to poll list_volumes; now the watcher keeps this table current
+   and pushes `volumes://changed` whenever a volume comes or goes. */
+
+#[derive(Default)]
+pub struct Volumes(Mutex<Option<Vec<VolumeInfo>>>);
+
+impl Volumes {
+  /// The cached table, scanning on first use.
+  pub fn list(&self) -> Result<Vec<VolumeInfo>, String> {
+    if let Some(v) = self.0.lock().ok().and_then(|g| g.clone()) {
+      return Ok(v);
+    }
+    self.refresh()
+  }
+
+  pub fn refresh(&self) -> Result<Vec<VolumeInfo>, String> {
+    let vols = scan_volumes()?;
+    if let Ok(mut g) = self.0.lock() {
+      *g = Some(vols.clone());
+    }
+    Ok(vols)
+  }
+}
+
+/* ---------------------------------- Watcher --------------------------------- */
+/* Plugging in a hub or a dual-slot card reader mounts several volumes over a
+   second or two (and a failing cable can flap). The watcher waits for the mount
+   set to hold still before refreshing, so the UI gets one event per storm. */
+
+pub const VOLUMES_CHANGED: &str = "volumes://changed";
+
+const POLL: Duration = Duration::from_secs(2);
+// The mount set must be unchanged for this long before an update goes out
+const SETTLE: Duration = Duration::from_millis(750);
+// ...but an update isn't held back longer than this while mounts keep changing
+const SETTLE_MAX: Duration = Duration::from_secs(10);
+
+// Mount points from the same source as scan_volumes
+fn mount_set() -> HashSet<String> {
+  Disks::new_with_refreshed_list()
+    .list()
+    .iter()
+    .map(|d| d.mount_point().to_string_lossy().to_string())
+    .collect()
+}
+
+/// Refreshes the volume table when the set of mount points changes (debounced)
+/// and emits `volumes://changed` with the new list.
+pub fn spawn_volume_watcher(app: AppHandle) {
+  thread::spawn(move || {
+    let mut known = mount_set();
+    loop {
+      thread::sleep(POLL);
+
+      let mut current = mount_set();
+      if current == known {
+        continue;
+      }
+      let first_change = Instant::now();
+      loop {
+        thread::sleep(SETTLE);
+        let again = mount_set();
+        let settled = again == current;
+        current = again;
+        if settled || first_change.elapsed() >= SETTLE_MAX {
+          break;
+        }
+      }
+      if current == known {
+        // Came and went within the settle window
+        continue;
+      }
+
+      for m in current.difference(&known) {
+        logging::info(&format!("volume mounted: {m}"));
+      }
+      for m in known.difference(&current) {
+        logging::info(&format!("volume unmounted: {m}"));
+      }
+      known = current;
+
+      match app.state::<Volumes>().refresh() {
+        Ok(vols) => {
+          let _ = app.emit(VOLUMES_CHANGED, vols);
+        }
+        Err(e) => logging::warn(&format!("volume refresh failed: {e}")),
+      }
+    }
+  });
+}
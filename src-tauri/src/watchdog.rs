@@ -0,0 +1,132 @@
+use std::{
+  sync::{
+    atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
+    Arc, Mutex,
+  },
+  thread,
+  time::{Duration, Instant},
+};
+use tauri::AppHandle;
+
+use crate::{
+  events::{self, StallEvent},
+  logging,
+};
+
+/* ----------------------------------- Types ---------------------------------- */
+
+// User decision for a stalled file, set by `resolve_stall` and consumed by the copy loop.
+pub const STALL_NONE: u8 = 0;
+pub const STALL_RETRY: u8 = 1;
+pub const STALL_ABORT: u8 = 2;
+
+pub fn parse_stall_action(s: &str) -> Result<u8, String> {
+  match s {
+    "retry" => Ok(STALL_RETRY),
+    "abort" => Ok(STALL_ABORT),
+    other => Err(format!("unknown stall action: {other}")),
+  }
+}
+
+/* --------------------------------- Watchdog --------------------------------- */
+
+/// Background thread that fires `transfer://stalled` when no bytes move for `timeout`.
+/// Stops when dropped.
+pub struct Watchdog {
+  epoch: Instant,
+  last_tick_ms: Arc<AtomicU64>,
+  current_path: Arc<Mutex<String>>,
+  stop: Arc<AtomicBool>,
+  action: Arc<AtomicU8>,
+}
+
+impl Watchdog {
+  pub fn spawn(app: AppHandle, job_id: &str, timeout: Duration, action: Arc<AtomicU8>) -> Self {
+    action.store(STALL_NONE, Ordering::SeqCst);
+
+    let epoch = Instant::now();
+    let last_tick_ms = Arc::new(AtomicU64::new(0));
+    let current_path = Arc::new(Mutex::new(String::new()));
+    let stop = Arc::new(AtomicBool::new(false));
+
+    if !timeout.is_zero() {
+      let last_tick_ms = last_tick_ms.clone();
+      let current_path = current_path.clone();
+      let stop = stop.clone();
+      let job_id = job_id.to_string();
+
+      thread::spawn(move || {
+        let mut reported = false;
+        while !stop.load(Ordering::SeqCst) {
+          thread::sleep(Duration::from_millis(500));
+
+          let now_ms = epoch.elapsed().as_millis() as u64;
+          let idle = Duration::from_millis(now_ms.saturating_sub(last_tick_ms.load(Ordering::SeqCst)));
+
+          if idle >= timeout {
+            if !reported {
+              let path = current_path.lock().map(|g| g.clone()).unwrap_or_default();
+              logging::warn(&format!("no I/O progress for {}s on {path}", idle.as_secs()));
+              events::emit(
+                &app,
+                events::STALLED,
+                &StallEvent {
+                  job_id: job_id.clone(),
+                  current_path: path,
+                  stalled_secs: idle.as_secs(),
+                },
+              );
+              reported = true;
+            }
+          } else {
+            reported = false;
+          }
+        }
+      });
+    }
+
+    Watchdog {
+      epoch,
+      last_tick_ms,
+      current_path,
+      stop,
+      action,
+    }
+  }
+
+  /// A watchdog that never fires, for hashing outside a transfer.
+  pub fn disabled() -> Self {
+    Watchdog {
+      epoch: Instant::now(),
+      last_tick_ms: Arc::new(AtomicU64::new(0)),
+      current_path: Arc::new(Mutex::new(String::new())),
+      stop: Arc::new(AtomicBool::new(true)),
+      action: Arc::new(AtomicU8::new(STALL_NONE)),
+    }
+  }
+
+  /// Marks forward progress; call after every successful read/write.
+  pub fn tick(&self) {
+    self
+      .last_tick_ms
+      .store(self.epoch.elapsed().as_millis() as u64, Ordering::SeqCst);
+  }
+
+  pub fn set_current(&self, path: &str) {
+    if let Ok(mut g) = self.current_path.lock() {
+      *g = path.to_string();
+    }
+    self.tick();
+  }
+
+  /// Returns and clears any pending stall decision.
+  pub fn take_action(&self) -> u8 {
+    self.action.swap(STALL_NONE, Ordering::SeqCst)
+  }
+}
+
+impl Drop for Watchdog {
+  fn drop(&mut self) {
+    self.stop.store(true, Ordering::SeqCst);
+  }
+}
@@ -22,12 +22,16 @@ export function VolumePicker({
   onSelect,
   onRefresh,
   loading,
+  removableOnly,
+  onRemovableOnlyChange,
 }: {
   volumes: VolumeInfo[];
   selected: string;
   onSelect: (mount: string) => void;
   onRefresh: () => void;
   loading?: boolean;
+  removableOnly?: boolean;
+  onRemovableOnlyChange?: (v: boolean) => void;
 }) {
   const selectedVol = React.useMemo(
     () => volumes.find((v) => v.mount_point === selected) ?? null,
@@ -62,15 +66,28 @@ export function VolumePicker({
           </CardSubtle>
         </div>
 
-        <Button
-          variant="secondary"
-          onClick={onRefresh}
-          disabled={loading}
-          className="bg-zinc-100 text-zinc-900 border border-black/10 hover:bg-zinc-200
-                     dark:bg-white/10 dark:text-white dark:border-white/10 dark:hover:bg-white/15"
-        >
-          {loading ? "Refreshing…" : "Refresh"}
-        </Button>
+        <div className="flex items-center gap-3">
+          {onRemovableOnlyChange && (
+            <label className="flex items-center gap-2 text-xs text-zinc-600 dark:text-white/60">
+              <input
+                type="checkbox"
+                checked={!!removableOnly}
+                onChange={(e) => onRemovableOnlyChange(e.target.checked)}
+              />
+              External drives only
+            </label>
+          )}
+
+          <Button
+            variant="secondary"
+            onClick={onRefresh}
+            disabled={loading}
+            className="bg-zinc-100 text-zinc-900 border border-black/10 hover:bg-zinc-200
+                       dark:bg-white/10 dark:text-white dark:border-white/10 dark:hover:bg-white/15"
+          >
+            {loading ? "Refreshing…" : "Refresh"}
+          </Button>
+        </div>
       </CardHeader>
 
       <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
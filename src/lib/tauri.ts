@@ -1,58 +1,147 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   VolumeInfo,
   QueueItem,
+  QueueAdd,
+  NormalizedPath,
   Preflight,
   TransferSummary,
   TransferOptions,
+  AuditEntry,
+  AuditVerifyReport,
+  StallAction,
+  EngineOptions,
+  TransferPlan,
+  PlanImport,
+  Profile,
+  OffloadReport,
+  ItemPreview,
+  FileInfo,
+  DuplicateReport,
+  StatsRange,
+  TransferStats,
+  ReportOptions,
+  DeliveryReport,
+  HashdeepExport,
+  ManifestExport,
+  PartialsReport,
+  Quota,
+  PermissionReport,
+  DaemonSettings,
+  DaemonStatus,
+  ThroughputSample,
+  TransferProgress,
+  HistoryFilter,
+  HistorySession,
+  SessionDetail,
+  SessionInfo,
+  SessionVerifyReport,
 } from "@/types/transfer";
 
-type PickedItem = { kind: "file" | "folder"; path: string };
+/**
+ * Listen to an engine event (transfer://progress, transfer://job, ...) for one job only.
+ * Every engine payload carries a job_id; with several jobs running, unfiltered
+ * listeners would mix their progress together.
+ */
+export async function listenJob<T extends { job_id: string }>(
+  topic: string,
+  jobId: string,
+  handler: (payload: T) => void
+): Promise<UnlistenFn> {
+  return await listen<T>(topic, (evt) => {
+    if (evt.payload.job_id === jobId) handler(evt.payload);
+  });
+}
+
+/**
+ * Listen to one job's progress on its own topic (transfer://progress/{jobId}), so the
+ * handler isn't woken for every other job's ticks. Pass the jobId given to
+ * startTransfer; ids with characters other than letters, digits, "-", "_" and ":"
+ * only get the shared topic (use listenJob for those).
+ */
+export async function listenJobProgress(
+  jobId: string,
+  handler: (payload: TransferProgress) => void
+): Promise<UnlistenFn> {
+  return await listen<TransferProgress>(`transfer://progress/${jobId}`, (evt) =>
+    handler(evt.payload)
+  );
+}
+
+type PickedItem = { kind: "file" | "folder"; path: string; contents_only?: boolean };
 type CopyMode = "copy" | "move";
-type ConflictPolicy = "rename" | "overwrite" | "skip";
-type VerifyMode = "none" | "size" | "sha256";
+type ConflictPolicy = "rename" | "overwrite" | "skip" | "newer" | "sync";
+type VerifyMode = "none" | "size" | "xxh3" | "xxh64" | "blake3" | "sha256" | "md5" | "crc32";
 
-export async function listVolumes(): Promise<VolumeInfo[]> {
-  return await invoke("list_volumes");
+/** Unmount a destination so it can be unplugged; refused while a transfer is writing to it. */
+export async function ejectVolume(mountPoint: string): Promise<void> {
+  return await invoke("eject_volume", { mountPoint });
 }
 
-export async function pickFiles(): Promise<QueueItem[]> {
+/**
+ * Cached volume table; updates arrive as `volumes://changed` events.
+ * With removableOnly, just USB sticks, cards and external disks.
+ */
+export async function listVolumes(removableOnly = false): Promise<VolumeInfo[]> {
+  return await invoke("list_volumes", { removableOnly });
+}
+
+export async function pickFiles(): Promise<QueueAdd> {
   return await invoke("pick_files");
 }
 
-export async function pickFolders(): Promise<QueueItem[]> {
+export async function pickFolders(): Promise<QueueAdd> {
   return await invoke("pick_folders");
 }
 
+type TransferConfig = {
+  // Required: the caller picks the id so it can follow the job's events and
+  // controls while the call is still pending
+  jobId: string;
+  conflictPolicy?: ConflictPolicy;
+  verifyMode?: VerifyMode;
+  // Engine options; anything omitted uses the Rust default
+  engine?: EngineOptions;
+  // Preflight the user confirmed; the selection is re-checked against it at start
+  preflight?: Preflight;
+};
+
 function toPicked(items: QueueItem[]): PickedItem[] {
-  return items.map((i) => ({ kind: i.kind, path: i.path }));
+  return items.map((i) => ({ kind: i.kind, path: i.path, contents_only: i.contents_only ?? false }));
 }
 
 /**
- * Rust: preflight_scan(items: Vec<PickedItem>, dest_mount_point: String)
- * Tauri args: { items, destMountPoint }
+ * Rust: preflight_scan(items: Vec<PickedItem>, dest_mount_point: String, options: Option<TransferOptions>, preflight_id: Option<String>)
+ * Tauri args: { items, destMountPoint, options, preflightId }
  */
 export async function preflightScan(
   items: QueueItem[],
-  destMountPoint: string
+  destMountPoint: string,
+  engine?: EngineOptions,
+  preflightId?: string
 ): Promise<Preflight> {
   return await invoke("preflight_scan", {
     items: toPicked(items),
     destMountPoint,
+    options: engine ?? null,
+    preflightId: preflightId ?? null,
   });
 }
 
+/** Stops a running preflight; resolves with what it had scanned so far (null if none was running). */
+export async function cancelPreflight(preflightId: string): Promise<Preflight | null> {
+  return await invoke("cancel_preflight", { preflightId });
+}
+
 /**
  * Rust: start_transfer(app, items: Vec<PickedItem>, dest_mount_point: String, copy_mode: String, conflict_policy: String, verify_mode: String, ...)
- * Tauri args: { items, destMountPoint, copyMode, conflictPolicy, verifyMode }
+ * Tauri args: { items, destMountPoint, copyMode, conflictPolicy, verifyMode, jobId }
  */
 export async function startTransfer(
   items: QueueItem[],
   opts: TransferOptions,
-  config?: {
-    conflictPolicy?: ConflictPolicy;
-    verifyMode?: VerifyMode;
-  }
+  config: TransferConfig
 ): Promise<TransferSummary> {
   const copyMode: CopyMode = opts.move_instead_of_copy ? "move" : "copy";
 
@@ -60,15 +149,348 @@ export async function startTransfer(
     items: toPicked(items),
     destMountPoint: opts.dest_mount_point,
     copyMode,
-    conflictPolicy: config?.conflictPolicy ?? "rename",
-    verifyMode: config?.verifyMode ?? "size",
+    conflictPolicy: config.conflictPolicy ?? "rename",
+    verifyMode: config.verifyMode ?? "xxh3",
+    options: config.engine ?? {},
+    preflight: config.preflight
+      ? { total_files: config.preflight.total_files, total_bytes: config.preflight.total_bytes }
+      : null,
+    jobId: config.jobId,
+  });
+}
+
+/**
+ * Queue a transfer bound to a drive (by volume UUID) that may not be mounted yet.
+ * Resolves once the drive appears and the transfer finishes; cancelTransfer() stops the wait.
+ */
+export async function startTransferOnDevice(
+  items: QueueItem[],
+  deviceUuid: string,
+  opts: TransferOptions,
+  config: TransferConfig
+): Promise<TransferSummary> {
+  const copyMode: CopyMode = opts.move_instead_of_copy ? "move" : "copy";
+
+  return await invoke("start_transfer_on_device", {
+    items: toPicked(items),
+    deviceUuid,
+    copyMode,
+    conflictPolicy: config.conflictPolicy ?? "rename",
+    verifyMode: config.verifyMode ?? "xxh3",
+    options: config.engine ?? {},
+    jobId: config.jobId,
+  });
+}
+
+/** Hard cancel: aborts mid-file. Omit jobId to cancel every running job. */
+export async function cancelTransfer(jobId?: string): Promise<void> {
+  return await invoke("cancel_transfer", { jobId: jobId ?? null });
+}
+
+/** Hold the in-flight file mid-copy; progress reports phase "paused" until resumeTransfer(). */
+export async function pauseTransfer(jobId?: string): Promise<void> {
+  return await invoke("pause_transfer", { jobId: jobId ?? null });
+}
+
+/** Continue a paused transfer from the same byte of the same file. */
+export async function resumeTransfer(jobId?: string): Promise<void> {
+  return await invoke("resume_transfer", { jobId: jobId ?? null });
+}
+
+/** Change the throughput cap of a running transfer (all jobs if no id); 0 removes it. */
+export async function setTransferLimit(
+  bytesPerSec: number,
+  jobId?: string,
+): Promise<void> {
+  return await invoke("set_transfer_limit", {
+    jobId: jobId ?? null,
+    bytesPerSec,
+  });
+}
+
+/** Throughput of a running job so far, for a speed chart (its summary has the full list). */
+export async function getTransferSamples(
+  jobId: string,
+): Promise<ThroughputSample[]> {
+  return await invoke("get_transfer_samples", { jobId });
+}
+
+/** Soft cancel: finish and verify the in-flight file, write the manifest, then stop. */
+export async function stopAfterCurrent(jobId?: string): Promise<void> {
+  return await invoke("stop_after_current", { jobId: jobId ?? null });
+}
+
+/**
+ * Answer a `transfer://stalled` event: retry the current file from the start, or abort the transfer.
+ */
+/** Skip one file in a running job; itemId is the file's source path (progress `current_path`). */
+export async function skipItem(jobId: string, itemId: string): Promise<void> {
+  return await invoke("skip_item", { jobId, itemId });
+}
+
+/** Move a pending file to the front of a running job's queue. */
+export async function prioritizeItem(jobId: string, itemId: string): Promise<void> {
+  return await invoke("prioritize_item", { jobId, itemId });
+}
+
+export async function resolveStall(action: StallAction, jobId?: string): Promise<void> {
+  return await invoke("resolve_stall", { action, jobId: jobId ?? null });
+}
+
+export type VerifyModeInfo = { id: VerifyMode; label: string; recommended: boolean };
+
+export async function listVerifyModes(): Promise<VerifyModeInfo[]> {
+  return await invoke("list_verify_modes");
+}
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+export async function setLogLevel(level: LogLevel): Promise<void> {
+  return await invoke("set_log_level", { level });
+}
+
+/** Totals from the transfer history: per month, per destination drive, and overall. */
+export async function getStats(range?: StatsRange): Promise<TransferStats> {
+  return await invoke("get_stats", { range: range ?? null });
+}
+
+/** Search past sessions by date, destination drive, outcome or file name (newest first). */
+export async function queryHistory(
+  filter?: HistoryFilter,
+): Promise<HistorySession[]> {
+  return await invoke("query_history", { filter: filter ?? null });
+}
+
+/** One past session with the settings it ran with and every file's result. */
+export async function getSessionDetail(id: number): Promise<SessionDetail> {
+  return await invoke("get_session_detail", { id });
+}
+
+/**
+ * Replay a previous session onto another drive. `path` is a session dir, its manifest.json,
+ * or a plan file exported with exportPlan().
+ */
+export async function startFromManifest(
+  path: string,
+  newDest: string,
+  config: { jobId: string; verifyMode?: VerifyMode; engine?: EngineOptions }
+): Promise<TransferSummary> {
+  return await invoke("start_from_manifest", {
+    path,
+    newDest,
+    verifyMode: config.verifyMode ?? null,
+    options: config.engine ?? null,
+    jobId: config.jobId,
+  });
+}
+
+/**
+ * Re-copy only the files a session recorded as `error`, updating its manifest in place.
+ * `sessionDir` may also be the session's manifest.json.
+ */
+export async function retryFailed(
+  sessionDir: string,
+  config: { jobId: string; copyMode?: CopyMode; verifyMode?: VerifyMode; engine?: EngineOptions }
+): Promise<TransferSummary> {
+  return await invoke("retry_failed", {
+    sessionDir,
+    copyMode: config.copyMode ?? null,
+    verifyMode: config.verifyMode ?? null,
+    options: config.engine ?? null,
+    jobId: config.jobId,
+  });
+}
+
+/**
+ * Re-hash every file a session copied and compare with its manifest: reports files
+ * that are missing, changed size or no longer match their checksum.
+ * Sends transfer://progress ("verifying") under jobId; cancelTransfer(jobId) stops it.
+ */
+export async function verifySession(
+  sessionDir: string,
+  jobId?: string,
+): Promise<SessionVerifyReport> {
+  return await invoke("verify_session", { sessionDir, jobId: jobId ?? null });
+}
+
+// Copies the session's permission-denied files again behind an OS admin prompt, then
+// verifies them with the session's verify_mode; cancelTransfer(jobId) stops the verify
+export async function retryElevated(sessionDir: string, jobId?: string): Promise<TransferSummary> {
+  return await invoke("retry_elevated", { sessionDir, jobId: jobId ?? null });
+}
+
+/** Continue an interrupted session in place: intact copies are skipped and a partly copied file continues from its last checkpoint. */
+export async function resumeSession(sessionDir: string, jobId: string): Promise<TransferSummary> {
+  return await invoke("resume_session", { sessionDir, jobId });
+}
+
+/**
+ * DIT offload: read each source once, write it to a primary and a backup drive,
+ * and verify both copies against the source hash. "none"/"size" fall back to xxh3.
+ */
+export async function startOffload(
+  items: QueueItem[],
+  primaryDest: string,
+  backupDest: string,
+  config?: { verifyMode?: VerifyMode; jobId?: string; engine?: EngineOptions }
+): Promise<OffloadReport> {
+  return await invoke("start_offload", {
+    items: toPicked(items),
+    primaryDest,
+    backupDest,
+    verifyMode: config?.verifyMode ?? "xxh3",
+    options: config?.engine ?? {},
+    jobId: config?.jobId ?? null,
   });
 }
 
-export async function cancelTransfer(): Promise<void> {
-  return await invoke("cancel_transfer");
+export async function listProfiles(): Promise<Profile[]> {
+  return await invoke("list_profiles");
+}
+
+export async function saveProfile(profile: Profile): Promise<Profile> {
+  return await invoke("save_profile", { profile });
+}
+
+export async function deleteProfile(id: string): Promise<void> {
+  return await invoke("delete_profile", { id });
+}
+
+export async function profileForCard(uuid: string): Promise<Profile | null> {
+  return await invoke("profile_for_card", { uuid });
+}
+
+/** Background-mode settings plus the jobs already running, for attaching to them. */
+export async function getDaemonStatus(): Promise<DaemonStatus> {
+  return await invoke("get_daemon_status");
+}
+
+export async function setDaemonSettings(settings: DaemonSettings): Promise<void> {
+  return await invoke("set_daemon_settings", { settings });
+}
+
+export async function getLaunchAtLogin(): Promise<boolean> {
+  return await invoke("get_launch_at_login");
+}
+
+/** Start hidden in the tray at login, with card auto-import armed. */
+export async function setLaunchAtLogin(enabled: boolean): Promise<void> {
+  return await invoke("set_launch_at_login", { enabled });
+}
+
+export async function exportPlan(path: string, plan: TransferPlan): Promise<void> {
+  return await invoke("export_plan", { path, plan });
+}
+
+/** Reads a plan file; missing_items lists sources that don't exist on this machine. */
+export async function importPlan(path: string): Promise<PlanImport> {
+  return await invoke("import_plan", { path });
+}
+
+/** Absolute, canonical form of a typed/dropped path (~ expanded, relative resolved against base). */
+export async function normalizePath(input: string, base?: string): Promise<NormalizedPath> {
+  return invoke<NormalizedPath>("normalize_path", { input, base: base ?? null });
+}
+
+/** Queues dropped paths; missing ones and ones already queued come back in `rejected`. */
+export async function addDroppedPaths(paths: string[]): Promise<QueueAdd> {
+  return invoke<QueueAdd>("add_dropped_paths", { paths });
+}
+
+export async function removeQueueItems(ids: string[]): Promise<void> {
+  return invoke("remove_queue_items", { ids });
+}
+
+export async function clearQueue(): Promise<void> {
+  return invoke("clear_queue");
 }
 
-export async function addDroppedPaths(paths: string[]): Promise<QueueItem[]> {
-  return invoke<QueueItem[]>("add_dropped_paths", { paths });
-}
\ No newline at end of file
+export async function listQueue(): Promise<QueueItem[]> {
+  return invoke<QueueItem[]>("list_queue");
+}
+
+/** Thumbnail (as a data: URL) and key metadata for a queued file or folder. */
+export async function getItemPreview(path: string): Promise<ItemPreview> {
+  return await invoke("get_item_preview", { path });
+}
+
+/** Size, timestamps, detected type and an EXIF/ID3 summary for one path. */
+export async function getFileInfo(path: string): Promise<FileInfo> {
+  return await invoke("get_file_info", { path });
+}
+
+/**
+ * Find files with identical contents under a destination (e.g. years of Transfers sessions).
+ * Progress arrives as `dupes://progress`.
+ */
+export async function findDuplicates(root: string): Promise<DuplicateReport> {
+  return await invoke("find_duplicates", { root });
+}
+
+/**
+ * Write delivery_report.html (and optionally .pdf) into a session folder: logo, label,
+ * per-file table with checksums, totals, operator and duration.
+ */
+export async function generateDeliveryReport(
+  sessionDir: string,
+  options?: ReportOptions
+): Promise<DeliveryReport> {
+  return await invoke("generate_delivery_report", { sessionDir, options: options ?? null });
+}
+
+export type SummaryFormat = "text" | "markdown" | "checksums";
+
+/** Copy a session's summary (or its checksum list) to the clipboard; resolves with the copied text. */
+export async function copySummaryToClipboard(sessionDir: string, format: SummaryFormat): Promise<string> {
+  return await invoke("copy_summary_to_clipboard", { sessionDir, format });
+}
+
+/** Write hashdeep_audit.txt (checkable with `hashdeep -a -l -r -k hashdeep_audit.txt .`). */
+export async function exportHashdeep(sessionDir: string, algo?: "md5" | "sha256"): Promise<HashdeepExport> {
+  return await invoke("export_hashdeep", { sessionDir, algo: algo ?? null });
+}
+
+/** Write the session manifest as manifest.csv in the session folder, for opening in Excel. */
+export async function exportManifest(sessionDir: string, format: "csv" = "csv"): Promise<ManifestExport> {
+  return await invoke("export_manifest", { sessionDir, format });
+}
+
+/**
+ * Find leftovers from crashed or cancelled sessions under a drive's Transfers tree.
+ * Call again with `confirm` (paths the user approved) to delete them.
+ */
+export async function cleanPartials(destMount: string, confirm?: string[]): Promise<PartialsReport> {
+  return await invoke("clean_partials", { destMount, confirm: confirm ?? null });
+}
+
+/** Previous sessions on a destination drive, newest first. */
+export async function listSessions(mountPoint: string): Promise<SessionInfo[]> {
+  return await invoke("list_sessions", { mountPoint });
+}
+
+/** Show a session folder in Finder / Explorer. */
+export async function openSessionDir(path: string): Promise<void> {
+  return await invoke("open_session_dir", { path });
+}
+
+/** Missing macOS privacy access for these transfer paths; `checked` is false off macOS. */
+export async function checkPermissions(paths?: string[]): Promise<PermissionReport> {
+  return await invoke("check_permissions", { paths: paths ?? null });
+}
+
+export async function getDestQuota(destMount: string): Promise<Quota | null> {
+  return await invoke("get_dest_quota", { destMount });
+}
+
+/** Cap what Transfers/ may use on a drive; pass null to remove the cap. */
+export async function setDestQuota(destMount: string, quota: Quota | null): Promise<void> {
+  return await invoke("set_dest_quota", { destMount, quota });
+}
+
+export async function readAuditLog(): Promise<AuditEntry[]> {
+  return await invoke("read_audit_log");
+}
+
+export async function verifyAuditLog(): Promise<AuditVerifyReport> {
+  return await invoke("verify_audit_log");
+}
@@ -5,6 +5,7 @@ import transferPilotIcon from "../assets/transferpilot-mark.png";
 
 import type {
   QueueItem,
+  QueueAdd,
   VolumeInfo,
   Preflight,
   TransferSummary,
@@ -14,6 +15,7 @@ import type {
 
 import {
   addDroppedPaths,
+  clearQueue,
   listVolumes,
   pickFiles,
   pickFolders,
@@ -21,6 +23,8 @@ import {
   startTransfer,
   // ✅ you’ll add this to lib/tauri (snippet below)
   cancelTransfer,
+  pauseTransfer,
+  resumeTransfer,
 } from "@/lib/tauri";
 
 import { VolumePicker } from "@/components/transfer/VolumePicker";
@@ -58,12 +62,22 @@ function fmtMs(ms: number) {
   return `${(ms / 1000).toFixed(2)} s`;
 }
 
+function fmtEta(secs: number) {
+  if (secs < 60) return `${secs}s left`;
+  const m = Math.floor(secs / 60);
+  if (m < 60) return `${m}m ${secs % 60}s left`;
+  return `${Math.floor(m / 60)}h ${m % 60}m left`;
+}
+
 function clamp01(n: number) {
   return Math.max(0, Math.min(1, n));
 }
 
 export default function DashboardPage() {
   const [volumes, setVolumes] = React.useState<VolumeInfo[]>([]);
+  const [removableOnly, setRemovableOnly] = React.useState(false);
+  const removableOnlyRef = React.useRef(removableOnly);
+  removableOnlyRef.current = removableOnly;
   const [destMount, setDestMount] = React.useState("");
   const [queue, setQueue] = React.useState<QueueItem[]>([]);
   const [filter, setFilter] = React.useState("");
@@ -108,23 +122,53 @@ export default function DashboardPage() {
     try {
       setError(null);
       setBusy(true);
-      setVolumes(await listVolumes());
+      setVolumes(await listVolumes(removableOnly));
     } catch (e: any) {
       setError(e?.toString?.() ?? "Failed to list volumes.");
     } finally {
       setBusy(false);
     }
-  }, []);
+  }, [removableOnly]);
 
   React.useEffect(() => {
     refreshVolumes();
   }, [refreshVolumes]);
 
-  const addItems = React.useCallback((items: QueueItem[]) => {
+  // Rust pushes the new volume list whenever a drive is mounted or unmounted
+  React.useEffect(() => {
+    let unlisten: null | (() => void) = null;
+
+    (async () => {
+      unlisten = await listen<VolumeInfo[]>("volumes://changed", (evt) => {
+        setVolumes(
+          removableOnlyRef.current
+            ? evt.payload.filter((v) => v.removable === true)
+            : evt.payload
+        );
+      });
+    })();
+
+    return () => {
+      unlisten?.();
+    };
+  }, []);
+
+  const addItems = React.useCallback(({ added: items, rejected, merged, removed_ids }: QueueAdd) => {
+    const missing = rejected.filter((r) => r.reason === "not_found");
+    const notes = [];
+    if (missing.length) {
+      notes.push(`${missing.length} path(s) no longer exist and were skipped.`);
+    }
+    if (merged.length) {
+      notes.push(`${merged.length} item(s) were already inside a queued folder and were merged into it.`);
+    }
+    if (notes.length) setError(notes.join(" "));
     setQueue((q) => {
-      const existing = new Set(q.map((x) => x.path));
+      const removed = new Set(removed_ids);
+      const kept = q.filter((x) => !removed.has(x.id));
+      const existing = new Set(kept.map((x) => x.path));
       const deduped = items.filter((x) => !existing.has(x.path));
-      return [...q, ...deduped];
+      return [...kept, ...deduped];
     });
     setPreflight(null);
   }, []);
@@ -133,8 +177,7 @@ export default function DashboardPage() {
     try {
       setBusy(true);
       setError(null);
-      const newItems = await addDroppedPaths(paths);
-      addItems(newItems);
+      addItems(await addDroppedPaths(paths));
     } catch (e: any) {
       setError(e?.toString?.() ?? "Failed to add dropped items.");
     } finally {
@@ -175,7 +218,8 @@ export default function DashboardPage() {
           if (
             p.phase === "copying" ||
             p.phase === "verifying" ||
-            p.phase === "scanning"
+            p.phase === "scanning" ||
+            p.phase === "paused"
           ) {
             setIsTransferring(true);
           }
@@ -221,6 +265,7 @@ export default function DashboardPage() {
   };
 
   const onClear = () => {
+    void clearQueue();
     setQueue([]);
     setPreflight(null);
     setFilter("");
@@ -229,6 +274,7 @@ export default function DashboardPage() {
   };
 
   const canPreflight = Boolean(destMount && queue.length > 0);
+  const preflightReady = Boolean(preflight?.will_fit && preflight?.writable);
 
   const runPreflight = async () => {
     if (!canPreflight) return;
@@ -245,7 +291,7 @@ export default function DashboardPage() {
   };
 
   const runTransfer = async () => {
-    if (!preflight?.will_fit || queue.length === 0 || isTransferring) return;
+    if (!preflightReady || queue.length === 0 || isTransferring) return;
     try {
       setBusy(true);
       setError(null);
@@ -254,9 +300,10 @@ export default function DashboardPage() {
       setIsTransferring(true);
 
       // NOTE: startTransfer can remain "await" even if Rust emits progress during the run.
-      const s = await startTransfer(queue, opts);
+      const s = await startTransfer(queue, opts, { jobId: crypto.randomUUID() });
 
       setLatest(s);
+      void clearQueue();
       setQueue([]);
       setPreflight(null);
       setFilter("");
@@ -279,6 +326,20 @@ export default function DashboardPage() {
     }
   };
 
+  const isPaused = isTransferring && progress?.phase === "paused";
+
+  const onTogglePause = async () => {
+    try {
+      if (isPaused) {
+        await resumeTransfer();
+      } else {
+        await pauseTransfer();
+      }
+    } catch (e: any) {
+      setError(e?.toString?.() ?? "Pause failed.");
+    }
+  };
+
   const copyToClipboard = React.useCallback(
     async (text: string, msg = "Copied") => {
       try {
@@ -344,6 +405,8 @@ export default function DashboardPage() {
       ? "Verifying…"
       : progress?.phase === "scanning"
       ? "Scanning…"
+      : progress?.phase === "paused"
+      ? "Paused"
       : progress?.phase === "cancelled"
       ? "Cancelled"
       : progress?.phase === "done"
@@ -406,6 +469,8 @@ export default function DashboardPage() {
             setPreflight(null);
           }}
           onRefresh={refreshVolumes}
+          removableOnly={removableOnly}
+          onRemovableOnlyChange={setRemovableOnly}
           loading={busy || isTransferring}
         />
 
@@ -483,15 +548,20 @@ export default function DashboardPage() {
                     variant="primary"
                     onClick={runTransfer}
                     disabled={
-                      !preflight?.will_fit || busy || queue.length === 0
+                      !preflightReady || busy || queue.length === 0
                     }
                   >
                     Start Transfer
                   </Button>
                 ) : (
-                  <Button variant="danger" onClick={onCancelTransfer}>
-                    Cancel
-                  </Button>
+                  <>
+                    <Button variant="secondary" onClick={onTogglePause}>
+                      {isPaused ? "Resume" : "Pause"}
+                    </Button>
+                    <Button variant="danger" onClick={onCancelTransfer}>
+                      Cancel
+                    </Button>
+                  </>
                 )}
               </div>
             </CardHeader>
@@ -515,6 +585,12 @@ export default function DashboardPage() {
                     <>
                       {fmtBytes(progress.bytes_done)} /{" "}
                       {fmtBytes(progress.bytes_total)}
+                      {progress.bytes_per_sec ? (
+                        <> · {fmtBytes(progress.bytes_per_sec)}/s</>
+                      ) : null}
+                      {progress.eta_seconds != null && progress.eta_seconds > 0 ? (
+                        <> · {fmtEta(progress.eta_seconds)}</>
+                      ) : null}
                     </>
                   ) : (
                     "—"
@@ -561,7 +637,7 @@ export default function DashboardPage() {
                   label="Preflight"
                   value={
                     preflight
-                      ? preflight.will_fit
+                      ? preflightReady
                         ? "Ready"
                         : "Blocked"
                       : "Not run"
@@ -578,12 +654,45 @@ export default function DashboardPage() {
                 />
               </div>
 
+              {preflight &&
+              (preflight.excluded.files > 0 || preflight.excluded.folders > 0) ? (
+                <div className="mt-2 text-xs text-zinc-600 dark:text-white/60">
+                  Filtered out: {preflight.excluded.files} file(s) (
+                  {fmtBytes(preflight.excluded.bytes)})
+                  {preflight.excluded.folders > 0
+                    ? ` and ${preflight.excluded.folders} folder(s)`
+                    : ""}
+                  .
+                </div>
+              ) : null}
+
+              {preflight && !preflight.writable ? (
+                <div className="mt-3 rounded-xl border border-amber-500/20 bg-amber-500/10 p-3 text-sm text-amber-700 dark:text-amber-200">
+                  Destination can't be written to
+                  {preflight.write_error ? `: ${preflight.write_error}` : "."}
+                </div>
+              ) : null}
+
               {preflight && !preflight.will_fit ? (
                 <div className="mt-3 rounded-xl border border-amber-500/20 bg-amber-500/10 p-3 text-sm text-amber-700 dark:text-amber-200">
                   Destination has {fmtBytes(preflight.dest_avail_bytes)}{" "}
                   available, but queue is {fmtBytes(preflight.total_bytes)}.
                 </div>
               ) : null}
+
+              {preflight && preflight.warnings.length > 0 ? (
+                <div className="mt-3 rounded-xl border border-amber-500/20 bg-amber-500/10 p-3 text-sm text-amber-700 dark:text-amber-200">
+                  {preflight.warnings.length} file(s) won't copy as-is to this
+                  drive's filesystem:
+                  <ul className="mt-1 list-disc pl-5 text-xs">
+                    {preflight.warnings.slice(0, 5).map((w) => (
+                      <li key={`${w.kind}:${w.path}`} className="break-all">
+                        {w.path}: {w.message}
+                      </li>
+                    ))}
+                  </ul>
+                </div>
+              ) : null}
             </div>
           </Card>
 
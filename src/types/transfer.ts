@@ -5,25 +5,110 @@ export type VolumeInfo = {
   total_bytes: number;
   avail_bytes: number;
   removable?: boolean | null;
+  uuid?: string | null;
 };
 
 export type QueueItem = {
   id: string;
   kind: "file" | "folder";
   path: string;
+  contents_only?: boolean; // folders: copy the contents without the folder name
   size_bytes?: number | null;
   file_count?: number | null;
 };
 
+// This matches Rust paths::NormalizedPath
+export type NormalizedPath = {
+  input: string;
+  path: string; // absolute; canonical when it exists
+  exists: boolean;
+  kind?: "file" | "folder" | "other" | null;
+  unc: boolean; // Windows \\server\share path
+};
+
+// This matches Rust queue::QueueAdd
+export type RejectedPath = {
+  path: string;
+  reason: "empty" | "not_found" | "duplicate" | "wrong_kind";
+};
+
+// A path folded into a queued folder that already covers it (or that now does)
+export type MergedPath = {
+  path: string;
+  into: string;
+};
+
+export type QueueAdd = {
+  added: QueueItem[];
+  rejected: RejectedPath[];
+  merged: MergedPath[];
+  removed_ids: string[]; // previously queued items now covered by an added folder
+};
+
+// Payload of transfer://plan-stale: the selection changed since preflight
+export type PlanStaleEvent = {
+  job_id: string;
+  expected_files: number;
+  expected_bytes: number;
+  actual_files: number;
+  actual_bytes: number;
+};
+
+// This matches Rust transfer::RetryPolicy
+export type RetryPolicy = {
+  max_attempts: number; // tries per file, the first included (1 = never retry)
+  backoff_ms: number; // wait before the first retry; doubles for each one after
+};
+
+// This matches Rust naming::RouteRule
+export type RouteRule = {
+  extensions: string[]; // without the dot, e.g. ["wav", "bwf"]
+  dir: string; // layout template, e.g. "Audio/Location" or "Footage/{reel}"
+};
+
+export type LargeFile = {
+  path: string;
+  bytes: number;
+};
+
+// This matches Rust filters::Excluded
+export type Excluded = {
+  files: number;
+  bytes: number;
+  folders: number; // skipped whole; their files aren't counted above
+};
+
+// This matches Rust fscompat::PreflightWarning
+export type PreflightWarning = {
+  kind: "too_large" | "invalid_name" | "name_too_long" | "path_too_long";
+  path: string; // source path
+  message: string;
+};
+
 // This matches Rust Preflight
 export type Preflight = {
   total_files: number;
   total_folders: number;
   total_bytes: number;
   dest_avail_bytes: number;
-  will_fit: boolean;
+  reserve_bytes: number; // kept free on the destination
+  dest_reserved_bytes: number; // still to be written to the same drive by running jobs
+  will_fit: boolean; // total_bytes + reserve_bytes + dest_reserved_bytes fits (and no failing quota)
   by_category: Record<string, number>;
   by_extension: Record<string, number>;
+  camera_checksum_files: string[]; // MHL/XML checksum files found on the source
+  camera_hashed_files: number;
+  quota?: QuotaCheck | null; // set when the destination has a Transfers quota
+  largest_files: LargeFile[]; // the 10 biggest, largest first
+  placeholder_files: number; // online-only cloud files (OneDrive/iCloud/Dropbox)
+  placeholder_paths: string[]; // the first 20 of them
+  writable: boolean; // a test file could be written to the destination
+  write_error?: string | null; // why not, when writable is false
+  dest_network: boolean; // destination is an SMB/NFS/WebDAV mount
+  notes: string[]; // informational, shown before starting
+  warnings: PreflightWarning[]; // files the destination filesystem would refuse; the first 100
+  excluded: Excluded; // left out by include_globs / exclude_globs / skip_hidden
+  cancelled: boolean; // stopped by cancelPreflight; totals are partial
 };
 
 export type TransferOptions = {
@@ -35,10 +120,61 @@ export type TransferOptions = {
   preserve_folder_structure?: boolean; // default: false
 };
 
+// This matches Rust transfer::TransferOptions (every field optional; Rust fills defaults)
+export type EngineOptions = {
+  debug?: boolean; // default: false
+  stall_timeout_secs?: number; // default: 30, 0 = off
+  ordering?: "path" | "scan" | "locality"; // default: path
+  verify_strategy?: "inline" | "batch"; // default: inline
+  verify_workers?: number; // batch verify threads, default: 0 (auto)
+  verify_bypass_cache?: boolean; // read back from the drive, not the page cache; default: false
+  concurrency?: number; // files copied at once (small files only; large ones stay sequential); default: 1
+  dest_subdir?: string | null; // sessions go under <dest>/<dest_subdir>/Transfers/
+  rename_prefix?: string | null; // prepended to every copied file name
+  project?: string | null; // value of the {project} token
+  layout_template?: string | null; // e.g. "{project}/{reel}"; tokens: project, reel, clip, folder, subpath, stem, ext, date, artist, album, title, track, shot_date, shot_time, shot_year, shot_month, exif_date, exif_time, camera_make, camera_model, lens, iso
+  rename_template?: string | null; // new name without extension, e.g. "{reel}_{clip}_{stem}"
+  organize_audio?: boolean; // file audio under Music/<Artist>/<Album>/ by its tags
+  burst_grouping?: "off" | "folder" | "prefix"; // group rapid-fire stills into Burst_<first frame>
+  device_bytes_per_sec?: number; // read cap per source device, shared across jobs; 0 = none
+  on_complete?: "none" | "reveal" | "hook" | "eject"; // run by the backend when the job reaches done
+  on_complete_hook?: string | null; // shell command; env has TRANSFERPILOT_SESSION_DIR, _DEST, _JOB_ID, ...
+  auto_eject?: boolean; // eject the destination after a done run with zero failed files; default: false
+  durability?: "per_file" | "per_batch" | "end_of_session" | "none"; // when copies are fsynced; default: per_file
+  reserve_bytes?: number; // free space to leave on the destination; default: 512 MiB
+  reserve_percent?: number; // or this % of the volume, whichever is larger; default: 0
+  network_tuning?: boolean; // bigger buffers, batched fsyncs and retries on SMB/NFS/WebDAV; default: true
+  mtime_tolerance_ms?: number | null; // mtime slack for "newer"/"sync"; default: by filesystem (2000 on FAT/exFAT)
+  session_naming?: "local" | "utc" | "offset"; // session folder time: HHMMSS | HHMMSSZ | HHMMSS+hhmm; default: local
+  contents_only?: boolean; // every folder pick drops its top-level folder name; default: false
+  flatten?: boolean; // everything straight into Files/, clashing names de-duplicated; default: false
+  flatten_hash?: boolean; // de-duplicate with a short source-path hash instead of " (n)"; default: false
+  routes?: RouteRule[]; // per-extension destinations, first match wins; default: []
+  follow_dir_symlinks?: boolean; // walk into symlinked folders (loops skipped); default: false
+  progress_interval_ms?: number; // min gap between progress events while copying; default: 120
+  progress_precise?: boolean; // also emit after every MiB copied; default: false
+  adaptive_workers?: boolean; // tune parallel copies (from concurrency) and, with verify_workers = 0, verify threads to throughput; default: true
+  secure_erase_passes?: number; // move only: overwrite sources this many times before deleting (max 7, needs a hash verify_mode); default: 0
+  lock_readonly?: boolean; // make verified copies read-only when the job ends; default: false
+  lock_session_dir?: boolean; // with lock_readonly, lock the whole session folder too (blocks later retries); default: false
+  cloud_placeholders?: "hydrate" | "skip" | "fail"; // online-only cloud files: download while copying, skip, or refuse to start; default: "hydrate"
+  preserve_metadata?: boolean; // copy source times, permission bits and xattrs onto each copy; default: false
+  include_globs?: string[]; // only scan files matching one of these, e.g. ["**/*.raw"]; default: [] (everything)
+  exclude_globs?: string[]; // skip matching files and folders, e.g. ["node_modules/**", "*.tmp"]; default: []
+  skip_hidden?: boolean; // leave out dotfiles, .DS_Store, Thumbs.db, desktop.ini, __MACOSX; default: false
+  dry_run?: boolean; // write the manifest a run would produce (to the app data dir) without copying; default: false
+  max_bytes_per_sec?: number; // cap on the job's total throughput, changeable mid-run with setTransferLimit (0 = none); default: 0
+  retry_policy?: RetryPolicy; // retries for read/write errors on a file; default: { max_attempts: 1, backoff_ms: 1000 }
+  manifest_csv?: boolean; // also write manifest.csv (opens in Excel) beside manifest.json; default: false
+};
+
 // This matches Rust TransferSummary
 export type TransferSummary = {
-  started_at: string;
+  job_id?: string; // the job that produced it; empty in older summary.json files
+  started_at: string; // local, with offset
   finished_at: string;
+  started_at_utc?: string;
+  finished_at_utc?: string;
   duration_ms: number;
   total_files: number;
   total_bytes: number;
@@ -46,24 +182,553 @@ export type TransferSummary = {
   moved_files: number;
   skipped_files: number;
   error_files: number;
+  unverified_files?: number; // copied, but a hard cancel ended the batch verify pass first
   output_session_dir: string;
+  dry_run?: boolean; // planned only; output_session_dir holds the would-be manifest
+  samples?: ThroughputSample[]; // throughput over the run (getTransferSamples)
+};
+
+// This matches Rust speed::ThroughputSample. About one a second; long runs
+// merge neighbours, so the step can grow.
+export type ThroughputSample = {
+  t_ms: number; // end of the sample, since the job started
+  bytes_per_sec: number;
+};
+
+// This matches Rust transfer::SessionVerifyIssue
+export type SessionVerifyIssue = {
+  kind: "missing" | "size_mismatch" | "checksum_mismatch" | "read_error";
+  source: string;
+  dest: string;
+  message: string;
+};
+
+// This matches Rust transfer::SessionVerifyReport
+export type SessionVerifyReport = {
+  session_dir: string;
+  files: number; // copied and moved rows
+  bytes: number;
+  ok: number;
+  size_only: number; // of ok: no recorded checksum, checked by size
+  issues: SessionVerifyIssue[];
+  cancelled: boolean; // files not reached are neither ok nor issues
+};
+
+// This matches Rust sessions::SessionInfo
+export type SessionInfo = {
+  session_dir: string;
+  day: string; // Transfers/<day>/<run>
+  run: string;
+  summary: TransferSummary; // from summary.json, else counted from the manifest
+  complete: boolean; // manifest written, nothing left to resume
+  active: boolean; // a running job is writing into it
+  error?: string | null; // manifest unreadable
+};
+
+// Every engine event payload carries this alongside its own fields (Rust events.rs)
+export const EVENT_SCHEMA_VERSION = 1;
+export type Versioned<T> = T & { schema_version: number };
+
+// Payload of transfer://job: lifecycle, separate from byte progress
+export type JobEvent = {
+  job_id: string;
+  kind: "transfer" | "offload" | "verify";
+  state: "queued" | "started" | "finished" | "failed";
+  error?: string | null;
 };
 
 export type TransferProgress = {
-  phase: "scanning" | "copying" | "verifying" | "done" | "cancelled" | "error";
+  job_id: string;
+  phase: "scanning" | "copying" | "verifying" | "paused" | "done" | "stopped" | "cancelled" | "error";
   current_file: number;   // 1-based
   total_files: number;
   current_path: string;
   bytes_done: number;
   bytes_total: number;
   percent: number;        // 0..=100
+  io?: IoStats | null;    // while copying
+  bytes_per_sec?: number; // smoothed (EWMA) throughput
+  eta_seconds?: number | null; // null until there's a rate to go on
+  elapsed_ms?: number;    // since the job started
+};
+
+// This matches Rust bottleneck::IoStats
+export type IoStats = {
+  read_bytes_per_sec: number;
+  write_bytes_per_sec: number;
+  bottleneck: "source" | "destination" | "balanced";
+  summary: string; // e.g. "destination-limited at 38 MB/s"
 };
 
 export type PickedItem = {
   kind: "file" | "folder";
   path: string;
+  contents_only?: boolean;
 };
 
 export type CopyMode = "copy" | "move";
-export type ConflictPolicy = "rename" | "overwrite" | "skip";
-export type VerifyMode = "none" | "size" | "sha256";
\ No newline at end of file
+// "newer": replace only if the source is newer; "sync": also replace on size change (mtimes compared with FS-aware tolerance)
+export type ConflictPolicy = "rename" | "overwrite" | "skip" | "newer" | "sync";
+export type VerifyMode = "none" | "size" | "xxh3" | "xxh64" | "blake3" | "sha256" | "md5" | "crc32";
+export type AuditEntry = {
+  seq: number;
+  at: string;
+  event: "transfer_start" | "transfer_cancel" | "source_delete" | "rollback" | string;
+  user: string;
+  details: Record<string, unknown>;
+  prev_hash: string;
+  hash: string;
+};
+
+export type AuditVerifyReport = {
+  entries: number;
+  valid: boolean;
+  first_bad_seq?: number | null;
+  message?: string | null;
+};
+
+export type StallEvent = {
+  job_id: string;
+  current_path: string;
+  stalled_secs: number;
+};
+
+export type StallAction = "retry" | "abort";
+
+// Payload of transfer://device-removed and transfer://device-restored
+export type DeviceEvent = {
+  job_id: string;
+  mount_point: string;
+  uuid?: string | null;
+};
+
+// Payload of transfer://on-complete
+export type CompletionEvent = {
+  job_id: string;
+  action: "reveal" | "hook" | "eject" | "auto_eject";
+  ok: boolean;
+  error?: string | null;
+};
+
+// This matches Rust errors::classify
+export type ErrorClass =
+  | "permission_denied"
+  | "disk_full"
+  | "device_removed"
+  | "path_too_long"
+  | "file_in_use"
+  | "read_only"
+  | "verify_mismatch"
+  | "network"
+  | "io_error"
+  | "other";
+
+export type ErrorSuggestion =
+  | "retry_elevated"
+  | "free_space"
+  | "reconnect_device"
+  | "shorten_path"
+  | "close_other_apps"
+  | "choose_writable_dest"
+  | "retry"
+  | "check_media"
+  | "none";
+
+// One manifest.json row
+export type ManifestItem = {
+  source: string;
+  dest: string;
+  category: string;
+  ext: string;
+  bytes: number;
+  status:
+    | "copied"
+    | "moved"
+    | "skipped"
+    | "error"
+    | "cancelled"
+    | "unverified" // copied; a hard cancel ended the batch verify pass before it
+    // dry runs only
+    | "would_copy"
+    | "would_move"
+    | "would_overwrite"
+    | "would_rename";
+  error?: string | null;
+  checksum?: string;
+  checksum_algo?: VerifyMode;
+  mtime?: string | null; // source modification time, RFC 3339
+  size_on_disk?: number | null;
+  permissions?: string | null; // octal, e.g. "644"
+  readonly: boolean;
+  error_class?: ErrorClass; // error rows only
+  suggestion?: ErrorSuggestion;
+  metadata_error?: string; // preserve_metadata couldn't restore everything (the copy is fine)
+  retries?: number; // attempts after the first; omitted when 0
+  copy_started_at?: string; // local RFC 3339, retries included
+  copy_finished_at?: string;
+};
+
+// How a session was made; the header of a v2 manifest.json
+// This matches Rust transfer::SessionHeader
+export type SessionHeader = {
+  app_version: string;
+  source_host: string; // machine the copy ran on
+  created_at: string;
+  copy_mode: CopyMode;
+  conflict_policy: ConflictPolicy;
+  verify_mode: VerifyMode;
+  settings: EngineOptions;
+};
+
+// manifest.json as written since version 2 (version 1 was a bare ManifestItem[])
+export type ManifestFile = {
+  manifest_version: 2;
+  header?: SessionHeader | null;
+  files: ManifestItem[];
+};
+
+// Payload of transfer://manifest-row, sent as each row is recorded. A row with the
+// same session_dir + row_index replaces the earlier one (e.g. a failed deferred sync).
+export type ManifestRowEvent = ManifestItem & {
+  job_id: string;
+  session_dir: string;
+  row_index: number;
+};
+
+// Payload of transfer://file-error. will_retry: the file is about to be tried
+// again; otherwise its manifest row is an error and the job moves on (cancel to abort).
+export type FileErrorEvent = {
+  job_id: string;
+  source: string;
+  dest: string;
+  error: string;
+  error_class: ErrorClass;
+  will_retry: boolean;
+  attempt: number; // which try failed, 1-based
+};
+
+// This matches Rust plan::TransferPlan
+export type TransferPlan = {
+  version?: number;
+  name?: string | null;
+  created_at?: string | null;
+  items: PickedItem[];
+  dest_mount_point?: string | null;
+  copy_mode?: CopyMode;
+  conflict_policy?: ConflictPolicy;
+  verify_mode?: VerifyMode;
+  options?: EngineOptions;
+};
+
+export type PlanImport = {
+  plan: TransferPlan;
+  missing_items: string[];
+};
+
+// This matches Rust profiles::Profile
+export type Profile = {
+  id: string; // "" to create
+  name: string;
+  card_uuid?: string | null; // auto-apply when this card is mounted
+  dest_mount_point?: string | null;
+  copy_mode?: CopyMode;
+  conflict_policy?: ConflictPolicy;
+  verify_mode?: VerifyMode;
+  options?: EngineOptions;
+};
+
+// Payload of profiles://card-matched
+export type CardMatched = {
+  profile: Profile;
+  mount_point: string;
+  job_id?: string | null; // set when background auto-import already started the copy
+};
+
+// This matches Rust daemon::DaemonSettings
+export type DaemonSettings = {
+  keep_running: boolean; // closing the window hides it to the tray
+  auto_import: boolean; // matched cards start copying without the UI
+};
+
+export type DaemonStatus = {
+  settings: DaemonSettings;
+  background_launch: boolean;
+  running_jobs: string[];
+};
+
+// This matches Rust transfer::OffloadReport (also written as offload_report.json)
+export type OffloadCopy = {
+  dest: string;
+  status: "copied" | "error" | "skipped" | "cancelled";
+  error?: string | null;
+  error_class?: ErrorClass;
+  suggestion?: ErrorSuggestion;
+};
+
+export type OffloadItem = {
+  source: string;
+  bytes: number;
+  checksum?: string | null;
+  primary: OffloadCopy;
+  backup: OffloadCopy;
+};
+
+export type OffloadReport = {
+  started_at: string;
+  finished_at: string;
+  duration_ms: number;
+  verify_mode: string;
+  total_files: number;
+  total_bytes: number;
+  verified_files: number; // verified on both drives
+  error_files: number;
+  skipped_files: number;
+  primary: TransferSummary;
+  backup: TransferSummary;
+  items: OffloadItem[];
+};
+
+// This matches Rust preview::ItemPreview
+export type ItemPreview = {
+  path: string;
+  name: string;
+  kind: "file" | "folder";
+  size_bytes: number;
+  file_count?: number | null; // folders only
+  modified?: string | null;
+  category: string;
+  ext: string;
+  captured_at?: string | null; // EXIF / container creation time
+  camera?: string | null;
+  thumbnail?: string | null; // data: URL
+  thumbnail_source?: "exif" | "system" | "file" | null;
+};
+
+// This matches Rust preview::FileInfo
+export type FileInfo = {
+  path: string;
+  name: string;
+  is_dir: boolean;
+  size_bytes: number;
+  created?: string | null;
+  modified?: string | null;
+  accessed?: string | null;
+  readonly: boolean;
+  permissions?: string | null; // octal, unix only
+  category: string;
+  ext: string;
+  mime: string; // from the extension
+  detected_type?: string | null; // from the file's magic bytes
+  exif?: {
+    make?: string | null;
+    model?: string | null;
+    lens?: string | null;
+    iso?: number | null;
+    taken_at?: string | null;
+  } | null;
+  audio?: {
+    artist?: string | null;
+    album_artist?: string | null;
+    album?: string | null;
+    title?: string | null;
+    track?: string | null;
+  } | null;
+  media_created?: string | null; // container creation time for clips
+};
+
+// This matches Rust dupes::DuplicateReport
+export type DuplicateGroup = {
+  checksum: string;
+  bytes: number; // size of each copy
+  paths: string[];
+  reclaimable_bytes: number; // bytes * (paths.length - 1)
+};
+
+export type DuplicateReport = {
+  root: string;
+  algo: string;
+  files_scanned: number;
+  bytes_scanned: number;
+  hashed_files: number;
+  cached_files: number; // digests reused from the hash cache
+  duplicate_files: number;
+  reclaimable_bytes: number;
+  groups: DuplicateGroup[]; // largest savings first
+};
+
+// Payload of dupes://progress
+export type DuplicateProgress = {
+  files_done: number;
+  files_total: number;
+  current_path: string;
+};
+
+// This matches Rust history::StatsRange (inclusive, "YYYY-MM-DD")
+export type StatsRange = {
+  from?: string | null;
+  to?: string | null;
+};
+
+export type MonthStats = {
+  month: string; // "YYYY-MM"
+  sessions: number;
+  files: number;
+  bytes: number;
+  error_files: number;
+};
+
+export type DeviceStats = {
+  device: string; // volume UUID, or mount point when unknown
+  mount_point: string; // where it was last seen
+  sessions: number;
+  files: number;
+  bytes: number;
+  error_files: number;
+  error_rate: number; // 0..=1
+  avg_bytes_per_sec: number;
+  last_used: string;
+};
+
+// This matches Rust history::TransferStats
+export type TransferStats = {
+  sessions: number;
+  files: number;
+  bytes: number;
+  error_files: number;
+  error_rate: number;
+  cancelled_sessions: number;
+  avg_bytes_per_sec: number;
+  by_month: MonthStats[]; // oldest first
+  by_device: DeviceStats[]; // most bytes first
+};
+
+// This matches Rust history_db::HistoryFilter
+export type HistoryFilter = {
+  from?: string | null; // inclusive, "YYYY-MM-DD"
+  to?: string | null;
+  device?: string | null; // volume UUID or mount point
+  filename?: string | null; // part of a file name
+  outcome?: "done" | "stopped" | "cancelled" | null;
+  limit?: number | null; // default: 200
+};
+
+// This matches Rust history::HistoryRecord plus the history_db id
+export type HistorySession = {
+  id: number;
+  job_id: string;
+  kind: "transfer" | "offload";
+  started_at: string;
+  finished_at: string;
+  duration_ms: number;
+  outcome: "done" | "stopped" | "cancelled";
+  session_dir: string;
+  dest_mount_point: string;
+  dest_uuid?: string | null;
+  copy_mode: string;
+  verify_mode: string;
+  total_files: number;
+  total_bytes: number;
+  copied_files: number;
+  moved_files: number;
+  skipped_files: number;
+  error_files: number;
+  matched_files: number; // files matching filter.filename
+};
+
+export type HistoryFile = {
+  name: string;
+  source: string;
+  dest: string;
+  bytes: number;
+  status: string;
+  error?: string | null;
+  error_class?: ErrorClass | null;
+  checksum?: string | null;
+  checksum_algo?: string | null;
+  retries: number;
+};
+
+// This matches Rust history_db::SessionDetail
+export type SessionDetail = HistorySession & {
+  settings?: EngineOptions | null; // null for sessions from before history.db
+  files: HistoryFile[];
+};
+
+// This matches Rust report::ReportOptions
+export type ReportOptions = {
+  label?: string | null; // default: session folder name
+  operator?: string | null; // default: logged-in user
+  pdf?: boolean; // needs wkhtmltopdf or a Chromium-based browser
+};
+
+export type DeliveryReport = {
+  html_path: string;
+  pdf_path?: string | null;
+  pdf_error?: string | null; // why no PDF was produced
+};
+
+// This matches Rust report::HashdeepExport
+export type HashdeepExport = {
+  path: string;
+  algo: "md5" | "sha256";
+  files: number;
+  rehashed: number; // hashed again from the drive (session verified with another algorithm)
+};
+
+// This matches Rust report::ManifestExport
+export type ManifestExport = {
+  path: string;
+  format: "csv";
+  rows: number;
+};
+
+// This matches Rust partials::PartialsReport
+export type PartialFile = {
+  path: string;
+  bytes: number;
+  session_dir: string;
+  reason: "cancelled" | "incomplete" | "unrecorded" | "temp";
+};
+
+export type PartialsReport = {
+  files: PartialFile[]; // still on disk
+  total_bytes: number;
+  removed_files: number;
+  removed_bytes: number;
+  active_sessions: string[]; // skipped: a running job is writing there
+};
+
+// This matches Rust quota::Quota (stored on the drive in Transfers/.transferpilot-quota.json)
+// This matches Rust permissions::PermissionReport
+export type MissingAccess = {
+  kind:
+    | "full_disk_access"
+    | "removable_volumes"
+    | "network_volumes"
+    | "desktop"
+    | "documents"
+    | "downloads"
+    | "files_and_folders";
+  path: string;
+  settings_url: string; // opens the matching System Settings pane
+};
+
+export type PermissionReport = {
+  checked: boolean;
+  full_disk_access?: boolean | null;
+  missing: MissingAccess[];
+};
+
+export type Quota = {
+  max_bytes: number;
+  action?: "warn" | "fail" | "trim"; // trim deletes the oldest finished sessions; default: warn
+};
+
+export type QuotaCheck = {
+  max_bytes: number;
+  action: "warn" | "fail" | "trim";
+  used_bytes: number;
+  incoming_bytes: number;
+  exceeded: boolean;
+  over_by: number;
+};